@@ -0,0 +1,418 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::game::{
+    blocks::Positioned as PositionedBlock,
+    board::Board,
+    moves::{FlatBoardMove, Step},
+};
+
+const NUM_CELLS: usize = (Board::ROWS * Board::COLS) as usize;
+
+// Index of the block covering each cell of the board, if any
+fn block_indices(board: &Board) -> [Option<usize>; NUM_CELLS] {
+    let mut indices = [None; NUM_CELLS];
+
+    for (block_idx, positioned_block) in board.blocks.iter().enumerate() {
+        for (row, col) in &positioned_block.range {
+            indices[usize::from(row * Board::COLS + col)] = Some(block_idx);
+        }
+    }
+
+    indices
+}
+
+// Cells adjacent to each side of the block towards which it is allowed to
+// move, where cells outside of the board are None
+fn sides(positioned_block: &PositionedBlock) -> Vec<Vec<Option<(u8, u8)>>> {
+    Step::ALL
+        .iter()
+        .filter(|step| positioned_block.allows(step))
+        .map(|step| positioned_block.side(step))
+        .collect()
+}
+
+// Group the cells which are neither walls nor covered by frozen blocks into
+// regions of cells connected through their edges, returning the region of each
+// cell, if any, along with the number of empty cells in each region. Blocks
+// which are not frozen can never cross a wall or a frozen block, so while the
+// frozen blocks stay in place, each block stays in its region and the number
+// of empty cells in each region never changes.
+fn regions(
+    board: &Board,
+    indices: &[Option<usize>; NUM_CELLS],
+    frozen: &HashSet<usize>,
+) -> ([Option<usize>; NUM_CELLS], Vec<usize>) {
+    let is_open = |row: u8, col: u8| {
+        !board.is_wall(row, col)
+            && !indices[usize::from(row * Board::COLS + col)]
+                .is_some_and(|block_idx| frozen.contains(&block_idx))
+    };
+
+    let mut cell_regions = [None; NUM_CELLS];
+    let mut num_empty = Vec::new();
+
+    for (row, col) in (0..Board::ROWS).flat_map(|row| (0..Board::COLS).map(move |col| (row, col))) {
+        let cell = usize::from(row * Board::COLS + col);
+
+        if cell_regions[cell].is_some() || !is_open(row, col) {
+            continue;
+        }
+
+        let region = num_empty.len();
+        num_empty.push(0);
+
+        cell_regions[cell] = Some(region);
+        let mut queue = VecDeque::from([(row, col)]);
+
+        while let Some((row, col)) = queue.pop_front() {
+            if indices[usize::from(row * Board::COLS + col)].is_none() {
+                num_empty[region] += 1;
+            }
+
+            let neighbours = [
+                row.checked_sub(1).map(|row| (row, col)),
+                (row + 1 < Board::ROWS).then_some((row + 1, col)),
+                col.checked_sub(1).map(|col| (row, col)),
+                (col + 1 < Board::COLS).then_some((row, col + 1)),
+            ];
+
+            for (row, col) in neighbours.into_iter().flatten() {
+                let neighbour = usize::from(row * Board::COLS + col);
+
+                if cell_regions[neighbour].is_none() && is_open(row, col) {
+                    cell_regions[neighbour] = Some(region);
+                    queue.push_back((row, col));
+                }
+            }
+        }
+    }
+
+    (cell_regions, num_empty)
+}
+
+// Find the blocks which can never move, whatever moves are made. A block can
+// move in a direction only once every cell next to it in that direction is
+// empty, other than those covered by blocks linked to it, so a block is frozen
+// if, on each of its sides, there is either the edge of the board, a wall, a
+// cell covered by another frozen block, or more cells in some region than
+// there are empty cells in that region. Starting with every block frozen,
+// blocks are unfrozen until every remaining block satisfies this condition.
+// Locked blocks are allowed to move towards none of their sides, so they are
+// never unfrozen.
+fn frozen_blocks(board: &Board) -> HashSet<usize> {
+    let indices = block_indices(board);
+
+    let mut frozen: HashSet<usize> = (0..board.blocks.len()).collect();
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        let (cell_regions, num_empty) = regions(board, &indices, &frozen);
+
+        for (block_idx, positioned_block) in board.blocks.iter().enumerate() {
+            if !frozen.contains(&block_idx) {
+                continue;
+            }
+
+            let is_linked = |other_idx: usize| {
+                positioned_block.link_group.is_some()
+                    && board.blocks[other_idx].link_group == positioned_block.link_group
+            };
+
+            let is_blocked = |side: &Vec<Option<(u8, u8)>>| {
+                let mut num_cells = vec![0; num_empty.len()];
+
+                side.iter().any(|cell| {
+                    let Some((row, col)) = cell else {
+                        return true;
+                    };
+
+                    let cell = usize::from(row * Board::COLS + col);
+
+                    if indices[cell].is_some_and(is_linked) {
+                        return frozen.contains(&indices[cell].unwrap());
+                    }
+
+                    let Some(region) = cell_regions[cell] else {
+                        return true;
+                    };
+
+                    num_cells[region] += 1;
+                    num_cells[region] > num_empty[region]
+                })
+            };
+
+            if !sides(positioned_block).iter().all(is_blocked) {
+                frozen.remove(&block_idx);
+                changed = true;
+            }
+        }
+    }
+
+    frozen
+}
+
+// Whether the board can be proven to be unsolvable without searching its moves.
+// The goal block can only move through cells which are neither walls nor
+// covered by frozen blocks, so the board is unsolvable if the goal block is
+// frozen away from the goal position, such as when there are never enough
+// empty cells around it to make room for it to move, or if the goal position cannot be reached
+// by moving the goal block, along its axis if it is constrained, through the
+// cells which are not covered by frozen blocks. Boards with targets are never
+// proven to be unsolvable.
+pub fn is_unsolvable(board: &Board) -> bool {
+    if !board.targets.is_empty() {
+        return false;
+    }
+
+    let Some(goal_idx) = board
+        .blocks
+        .iter()
+        .position(|positioned_block| positioned_block.block == board.goal.block)
+    else {
+        return false;
+    };
+
+    let goal_block = &board.blocks[goal_idx];
+    let start = (goal_block.min_position.row, goal_block.min_position.col);
+    let target = (board.goal.row, board.goal.col);
+
+    if start == target {
+        return false;
+    }
+
+    let frozen = frozen_blocks(board);
+
+    if frozen.contains(&goal_idx) {
+        return true;
+    }
+
+    let indices = block_indices(board);
+
+    let fits = |(row, col): (u8, u8)| {
+        PositionedBlock::new(goal_block.block, row, col).is_some_and(|positioned_block| {
+            positioned_block.range.iter().all(|(row, col)| {
+                !board.is_wall(*row, *col)
+                    && !indices[usize::from(row * Board::COLS + col)]
+                        .is_some_and(|block_idx| frozen.contains(&block_idx))
+            })
+        })
+    };
+
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some((row, col)) = queue.pop_front() {
+        if (row, col) == target {
+            return false;
+        }
+
+        let neighbours = Step::ALL
+            .iter()
+            .filter(|step| goal_block.allows(step))
+            .filter_map(|step| match step {
+                Step::Up => row.checked_sub(1).map(|row| (row, col)),
+                Step::Down => Some((row + 1, col)),
+                Step::Left => col.checked_sub(1).map(|col| (row, col)),
+                Step::Right => Some((row, col + 1)),
+            });
+
+        for position in neighbours {
+            if fits(position) && seen.insert(position) {
+                queue.push_back(position);
+            }
+        }
+    }
+
+    true
+}
+
+// Difficulty of the board given an optimal solution, as the number of bits
+// needed to choose each move of the solution among the moves available at
+// each step, so that it grows with both the length of the solution and the
+// branching factor of the boards along the way
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn difficulty(board: &Board, solution: &[FlatBoardMove]) -> u32 {
+    let mut board = board.clone();
+    let mut bits = 0.0;
+
+    for move_ in solution {
+        let num_moves: usize = board.get_next_moves().iter().map(Vec::len).sum();
+
+        bits += f64::from(u32::try_from(num_moves.max(1)).unwrap()).log2();
+
+        board.move_block_unchecked(move_.block_idx, move_.row_diff, move_.col_diff);
+    }
+
+    bits.round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{blocks::Block, board::State as BoardState, moves::FlatMove};
+
+    fn board(blocks: &[PositionedBlock]) -> Board {
+        let mut board = Board::default();
+
+        for block in blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board
+    }
+
+    #[test]
+    fn test_frozen_goal_block() {
+        let board = board(&[
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 4, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+        ]);
+
+        assert_eq!(frozen_blocks(&board).len(), board.blocks.len());
+        assert!(is_unsolvable(&board));
+
+        let mut board = board;
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert!(board.get_next_moves().iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn test_solvable_board() {
+        let board = board(&[
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ]);
+
+        assert!(frozen_blocks(&board).is_empty());
+        assert!(!is_unsolvable(&board));
+    }
+
+    #[test]
+    fn test_locked_block_in_path() {
+        let mut board = board(&[
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ]);
+
+        board.set_locked(4, true).unwrap();
+
+        assert_eq!(frozen_blocks(&board), HashSet::from([4]));
+        assert!(is_unsolvable(&board));
+    }
+
+    #[test]
+    fn test_wall_in_goal() {
+        let mut board = board(&[PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap()]);
+
+        assert!(!is_unsolvable(&board));
+
+        board.add_wall(4, 1).unwrap();
+
+        assert!(is_unsolvable(&board));
+    }
+
+    // Whether any board reached by moving the blocks of the given board has the
+    // block at the given index moved away from where it started
+    fn block_ever_moves(board: &Board, block_idx: usize) -> bool {
+        let mut board = board.clone();
+        board.change_state(BoardState::Solving).unwrap();
+
+        let start = board.blocks[block_idx].min_position.clone();
+
+        let mut seen = HashSet::from([board.key()]);
+        let mut queue = VecDeque::from([board]);
+
+        while let Some(mut board) = queue.pop_front() {
+            if board.blocks[block_idx].min_position != start {
+                return true;
+            }
+
+            for (idx, moves) in board.get_next_moves().into_iter().enumerate() {
+                for move_ in moves {
+                    board.move_block_unchecked(idx, move_.row_diff, move_.col_diff);
+
+                    if seen.insert(board.key()) {
+                        queue.push_back(board.clone());
+                    }
+
+                    board.undo_move_unchecked();
+                }
+            }
+        }
+
+        false
+    }
+
+    #[test]
+    fn test_no_room_in_region() {
+        let mut blocks = vec![PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap()];
+
+        for (row, col) in [(1, 0), (0, 3), (1, 3), (2, 1), (2, 2), (2, 3)]
+            .into_iter()
+            .chain((3..5).flat_map(|row| (0..4).map(move |col| (row, col))))
+            .filter(|&cell| cell != (4, 3))
+        {
+            blocks.push(PositionedBlock::new(Block::ONE_BY_ONE, row, col).unwrap());
+        }
+
+        let mut board = board(&blocks);
+
+        // There are two empty cells on the board, but the wall keeps the one
+        // next to the goal block's left side apart from the other
+        board.add_wall(2, 0).unwrap();
+
+        assert_eq!(frozen_blocks(&board), HashSet::from([0]));
+        assert!(is_unsolvable(&board));
+        assert!(!block_ever_moves(&board, 0));
+
+        board.remove_wall(2, 0).unwrap();
+        board.set_empty_cells(3).unwrap();
+
+        assert!(frozen_blocks(&board).is_empty());
+        assert!(!is_unsolvable(&board));
+        assert!(block_ever_moves(&board, 0));
+    }
+
+    #[test]
+    fn test_difficulty() {
+        let board = board(&[
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+        ]);
+
+        assert_eq!(difficulty(&board, &[]), 0);
+
+        let solution = [
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+        ];
+
+        assert!(difficulty(&board, &solution) > difficulty(&board, &solution[..1]));
+    }
+}
@@ -0,0 +1,832 @@
+use crate::game::{
+    blocks::{Axis, Block, Positioned as PositionedBlock},
+    board::{Board, Goal, State as BoardState, Target},
+    moves::{FlatMove, Step},
+    utils::Position,
+};
+
+pub const MAX_BLOCKS: usize = (Board::ROWS * Board::COLS - Board::MIN_EMPTY_CELLS) as usize;
+
+const FIRST_ROW: u64 = (1 << Board::COLS) - 1;
+const LAST_ROW: u64 = FIRST_ROW << ((Board::ROWS - 1) * Board::COLS);
+const FIRST_COL: u64 = 0x1_1111;
+const LAST_COL: u64 = FIRST_COL << (Board::COLS - 1);
+
+// Mask of the cells covered by the given block with its top-left corner at the
+// given cell, where the cell at row i and column j is bit i * COLS + j
+fn block_mask(block: Block, cell: u8) -> u64 {
+    block.cells().iter().fold(0, |mask, (row, col)| {
+        mask | (1 << (row * Board::COLS + col))
+    }) << cell
+}
+
+// Shift the mask by a single step, or return None if the mask would leave the
+// board
+fn shift(mask: u64, step: &Step) -> Option<u64> {
+    match step {
+        Step::Up => (mask & FIRST_ROW == 0).then_some(mask >> Board::COLS),
+        Step::Down => (mask & LAST_ROW == 0).then_some(mask << Board::COLS),
+        Step::Left => (mask & FIRST_COL == 0).then_some(mask >> 1),
+        Step::Right => (mask & LAST_COL == 0).then_some(mask << 1),
+    }
+}
+
+// Cells of the mask whose right neighbour is also in the mask
+fn joins_right(mask: u64) -> u64 {
+    mask & (mask >> 1) & !LAST_COL
+}
+
+// Cells of the mask whose bottom neighbour is also in the mask
+fn joins_down(mask: u64) -> u64 {
+    mask & (mask >> Board::COLS)
+}
+
+// Mask with the cells of each row in reverse order
+fn mirror(mask: u64) -> u64 {
+    ((mask & FIRST_COL) << 3)
+        | ((mask & (FIRST_COL << 1)) << 1)
+        | ((mask & (FIRST_COL << 2)) >> 1)
+        | ((mask & LAST_COL) >> 3)
+}
+
+// Mask of the given cells
+fn cells_mask(cells: &[Position]) -> u64 {
+    cells.iter().fold(0, |mask, cell| {
+        mask | (1 << (cell.row * Board::COLS + cell.col))
+    })
+}
+
+// Cells of the given mask, in row-major order
+fn mask_cells(mut mask: u64) -> Vec<Position> {
+    let mut cells = vec![];
+
+    while mask != 0 {
+        let cell = u8::try_from(mask.trailing_zeros()).unwrap();
+        cells.push(Position::new(cell / Board::COLS, cell % Board::COLS).unwrap());
+        mask &= mask - 1;
+    }
+
+    cells
+}
+
+// Spread the bits of the mask so that bit i is moved to bit 3 * i, matching
+// the layout of the board's hash
+fn spread(mut mask: u64) -> u64 {
+    let mut spread = 0;
+
+    while mask != 0 {
+        spread |= 1 << (3 * mask.trailing_zeros());
+        mask &= mask - 1;
+    }
+
+    spread
+}
+
+// Board representation used internally by the solver, where the cells covered
+// by each block are stored as a bitmask, along with the occupancy of the whole
+// board, including its walls, the cells joined to their right and bottom
+// neighbours by a block, the cells covered by blocks only allowed to move
+// horizontally or vertically, and the cells covered by linked blocks, along
+// with the link group of each block and the number of link groups. Moves are
+// generated by shifting the masks, and since the bitboard has a fixed size, it
+// can be copied rather than cloned. The blocks are in the same order as the
+// blocks of the board it was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitboard {
+    blocks: [(Block, u64); MAX_BLOCKS],
+    axes: [Option<Axis>; MAX_BLOCKS],
+    locked: [bool; MAX_BLOCKS],
+    link_groups: [Option<u8>; MAX_BLOCKS],
+    num_blocks: usize,
+    num_link_groups: u8,
+    walls: u64,
+    occupied: u64,
+    right: u64,
+    down: u64,
+    horizontal: u64,
+    vertical: u64,
+    linked_cells: u64,
+    goal: Goal,
+    // block and mask of the cells of each of the board's targets
+    targets: [(Block, u64); Board::MAX_TARGETS],
+    num_targets: usize,
+    empty_cells: u8,
+}
+
+impl From<&Board> for Bitboard {
+    fn from(board: &Board) -> Self {
+        let mut bitboard = Self {
+            blocks: [(Block::ONE_BY_ONE, 0); MAX_BLOCKS],
+            axes: [None; MAX_BLOCKS],
+            locked: [false; MAX_BLOCKS],
+            link_groups: [None; MAX_BLOCKS],
+            num_blocks: board.blocks.len(),
+            num_link_groups: u8::try_from(board.num_link_groups()).unwrap(),
+            walls: board.walls.iter().fold(0, |walls, wall| {
+                walls | (1 << (wall.row * Board::COLS + wall.col))
+            }),
+            occupied: 0,
+            right: 0,
+            down: 0,
+            horizontal: 0,
+            vertical: 0,
+            linked_cells: 0,
+            goal: board.goal,
+            targets: [(Block::ONE_BY_ONE, 0); Board::MAX_TARGETS],
+            num_targets: board.targets.len(),
+            empty_cells: board.empty_cells,
+        };
+
+        for (i, target) in board.targets.iter().enumerate() {
+            bitboard.targets[i] = (target.block, cells_mask(&target.cells));
+        }
+
+        for (i, positioned_block) in board.blocks.iter().enumerate() {
+            let cell =
+                positioned_block.min_position.row * Board::COLS + positioned_block.min_position.col;
+
+            bitboard.blocks[i] = (
+                positioned_block.block,
+                block_mask(positioned_block.block, cell),
+            );
+            bitboard.axes[i] = positioned_block.axis;
+            bitboard.locked[i] = positioned_block.locked;
+            bitboard.link_groups[i] = positioned_block.link_group;
+        }
+
+        bitboard.update_occupancy();
+
+        bitboard
+    }
+}
+
+impl From<&Bitboard> for Board {
+    fn from(bitboard: &Bitboard) -> Self {
+        let mut walls = bitboard.walls;
+        let mut board = Board {
+            goal: bitboard.goal,
+            targets: bitboard.targets[..bitboard.num_targets]
+                .iter()
+                .map(|&(block, mask)| Target {
+                    block,
+                    cells: mask_cells(mask),
+                })
+                .collect(),
+            empty_cells: bitboard.empty_cells,
+            ..Board::default()
+        };
+
+        while walls != 0 {
+            let cell = u8::try_from(walls.trailing_zeros()).unwrap();
+
+            board
+                .add_wall(cell / Board::COLS, cell % Board::COLS)
+                .unwrap();
+
+            walls &= walls - 1;
+        }
+
+        for i in 0..bitboard.num_blocks {
+            let (block, cell) = (bitboard.blocks[i].0, bitboard.cell(i));
+
+            board
+                .add_block(
+                    PositionedBlock::new(block, cell / Board::COLS, cell % Board::COLS)
+                        .unwrap()
+                        .with_axis(bitboard.axes[i])
+                        .with_locked(bitboard.locked[i])
+                        .with_link_group(bitboard.link_groups[i]),
+                )
+                .unwrap();
+        }
+
+        let _is_solving = board.change_state(BoardState::Solving).is_ok();
+        let _is_solved = board.change_state(BoardState::Solved).is_ok();
+
+        board
+    }
+}
+
+impl Bitboard {
+    fn update_occupancy(&mut self) {
+        self.occupied = self.walls;
+        self.right = 0;
+        self.down = 0;
+        self.horizontal = 0;
+        self.vertical = 0;
+        self.linked_cells = 0;
+
+        for (i, &(_, mask)) in self.blocks[..self.num_blocks].iter().enumerate() {
+            self.occupied |= mask;
+            self.right |= joins_right(mask);
+            self.down |= joins_down(mask);
+
+            match self.axes[i] {
+                Some(Axis::Horizontal) => self.horizontal |= mask,
+                Some(Axis::Vertical) => self.vertical |= mask,
+                None => {}
+            }
+
+            if self.link_groups[i].is_some() {
+                self.linked_cells |= mask;
+            }
+        }
+    }
+
+    // Indices of the blocks which move along with the block at the given index
+    fn linked_indices(&self, block_idx: usize) -> Vec<usize> {
+        let Some(link_group) = self.link_groups[block_idx] else {
+            return vec![block_idx];
+        };
+
+        (0..self.num_blocks)
+            .filter(|&i| self.link_groups[i] == Some(link_group))
+            .collect()
+    }
+
+    // Whether the block at the given index, along with the blocks linked to it,
+    // is allowed to take the step
+    fn allows(&self, block_idx: usize, step: &Step) -> bool {
+        self.linked_indices(block_idx)
+            .into_iter()
+            .all(|i| !self.locked[i] && self.axes[i].into_iter().all(|axis| axis.allows(step)))
+    }
+
+    // Index of the top-left cell of the bounding box of the block at the given
+    // index, which is not covered by the block if it is L-shaped
+    pub fn cell(&self, block_idx: usize) -> u8 {
+        let (block, mask) = self.blocks[block_idx];
+
+        u8::try_from(mask.trailing_zeros() - block_mask(block, 0).trailing_zeros()).unwrap()
+    }
+
+    // Copy of the bitboard with the top-left cell of each block replaced by
+    // the given cells
+    pub fn with_cells(&self, cells: &[u8]) -> Self {
+        let mut bitboard = *self;
+
+        for (i, cell) in cells.iter().take(self.num_blocks).enumerate() {
+            bitboard.blocks[i].1 = block_mask(bitboard.blocks[i].0, *cell);
+        }
+
+        bitboard.update_occupancy();
+
+        bitboard
+    }
+
+    pub fn cells(&self) -> [u8; MAX_BLOCKS] {
+        let mut cells = [0; MAX_BLOCKS];
+
+        for (i, cell) in cells.iter_mut().enumerate().take(self.num_blocks) {
+            *cell = self.cell(i);
+        }
+
+        cells
+    }
+
+    pub fn is_solved(&self) -> bool {
+        if self.num_targets > 0 {
+            return self.targets[..self.num_targets]
+                .iter()
+                .all(|&(target_block, target_mask)| {
+                    self.blocks[..self.num_blocks]
+                        .iter()
+                        .any(|&(block, mask)| block == target_block && mask & !target_mask == 0)
+                });
+        }
+
+        let goal_mask = block_mask(self.goal.block, self.goal.row * Board::COLS + self.goal.col);
+
+        self.blocks[..self.num_blocks]
+            .iter()
+            .any(|&(block, mask)| block == self.goal.block && mask == goal_mask)
+    }
+
+    // Pack the same cell codes as the board's hash, of either the bitboard or
+    // its mirror image. A cell of the mirror image is joined to the cell to its
+    // right if the mirrored cell is joined to the cell to its left. With linked
+    // blocks, the hash is mixed from the key in the same way as the board's.
+    fn encode(&self, mirrored: bool) -> u64 {
+        if self.num_link_groups > 0 {
+            return Board::digest_key(self.encode_key(mirrored));
+        }
+
+        let reflect = |mask: u64| if mirrored { mirror(mask) } else { mask };
+        let constrained = self.horizontal | self.vertical;
+
+        spread(reflect(self.occupied & !self.walls & !constrained))
+            + spread(reflect(self.right & !constrained) >> u8::from(mirrored))
+            + 2 * spread(reflect(self.down & !constrained))
+            + 5 * spread(reflect(self.walls))
+            + 6 * spread(reflect(self.horizontal))
+            + 7 * spread(reflect(self.vertical))
+    }
+
+    // Same key as the board's key, of either the bitboard or its mirror image
+    fn encode_key(&self, mirrored: bool) -> u128 {
+        if self.num_link_groups == 0 {
+            return u128::from(self.encode(mirrored));
+        }
+
+        let reflect = |mask: u64| if mirrored { mirror(mask) } else { mask };
+        let right = reflect(self.right) >> u8::from(mirrored);
+        let down = reflect(self.down);
+
+        let mut codes = [0; (Board::ROWS * Board::COLS) as usize];
+        let mut link_groups = [None; (Board::ROWS * Board::COLS) as usize];
+
+        for (i, &(_, mask)) in self.blocks[..self.num_blocks].iter().enumerate() {
+            for cell in mask_cells(reflect(mask)) {
+                link_groups[usize::from(cell.row * Board::COLS + cell.col)] = self.link_groups[i];
+            }
+        }
+
+        for (cell, code) in codes.iter_mut().enumerate() {
+            let is_set = |mask: u64| mask & (1 << cell) != 0;
+            let joins = u64::from(is_set(right)) + 2 * u64::from(is_set(down));
+
+            *code = if is_set(reflect(self.walls)) {
+                5
+            } else if is_set(reflect(self.horizontal)) {
+                if is_set(reflect(self.linked_cells)) {
+                    12
+                } else {
+                    6
+                }
+            } else if is_set(reflect(self.vertical)) {
+                if is_set(reflect(self.linked_cells)) {
+                    13
+                } else {
+                    7
+                }
+            } else if is_set(reflect(self.linked_cells)) {
+                8 + joins
+            } else if is_set(reflect(self.occupied)) {
+                1 + joins
+            } else {
+                0
+            };
+        }
+
+        Board::pack_key(&codes, &link_groups)
+    }
+
+    // Same value as the hash of the equivalent board
+    pub fn hash(&self) -> u64 {
+        self.encode(false)
+    }
+
+    // Same value as the canonical hash of the equivalent board
+    pub fn canonical_hash(&self) -> u64 {
+        if !self.is_mirrorable() {
+            return self.hash();
+        }
+
+        self.hash().min(self.encode(true))
+    }
+
+    // Same value as the key of the equivalent board
+    pub fn key(&self) -> u128 {
+        self.encode_key(false)
+    }
+
+    // Same value as the canonical key of the equivalent board
+    pub fn canonical_key(&self) -> u128 {
+        if !self.is_mirrorable() {
+            return self.key();
+        }
+
+        self.key().min(self.encode_key(true))
+    }
+
+    // Whether the bitboard shares its canonical hash and key with its mirror
+    // image, in the same cases as the equivalent board
+    fn is_mirrorable(&self) -> bool {
+        self.goal.is_symmetric()
+            && self.num_targets == 0
+            && !self.locked[..self.num_blocks].contains(&true)
+    }
+
+    // Same moves, in the same order, as the next moves of the equivalent board.
+    // A move of up to empty_cells steps is valid if the block's mask does not
+    // overlap the other blocks or the walls after each step, nor return to a
+    // mask visited earlier in the move. Each move is stored with the mask it
+    // leads to and the index of the move which it extends by one step.
+    pub fn get_next_moves(&self) -> Vec<Vec<FlatMove>> {
+        (0..self.num_blocks)
+            .map(|block_idx| {
+                let mask = self
+                    .linked_indices(block_idx)
+                    .into_iter()
+                    .fold(0, |mask, i| mask | self.blocks[i].1);
+                let others = self.occupied & !mask;
+
+                let mut paths = vec![(mask, FlatMove::from_steps(&[]), None)];
+                let mut frontier = 0..1;
+
+                for _ in 0..self.empty_cells {
+                    for parent_idx in frontier.clone() {
+                        let (from, move_, _) = paths[parent_idx].clone();
+
+                        for step in &Step::ALL {
+                            if !self.allows(block_idx, step) {
+                                continue;
+                            }
+
+                            let Some(shifted) = shift(from, step) else {
+                                continue;
+                            };
+
+                            let mut path_idx = Some(parent_idx);
+                            let mut revisits = false;
+
+                            while let Some(idx) = path_idx {
+                                revisits |= paths[idx].0 == shifted;
+                                path_idx = paths[idx].2;
+                            }
+
+                            if shifted & others == 0 && !revisits {
+                                let next_move = FlatMove {
+                                    row_diff: move_.row_diff + step.row_diff(),
+                                    col_diff: move_.col_diff + step.col_diff(),
+                                };
+
+                                paths.push((shifted, next_move, Some(parent_idx)));
+                            }
+                        }
+                    }
+
+                    frontier = frontier.end..paths.len();
+                }
+
+                let mut moves: Vec<FlatMove> = paths
+                    .into_iter()
+                    .skip(1)
+                    .map(|(_, move_, _)| move_)
+                    .collect();
+
+                moves.dedup();
+                moves
+            })
+            .collect()
+    }
+
+    pub fn move_block(&mut self, block_idx: usize, move_: &FlatMove) {
+        let mask = self.blocks[block_idx].1;
+
+        let diff = i32::from(move_.row_diff) * i32::from(Board::COLS) + i32::from(move_.col_diff);
+        let shifted = |mask: u64| {
+            if diff >= 0 {
+                mask << diff
+            } else {
+                mask >> -diff
+            }
+        };
+
+        // The masks of the whole link group are shifted at once, so the
+        // occupancy is recomputed rather than updated for a single block
+        if self.link_groups[block_idx].is_some() {
+            for i in self.linked_indices(block_idx) {
+                self.blocks[i].1 = shifted(self.blocks[i].1);
+            }
+
+            self.update_occupancy();
+            return;
+        }
+
+        let new_mask = shifted(mask);
+
+        self.blocks[block_idx].1 = new_mask;
+        self.occupied = (self.occupied & !mask) | new_mask;
+        self.right = (self.right & !joins_right(mask)) | joins_right(new_mask);
+        self.down = (self.down & !joins_down(mask)) | joins_down(new_mask);
+
+        match self.axes[block_idx] {
+            Some(Axis::Horizontal) => self.horizontal = (self.horizontal & !mask) | new_mask,
+            Some(Axis::Vertical) => self.vertical = (self.vertical & !mask) | new_mask,
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::blocks::Corner;
+    use std::collections::{HashSet, VecDeque};
+
+    // Check that the bitboard of every board reachable from the given board
+    // matches the board
+    fn assert_matches_board(board: Board) {
+        let mut seen = HashSet::from([board.key()]);
+        let mut queue = VecDeque::from([board]);
+
+        while let Some(mut board) = queue.pop_front() {
+            let bitboard = Bitboard::from(&board);
+
+            assert_eq!(bitboard.hash(), board.hash());
+            assert_eq!(bitboard.canonical_hash(), board.canonical_hash());
+            assert_eq!(bitboard.key(), board.key());
+            assert_eq!(bitboard.canonical_key(), board.canonical_key());
+            assert_eq!(bitboard.is_solved(), board.is_solved());
+            assert_eq!(Board::from(&bitboard).hash(), board.hash());
+            assert_eq!(
+                Bitboard::from(&board).with_cells(&bitboard.cells()),
+                bitboard
+            );
+
+            let next_moves = board.get_next_moves();
+
+            assert_eq!(bitboard.get_next_moves(), next_moves);
+
+            for (block_idx, moves) in next_moves.into_iter().enumerate() {
+                for move_ in moves {
+                    board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                    let mut moved = bitboard;
+                    moved.move_block(block_idx, &move_);
+
+                    assert_eq!(moved, Bitboard::from(&board));
+
+                    if seen.insert(board.key()) {
+                        let mut next_board = board.clone();
+                        next_board.moves.clear();
+
+                        queue.push_back(next_board);
+                    }
+
+                    board.undo_move_unchecked();
+                }
+            }
+        }
+
+        assert!(seen.len() > 1000);
+    }
+
+    #[test]
+    fn test_matches_board() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_targets() {
+        let mut board = Board::default();
+        board
+            .add_block(PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap())
+            .unwrap();
+
+        let cells = (0..Board::ROWS)
+            .flat_map(|row| {
+                [
+                    Position::new(row, 2).unwrap(),
+                    Position::new(row, 3).unwrap(),
+                ]
+            })
+            .collect();
+
+        board
+            .set_targets(vec![Target {
+                block: Block::TWO_BY_TWO,
+                cells,
+            }])
+            .unwrap();
+
+        let bitboard = Bitboard::from(&board);
+
+        assert!(!bitboard.is_solved());
+        assert_eq!(Board::from(&bitboard).targets, board.targets);
+        assert_eq!(bitboard.canonical_hash(), board.canonical_hash());
+
+        board.move_block_unchecked(0, 2, 1);
+
+        assert!(board.is_solved());
+        assert!(Bitboard::from(&board).is_solved());
+    }
+
+    #[test]
+    fn test_matches_board_with_walls() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.add_wall(4, 0).unwrap();
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_matches_board_with_l_shapes() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::LShape(Corner::TopRight), 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::LShape(Corner::BottomLeft), 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_matches_board_with_rectangles() {
+        let blocks = [
+            PositionedBlock::new(Block::Rectangle { rows: 1, cols: 3 }, 0, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 1, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_matches_board_with_three_empty_cells() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.set_empty_cells(3).unwrap();
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_matches_board_with_constrained_blocks() {
+        let blocks = [
+            PositionedBlock::new(Block::Rectangle { rows: 3, cols: 1 }, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1)
+                .unwrap()
+                .with_axis(Some(Axis::Vertical)),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3)
+                .unwrap()
+                .with_axis(Some(Axis::Horizontal)),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_matches_board_with_locked_blocks() {
+        let blocks = [
+            PositionedBlock::new(Block::Rectangle { rows: 3, cols: 1 }, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3)
+                .unwrap()
+                .with_locked(true),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+
+    #[test]
+    fn test_matches_board_with_linked_blocks() {
+        let blocks = [
+            PositionedBlock::new(Block::Rectangle { rows: 3, cols: 1 }, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.link_blocks(&[9, 12]).unwrap();
+        board.link_blocks(&[2, 3]).unwrap();
+        board.change_state(BoardState::Solving).unwrap();
+
+        assert_matches_board(board);
+    }
+}
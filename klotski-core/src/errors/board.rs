@@ -0,0 +1,59 @@
+use std::error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    BlockIndexOutOfBounds,
+    BlockInvalid,
+    BlockPlacementInvalid,
+    BoardExpired,
+    BoardNotFound,
+    BoardsIncompatible,
+    BoardStateInvalid,
+    DifficultyNotFound,
+    DurationInvalid,
+    EmptyCellsInvalid,
+    LayoutNotFound,
+    LinkInvalid,
+    MetadataInvalid,
+    MoveIndexOutOfBounds,
+    NoMovesToUndo,
+    NotationInvalid,
+    PuzzleMismatch,
+    SolutionInvalid,
+    TargetsInvalid,
+    VersionConflict,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BlockIndexOutOfBounds => write!(f, "Block index is out of bounds"),
+            Error::BlockInvalid => write!(f, "Block ID provided is invalid"),
+            Error::BlockPlacementInvalid => write!(f, "Block placement is invalid"),
+            Error::BoardExpired => write!(f, "Board has expired"),
+            Error::BoardNotFound => write!(f, "No board with matching ID"),
+            Error::BoardsIncompatible => write!(f, "Boards do not have the same blocks"),
+            Error::BoardStateInvalid => write!(f, "Board state is invalid for operation"),
+            Error::DifficultyNotFound => {
+                write!(f, "No random board of the requested difficulty was found")
+            }
+            Error::DurationInvalid => {
+                write!(f, "Board was solved too quickly for its number of moves")
+            }
+            Error::EmptyCellsInvalid => write!(f, "Number of empty cells is invalid"),
+            Error::LayoutNotFound => write!(f, "No layout with matching name"),
+            Error::LinkInvalid => write!(f, "Linked blocks must be at least two blocks"),
+            Error::MetadataInvalid => write!(f, "Board name, description or tags are invalid"),
+            Error::MoveIndexOutOfBounds => write!(f, "Move index is out of bounds"),
+            Error::NoMovesToUndo => write!(f, "No board moves to undo"),
+            Error::NotationInvalid => write!(f, "Board notation is invalid"),
+            Error::PuzzleMismatch => write!(f, "Board was not started from the puzzle"),
+            Error::SolutionInvalid => write!(f, "Moves do not solve the board"),
+            Error::TargetsInvalid => write!(f, "Goal targets are invalid"),
+            Error::VersionConflict => write!(f, "Board has been changed since the given version"),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use std::error;
+use std::fmt;
+
+use crate::errors::board::Error as BoardError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    BoardError(BoardError),
+    BudgetExceeded,
+    Cancelled,
+    DepthExceeded,
+    TimedOut,
+}
+
+impl error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BoardError(err) => write!(f, "{err}"),
+            Error::BudgetExceeded => write!(f, "Solver node budget exceeded"),
+            Error::Cancelled => write!(f, "Solver cancelled"),
+            Error::DepthExceeded => write!(f, "Solver maximum depth exceeded"),
+            Error::TimedOut => write!(f, "Solver timed out"),
+        }
+    }
+}
+
+impl From<BoardError> for Error {
+    fn from(e: BoardError) -> Self {
+        Error::BoardError(e)
+    }
+}
@@ -0,0 +1,372 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{moves::Step, utils::Position};
+use crate::errors::board::Error as BoardError;
+
+// Corner of a block's bounding box
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Corner {
+    // Corner in the same position of the horizontal mirror image
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::TopLeft => Self::TopRight,
+            Self::TopRight => Self::TopLeft,
+            Self::BottomLeft => Self::BottomRight,
+            Self::BottomRight => Self::BottomLeft,
+        }
+    }
+
+    // Corner in the same position once rotated a quarter turn clockwise
+    pub fn rotated(self) -> Self {
+        match self {
+            Self::TopLeft => Self::TopRight,
+            Self::TopRight => Self::BottomRight,
+            Self::BottomRight => Self::BottomLeft,
+            Self::BottomLeft => Self::TopLeft,
+        }
+    }
+}
+
+// The rectangular block covers the given number of rows and columns, and the
+// L-shaped block covers the 2x2 bounding box except for the given corner
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Block {
+    Rectangle { rows: u8, cols: u8 },
+    LShape(Corner),
+}
+
+impl Block {
+    pub const ONE_BY_ONE: Self = Self::Rectangle { rows: 1, cols: 1 };
+    pub const ONE_BY_TWO: Self = Self::Rectangle { rows: 1, cols: 2 };
+    pub const TWO_BY_ONE: Self = Self::Rectangle { rows: 2, cols: 1 };
+    pub const TWO_BY_TWO: Self = Self::Rectangle { rows: 2, cols: 2 };
+
+    pub fn rows(self) -> u8 {
+        match self {
+            Self::Rectangle { rows, .. } => rows,
+            Self::LShape(_) => 2,
+        }
+    }
+
+    pub fn cols(self) -> u8 {
+        match self {
+            Self::Rectangle { cols, .. } => cols,
+            Self::LShape(_) => 2,
+        }
+    }
+
+    pub fn size(self) -> u8 {
+        u8::try_from(self.cells().len()).unwrap()
+    }
+
+    // Whether the block covers the cell at the given row and column, relative
+    // to the top-left corner of its bounding box
+    pub fn covers(self, row: u8, col: u8) -> bool {
+        let missing = match self {
+            Self::LShape(Corner::TopLeft) => Some((0, 0)),
+            Self::LShape(Corner::TopRight) => Some((0, 1)),
+            Self::LShape(Corner::BottomLeft) => Some((1, 0)),
+            Self::LShape(Corner::BottomRight) => Some((1, 1)),
+            Self::Rectangle { .. } => None,
+        };
+
+        row < self.rows() && col < self.cols() && missing != Some((row, col))
+    }
+
+    // Cells covered by the block, relative to the top-left corner of its
+    // bounding box
+    pub fn cells(self) -> Vec<(u8, u8)> {
+        (0..self.rows())
+            .flat_map(|row| (0..self.cols()).map(move |col| (row, col)))
+            .filter(|(row, col)| self.covers(*row, *col))
+            .collect()
+    }
+
+    // Block in the same position of the horizontal mirror image
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::LShape(corner) => Self::LShape(corner.mirrored()),
+            Self::Rectangle { .. } => self,
+        }
+    }
+
+    // Block rotated a quarter turn clockwise
+    pub fn rotated(self) -> Self {
+        match self {
+            Self::LShape(corner) => Self::LShape(corner.rotated()),
+            Self::Rectangle { rows, cols } => Self::Rectangle {
+                rows: cols,
+                cols: rows,
+            },
+        }
+    }
+}
+
+// Axis along which a block is allowed to move
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+impl Axis {
+    // Axis once rotated a quarter turn
+    pub fn rotated(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+
+    pub fn allows(self, step: &Step) -> bool {
+        match self {
+            Self::Horizontal => step.row_diff() == 0,
+            Self::Vertical => step.col_diff() == 0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = PositionedBlock)]
+pub struct Positioned {
+    pub block: Block,
+    pub min_position: Position,
+    pub max_position: Position,
+    pub range: Vec<(u8, u8)>,
+    // axis along which the block is allowed to move, if it cannot move freely
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub axis: Option<Axis>,
+    // whether the block is pinned in place and cannot move at all
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub locked: bool,
+    // group of linked blocks the block belongs to, with the other blocks of
+    // which it always moves as a single unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_group: Option<u8>,
+    // label and color with which clients display the block, which have no
+    // effect on how it moves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl Display for Positioned {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Block({:?}@{})", self.block, self.min_position)
+    }
+}
+
+impl Positioned {
+    fn range(block: Block, min_position: &Position) -> Vec<(u8, u8)> {
+        block
+            .cells()
+            .into_iter()
+            .map(|(row, col)| (min_position.row + row, min_position.col + col))
+            .collect()
+    }
+
+    pub fn new(block: Block, min_row: u8, min_col: u8) -> Option<Self> {
+        let min_position = Position::new(min_row, min_col)?;
+
+        // Blocks without any rows or columns do not fit anywhere on the board
+        let max_position = Position::new(
+            min_row.checked_add(block.rows().checked_sub(1)?)?,
+            min_col.checked_add(block.cols().checked_sub(1)?)?,
+        )?;
+
+        Some(Self {
+            block,
+            range: Self::range(block, &min_position),
+            min_position,
+            max_position,
+            axis: None,
+            locked: false,
+            link_group: None,
+            label: None,
+            color: None,
+        })
+    }
+
+    pub fn with_axis(mut self, axis: Option<Axis>) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    pub fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    pub fn with_link_group(mut self, link_group: Option<u8>) -> Self {
+        self.link_group = link_group;
+        self
+    }
+
+    pub fn with_display(mut self, label: Option<String>, color: Option<String>) -> Self {
+        self.label = label;
+        self.color = color;
+        self
+    }
+
+    // Whether the block is allowed to take the given step
+    pub fn allows(&self, step: &Step) -> bool {
+        !self.locked && self.axis.into_iter().all(|axis| axis.allows(step))
+    }
+
+    pub fn move_by(&mut self, row_diff: i8, col_diff: i8) -> Result<(), BoardError> {
+        let mut new_min_position = self.min_position.clone();
+        let mut new_max_position = self.max_position.clone();
+
+        new_min_position.move_by(row_diff, col_diff)?;
+        new_max_position.move_by(row_diff, col_diff)?;
+
+        self.range = Self::range(self.block, &new_min_position);
+        self.min_position = new_min_position;
+        self.max_position = new_max_position;
+
+        Ok(())
+    }
+
+    pub fn do_step(&mut self, step: &Step) -> Result<(), BoardError> {
+        self.move_by(step.row_diff(), step.col_diff())
+    }
+
+    pub fn undo_step(&mut self, step: &Step) -> Result<(), BoardError> {
+        self.do_step(&step.opposite())
+    }
+
+    // Cells which the block would newly cover by taking the given step, where
+    // cells outside of the board are None
+    pub fn side(&self, step: &Step) -> Vec<Option<(u8, u8)>> {
+        self.range
+            .iter()
+            .map(|(row, col)| {
+                row.checked_add_signed(step.row_diff())
+                    .zip(col.checked_add_signed(step.col_diff()))
+            })
+            .filter(|cell| !cell.is_some_and(|cell| self.range.contains(&cell)))
+            .map(|cell| cell.filter(|(row, col)| Position::new(*row, *col).is_some()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{moves::Step, utils::Position};
+
+    #[test]
+    fn rotated_blocks() {
+        assert_eq!(Block::ONE_BY_TWO.rotated(), Block::TWO_BY_ONE);
+        assert_eq!(Block::TWO_BY_TWO.rotated(), Block::TWO_BY_TWO);
+        assert_eq!(
+            Block::LShape(Corner::TopLeft).rotated(),
+            Block::LShape(Corner::TopRight)
+        );
+
+        let block = Block::LShape(Corner::BottomLeft);
+
+        assert_eq!(block.rotated().rotated().rotated().rotated(), block);
+    }
+
+    #[test]
+    fn valid_positioned_blocks() {
+        assert!(
+            Positioned::new(Block::ONE_BY_ONE, 0, 0).is_some()
+                && Positioned::new(Block::ONE_BY_ONE, Position::MAX_ROW, Position::MAX_COL)
+                    .is_some()
+        );
+    }
+
+    #[test]
+    fn invalid_positioned_blocks() {
+        assert!(
+            Positioned::new(Block::TWO_BY_TWO, Position::MAX_ROW, Position::MAX_COL).is_none()
+                && Positioned::new(Block::ONE_BY_ONE, 0, Position::MAX_COL + 1).is_none()
+        );
+    }
+
+    #[test]
+    fn rectangle_positioned_blocks() {
+        let block = Positioned::new(Block::Rectangle { rows: 1, cols: 3 }, 4, 1).unwrap();
+
+        assert_eq!(block.range, vec![(4, 1), (4, 2), (4, 3)]);
+        assert_eq!(block.block.size(), 3);
+
+        assert!(Positioned::new(Block::Rectangle { rows: 5, cols: 4 }, 0, 0).is_some());
+        assert!(Positioned::new(Block::Rectangle { rows: 3, cols: 1 }, 3, 0).is_none());
+        assert!(Positioned::new(Block::Rectangle { rows: 0, cols: 1 }, 0, 0).is_none());
+        assert!(Positioned::new(Block::Rectangle { rows: 1, cols: 255 }, 0, 1).is_none());
+    }
+
+    #[test]
+    fn positioned_block_max_position() {
+        let block_one = Positioned::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        let block_two = Positioned::new(Block::TWO_BY_TWO, 0, 1).unwrap();
+
+        assert!(
+            block_one.max_position == Position::new(0, 0).unwrap()
+                && block_two.max_position == Position::new(1, 2).unwrap()
+        );
+    }
+
+    #[test]
+    fn l_shape_range() {
+        let block = Positioned::new(Block::LShape(Corner::TopLeft), 0, 0).unwrap();
+
+        assert_eq!(block.range, vec![(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(block.max_position, Position::new(1, 1).unwrap());
+        assert_eq!(block.block.size(), 3);
+        assert!(Positioned::new(Block::LShape(Corner::TopLeft), Position::MAX_ROW, 0).is_none());
+    }
+
+    #[test]
+    fn l_shape_side() {
+        let block = Positioned::new(Block::LShape(Corner::BottomRight), 1, 1).unwrap();
+
+        assert_eq!(block.side(&Step::Down), vec![Some((2, 2)), Some((3, 1))]);
+        assert_eq!(block.side(&Step::Right), vec![Some((1, 3)), Some((2, 2))]);
+
+        let block = Positioned::new(Block::LShape(Corner::TopRight), 0, 0).unwrap();
+
+        assert_eq!(block.side(&Step::Up), vec![None, Some((0, 1))]);
+    }
+
+    #[test]
+    fn positioned_block_do_step() {
+        let mut block_one = Positioned::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        let res = block_one.do_step(&Step::Down);
+
+        assert!(res.is_ok());
+
+        let block_two = Positioned::new(Block::ONE_BY_ONE, 1, 0).unwrap();
+
+        assert_eq!(block_one, block_two);
+    }
+
+    #[test]
+    fn positioned_block_undo_step() {
+        let mut block_two = Positioned::new(Block::ONE_BY_ONE, 0, 1).unwrap();
+        let res = block_two.undo_step(&Step::Right);
+
+        assert!(res.is_ok());
+
+        let block_one = Positioned::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+
+        assert_eq!(block_one, block_two);
+    }
+}
@@ -0,0 +1,4078 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{
+    blocks::{Axis, Block, Positioned as PositionedBlock},
+    history::{Action as EditAction, Edit, Event as EditEvent},
+    moves::{DetailedMove, FlatBoardMove, FlatMove, Step},
+};
+use crate::{errors::board::Error as BoardError, game::utils::Position};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = BoardState)]
+#[serde(rename_all = "snake_case")]
+pub enum State {
+    Building,
+    ReadyToSolve,
+    Solving,
+    Solved,
+    // retired board, which can still be read but no longer changed
+    Archived,
+}
+
+// Block which must be moved to the given position for the board to be solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Goal {
+    pub block: Block,
+    pub row: u8,
+    pub col: u8,
+}
+
+impl Default for Goal {
+    fn default() -> Self {
+        Self {
+            block: Board::WINNING_BLOCK,
+            row: Board::WINNING_ROW,
+            col: Board::WINNING_COL,
+        }
+    }
+}
+
+impl Goal {
+    // Goal is valid if the block fits on the board at the goal position
+    pub fn is_valid(self) -> bool {
+        PositionedBlock::new(self.block, self.row, self.col).is_some()
+    }
+
+    // Goal is symmetric if its block and position are unchanged by mirroring
+    // the board horizontally
+    pub fn is_symmetric(self) -> bool {
+        self.block.mirrored() == self.block
+            && self.col + self.block.cols() + self.col == Board::COLS
+    }
+}
+
+// Shape of the board chosen when creating it, where the notched board walls
+// off the two outer cells of the bottom row, leaving a notch at the bottom
+// middle through which the goal block exits
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Variant {
+    #[default]
+    Classic,
+    Notched,
+}
+
+impl Variant {
+    // Cells which are walls on every board of the variant
+    pub fn walls(self) -> Vec<Position> {
+        match self {
+            Self::Classic => vec![],
+            Self::Notched => vec![
+                Position::new(Board::ROWS - 1, 0).unwrap(),
+                Position::new(Board::ROWS - 1, Board::COLS - 1).unwrap(),
+            ],
+        }
+    }
+}
+
+// Cells within which a block of the given shape must lie for the board to be
+// solved, e.g. the cells along one side of the board
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Target {
+    pub block: Block,
+    pub cells: Vec<Position>,
+}
+
+impl Target {
+    // Target is valid if its cells are on the board and the block fits within
+    // them at some position
+    pub fn is_valid(&self) -> bool {
+        self.cells
+            .iter()
+            .all(|cell| Position::new(cell.row, cell.col).is_some())
+            && (0..Board::ROWS)
+                .flat_map(|row| (0..Board::COLS).map(move |col| (row, col)))
+                .filter_map(|(row, col)| PositionedBlock::new(self.block, row, col))
+                .any(|positioned_block| self.is_met_by(&positioned_block))
+    }
+
+    // Whether the block has the target's shape and lies within its cells
+    pub fn is_met_by(&self, positioned_block: &PositionedBlock) -> bool {
+        positioned_block.block == self.block
+            && positioned_block.range.iter().all(|(row, col)| {
+                self.cells
+                    .iter()
+                    .any(|cell| cell.row == *row && cell.col == *col)
+            })
+    }
+}
+
+// Reason why a board is not yet ready to be solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum NotReadyReason {
+    // the board needs exactly one block of the goal block's shape, but has
+    // the given number of them
+    GoalBlockCount { count: usize },
+    // the given number of cells must still be covered by blocks or walls
+    CellsFree { count: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    pub id: i32,
+    // name, description and tags given to the board by its users
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub state: State,
+    pub blocks: Vec<PositionedBlock>,
+    // index in blocks of the block covering each cell, if any
+    pub grid: [Option<usize>; (Self::ROWS * Self::COLS) as usize],
+    // immovable cells which no block can cover
+    pub walls: Vec<Position>,
+    // variant the board was created as, whose walls cannot be removed
+    pub variant: Variant,
+    pub moves: Vec<FlatBoardMove>,
+    // time, in milliseconds since the Unix epoch, at which each move was made,
+    // if known, which is recorded when the board is saved
+    pub move_times: Vec<Option<u64>>,
+    // changes made to the board's blocks while building it, in the order
+    // they were made
+    pub edits: Vec<Edit>,
+    pub goal: Goal,
+    // cells which blocks must reach for the board to be solved, replacing the
+    // goal position if there are any
+    pub targets: Vec<Target>,
+    // number of cells which must be left empty once every block is added,
+    // which is also the most steps a block can be moved in a single move
+    pub empty_cells: u8,
+    // times, in milliseconds since the Unix epoch, at which the board started
+    // being solved and at which it was solved
+    pub started_solving_at: Option<u64>,
+    pub finished_at: Option<u64>,
+    // difficulty of solving the board from its starting position, known once
+    // it has been solved optimally and forgotten whenever it is rebuilt
+    pub difficulty: Option<u32>,
+    // seed from which the board's starting position was randomly generated,
+    // if it was, forgotten whenever it is rebuilt
+    pub seed: Option<u64>,
+    // number of times the saved board has been changed, used to reject changes
+    // made to an out-of-date copy of the board
+    pub version: u32,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new(
+            0,
+            State::Building,
+            vec![],
+            [None; (Self::COLS * Self::ROWS) as usize],
+            vec![],
+            vec![],
+            Goal::default(),
+        )
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Board(ID:{}, State:{:?}, Blocks:[", self.id, self.state)?;
+        for (i, block) in self.blocks.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{block}")?;
+        }
+        write!(f, "])")
+    }
+}
+
+impl Board {
+    pub const ROWS: u8 = 5;
+    pub const COLS: u8 = 4;
+    pub const MIN_EMPTY_CELLS: u8 = 2;
+    pub const MAX_EMPTY_CELLS: u8 = 4;
+    pub const MAX_TARGETS: usize = 4;
+    pub const MAX_NAME_LEN: usize = 100;
+    pub const MAX_DESCRIPTION_LEN: usize = 1000;
+    pub const MAX_TAGS: usize = 10;
+    pub const MAX_TAG_LEN: usize = 32;
+
+    pub const WINNING_BLOCK: Block = Block::TWO_BY_TWO;
+    pub const WINNING_ROW: u8 = 3;
+    pub const WINNING_COL: u8 = 1;
+
+    fn num_cells_free(&self) -> usize {
+        self.grid.iter().filter(|cell| cell.is_none()).count()
+            - self.walls.len()
+            - usize::from(self.empty_cells)
+    }
+
+    // Reasons why the board cannot yet be solved, which are empty once the
+    // board is ready to be solved
+    pub fn readiness_report(&self) -> Vec<NotReadyReason> {
+        let mut reasons = vec![];
+
+        let num_goal_blocks = self
+            .blocks
+            .iter()
+            .filter(|positioned_block| positioned_block.block == self.goal.block)
+            .count();
+
+        if num_goal_blocks != 1 {
+            reasons.push(NotReadyReason::GoalBlockCount {
+                count: num_goal_blocks,
+            });
+        }
+
+        let num_cells_free = self.num_cells_free();
+
+        if num_cells_free > 0 {
+            reasons.push(NotReadyReason::CellsFree {
+                count: num_cells_free,
+            });
+        }
+
+        reasons
+    }
+
+    fn is_ready_to_solve(&self) -> bool {
+        self.readiness_report().is_empty()
+    }
+
+    fn update_grid_range(&mut self, range: &[(u8, u8)], value: Option<usize>) {
+        range
+            .iter()
+            .for_each(|(i, j)| self.grid[usize::from(i * Self::COLS + j)] = value);
+    }
+
+    fn is_range_empty(&self, range: &[(u8, u8)]) -> bool {
+        range.iter().all(|(i, j)| self.is_cell_empty(*i, *j))
+    }
+
+    // Cell is empty if it is neither covered by a block nor a wall
+    fn is_cell_empty(&self, row: u8, col: u8) -> bool {
+        self.grid[usize::from(row * Self::COLS + col)].is_none() && !self.is_wall(row, col)
+    }
+
+    // Step is valid for the block, moved away from the origin by earlier steps
+    // of the same move, if every cell which it would newly cover is on the
+    // board and either empty or covered by the block at its origin, or by a
+    // block in the same link group, which moves along with it
+    fn is_step_valid_for_block(
+        &self,
+        origin: &PositionedBlock,
+        block: &PositionedBlock,
+        step: &Step,
+    ) -> bool {
+        block.side(step).iter().all(|cell| {
+            cell.is_some_and(|(row, col)| {
+                self.is_cell_empty(row, col)
+                    || origin.range.contains(&(row, col))
+                    || (origin.link_group.is_some()
+                        && self.grid[usize::from(row * Self::COLS + col)].is_some_and(
+                            |block_idx| self.blocks[block_idx].link_group == origin.link_group,
+                        ))
+            })
+        })
+    }
+
+    // Indices of the blocks which move along with the block at the given
+    // index, which are every block in its link group if the block is linked
+    fn linked_indices(&self, block_idx: usize) -> Vec<usize> {
+        let Some(link_group) = self.blocks[block_idx].link_group else {
+            return vec![block_idx];
+        };
+
+        (0..self.blocks.len())
+            .filter(|&other_idx| self.blocks[other_idx].link_group == Some(link_group))
+            .collect()
+    }
+
+    // Blocks which move along with the given block
+    fn linked_blocks(&self, block: &PositionedBlock) -> Vec<PositionedBlock> {
+        if block.link_group.is_none() {
+            return vec![block.clone()];
+        }
+
+        self.blocks
+            .iter()
+            .filter(|other| other.link_group == block.link_group)
+            .cloned()
+            .collect()
+    }
+
+    // Whether each of the blocks, moved away from their origins by earlier
+    // steps of the same move, is allowed to take the step and the step is
+    // valid for it
+    fn is_step_valid_for_blocks(
+        &self,
+        origins: &[PositionedBlock],
+        blocks: &[PositionedBlock],
+        step: &Step,
+    ) -> bool {
+        origins.iter().zip(blocks).all(|(origin, block)| {
+            block.allows(step) && self.is_step_valid_for_block(origin, block, step)
+        })
+    }
+
+    // Move the block at the given index, along with the blocks linked to it,
+    // by the given row and column difference without any error checking
+    fn move_linked_blocks(&mut self, block_idx: usize, row_diff: i8, col_diff: i8) {
+        let block_idxs = self.linked_indices(block_idx);
+
+        for &idx in &block_idxs {
+            let range = self.blocks[idx].range.clone();
+            self.update_grid_range(&range, None);
+        }
+
+        for &idx in &block_idxs {
+            self.blocks[idx].move_by(row_diff, col_diff).unwrap();
+
+            let range = self.blocks[idx].range.clone();
+            self.update_grid_range(&range, Some(idx));
+        }
+    }
+
+    // Whether taking the next step after the given steps would return the block
+    // to a position which it has already visited, including its origin
+    fn revisits(steps: &[Step], next_step: &Step) -> bool {
+        let mut row_diff = next_step.row_diff();
+        let mut col_diff = next_step.col_diff();
+
+        steps.iter().rev().any(|step| {
+            row_diff += step.row_diff();
+            col_diff += step.col_diff();
+
+            row_diff == 0 && col_diff == 0
+        })
+    }
+
+    // Moves of up to empty_cells steps, where each step of a move is valid and
+    // does not return the block to a position visited earlier in the move.
+    // Moves are ordered by their number of steps, and moves with the same
+    // number of steps by the move which they extend.
+    fn get_next_moves_for_block(&self, block: &PositionedBlock) -> Vec<FlatMove> {
+        self.get_next_paths_for_block(block)
+            .iter()
+            .map(|move_| FlatMove::from_steps(move_))
+            .collect()
+    }
+
+    // Sequence of steps of each move the block, along with the blocks linked
+    // to it, can make, in the order they are found, where moves reached by
+    // several paths appear once per path
+    fn get_next_paths_for_block(&self, block: &PositionedBlock) -> Vec<Vec<Step>> {
+        let mut moves: Vec<Vec<Step>> = vec![];
+        let mut frontier: Vec<Vec<Step>> = vec![vec![]];
+
+        let origins = self.linked_blocks(block);
+        let mut blocks = origins.clone();
+
+        for _ in 0..self.empty_cells {
+            let mut next_frontier = vec![];
+
+            for steps in &frontier {
+                for step in steps {
+                    for block in &mut blocks {
+                        block.do_step(step).unwrap();
+                    }
+                }
+
+                for ref next_step in Step::ALL {
+                    if Self::revisits(steps, next_step) {
+                        continue;
+                    }
+
+                    // Every cell newly covered by a valid step is on the board,
+                    // so the blocks can take the step
+                    if self.is_step_valid_for_blocks(&origins, &blocks, next_step) {
+                        let mut new_move = steps.clone();
+                        new_move.push(next_step.clone());
+
+                        next_frontier.push(new_move);
+                    }
+                }
+
+                for step in steps.iter().rev() {
+                    for block in &mut blocks {
+                        block.undo_step(step).unwrap();
+                    }
+                }
+            }
+
+            moves.extend(next_frontier.iter().cloned());
+            frontier = next_frontier;
+        }
+
+        moves
+    }
+}
+
+impl Board {
+    // Grid of the cells covered by the given blocks, for boards whose grid is
+    // stored only in its packed form
+    pub fn grid_from_blocks(
+        blocks: &[PositionedBlock],
+    ) -> [Option<usize>; (Self::ROWS * Self::COLS) as usize] {
+        let mut grid = [None; (Self::ROWS * Self::COLS) as usize];
+
+        for (block_idx, positioned_block) in blocks.iter().enumerate() {
+            for (row, col) in &positioned_block.range {
+                grid[usize::from(row * Self::COLS + col)] = Some(block_idx);
+            }
+        }
+
+        grid
+    }
+
+    pub fn new(
+        id: i32,
+        state: State,
+        blocks: Vec<PositionedBlock>,
+        grid: [Option<usize>; (Self::COLS * Self::ROWS) as usize],
+        walls: Vec<Position>,
+        moves: Vec<FlatBoardMove>,
+        goal: Goal,
+    ) -> Self {
+        Self {
+            id,
+            name: None,
+            description: None,
+            tags: vec![],
+            state,
+            blocks,
+            grid,
+            walls,
+            variant: Variant::default(),
+            moves,
+            move_times: vec![],
+            edits: vec![],
+            goal,
+            targets: vec![],
+            empty_cells: Self::MIN_EMPTY_CELLS,
+            started_solving_at: None,
+            finished_at: None,
+            difficulty: None,
+            seed: None,
+            version: 0,
+        }
+    }
+
+    // Code of each cell, where empty cells are 0, walls are 5, and each cell
+    // covered by a block is 1, plus 1 if the cell to its right and 2 if the
+    // cell below it are covered by the same block, so that the codes describe
+    // how the covered cells are split into blocks whatever their shapes. Each
+    // block is found from the first cell it covers in row-major order. Cells
+    // covered by blocks only allowed to move horizontally or vertically are 6
+    // or 7 instead. Since these blocks never leave their rows or columns nor
+    // pass each other, the codes still tell apart the boards reachable from one
+    // another, but not boards with differently split constrained blocks.
+    // Cells covered by linked blocks are 5 like walls, so the codes alone
+    // cannot tell apart walls from linked blocks, nor the blocks of separate
+    // link groups from one another, which is left to the board's key.
+    fn encode_cells(&self) -> [u64; (Self::ROWS * Self::COLS) as usize] {
+        let mut codes = [0; (Self::ROWS * Self::COLS) as usize];
+
+        for cell in 0..Self::ROWS * Self::COLS {
+            let (row, col) = (cell / Self::COLS, cell % Self::COLS);
+
+            if self.is_wall(row, col) {
+                codes[usize::from(cell)] = 5;
+            }
+
+            let Some(block) =
+                self.grid[usize::from(cell)].map(|block_idx| self.blocks[block_idx].block)
+            else {
+                continue;
+            };
+
+            if codes[usize::from(cell)] != 0 {
+                continue;
+            }
+
+            // Only an L-shaped block missing its top-left corner does not cover
+            // the top-left corner of its bounding box
+            let min_col = col - u8::from(!block.covers(0, 0));
+
+            for block_row in 0..block.rows() {
+                for block_col in 0..block.cols() {
+                    if block.covers(block_row, block_col) {
+                        let cell = (row + block_row) * Self::COLS + min_col + block_col;
+
+                        codes[usize::from(cell)] = 1
+                            + u64::from(block.covers(block_row, block_col + 1))
+                            + 2 * u64::from(block.covers(block_row + 1, block_col));
+                    }
+                }
+            }
+        }
+
+        for positioned_block in &self.blocks {
+            let code = match positioned_block.axis {
+                Some(Axis::Horizontal) => 6,
+                Some(Axis::Vertical) => 7,
+                None => continue,
+            };
+
+            for (row, col) in &positioned_block.range {
+                codes[usize::from(row * Self::COLS + col)] = code;
+            }
+        }
+
+        for positioned_block in self
+            .blocks
+            .iter()
+            .filter(|block| block.link_group.is_some())
+        {
+            for (row, col) in &positioned_block.range {
+                codes[usize::from(row * Self::COLS + col)] = 5;
+            }
+        }
+
+        codes
+    }
+
+    // Pack the code of each cell into 3 bits
+    fn pack_codes(codes: &[u64]) -> u64 {
+        codes.iter().rev().fold(0, |acc, code| (acc << 3) | code)
+    }
+
+    // Codes of the cells of the board's key, which are the codes of its hash,
+    // except that cells covered by linked blocks are 8, plus 1 if the cell to
+    // their right and 2 if the cell below them are covered by the same block,
+    // or 12 or 13 if the block is only allowed to move horizontally or
+    // vertically, so that linked blocks are split into blocks the same way as
+    // the other blocks and are told apart from walls
+    fn encode_key_cells(&self) -> [u64; (Self::ROWS * Self::COLS) as usize] {
+        let mut codes = self.encode_cells();
+
+        for positioned_block in self
+            .blocks
+            .iter()
+            .filter(|block| block.link_group.is_some())
+        {
+            let (block, min_position) = (positioned_block.block, &positioned_block.min_position);
+
+            for (block_row, block_col) in block.cells() {
+                let cell =
+                    (min_position.row + block_row) * Self::COLS + min_position.col + block_col;
+
+                codes[usize::from(cell)] = match positioned_block.axis {
+                    Some(Axis::Horizontal) => 12,
+                    Some(Axis::Vertical) => 13,
+                    None => {
+                        8 + u64::from(block.covers(block_row, block_col + 1))
+                            + 2 * u64::from(block.covers(block_row + 1, block_col))
+                    }
+                };
+            }
+        }
+
+        codes
+    }
+
+    // Link group of the block covering each cell, if it is linked
+    fn cell_link_groups(&self) -> [Option<u8>; (Self::ROWS * Self::COLS) as usize] {
+        let mut link_groups = [None; (Self::ROWS * Self::COLS) as usize];
+
+        for positioned_block in &self.blocks {
+            for (row, col) in &positioned_block.range {
+                link_groups[usize::from(row * Self::COLS + col)] = positioned_block.link_group;
+            }
+        }
+
+        link_groups
+    }
+
+    // Values of the cells with the cells of each row in reverse order
+    fn mirror_cells<T: Copy>(
+        cells: &[T; (Self::ROWS * Self::COLS) as usize],
+    ) -> [T; (Self::ROWS * Self::COLS) as usize] {
+        let mut mirrored_cells = *cells;
+
+        for (cell, mirrored_cell) in mirrored_cells.iter_mut().enumerate() {
+            let (row, col) = (
+                cell / usize::from(Self::COLS),
+                cell % usize::from(Self::COLS),
+            );
+
+            *mirrored_cell =
+                cells[row * usize::from(Self::COLS) + usize::from(Self::COLS) - 1 - col];
+        }
+
+        mirrored_cells
+    }
+
+    // Codes of the cells of the mirror image, where a cell of the mirror image
+    // is joined to the cell to its right if the mirrored cell is joined to the
+    // cell to its left
+    fn mirror_codes(
+        codes: &[u64; (Self::ROWS * Self::COLS) as usize],
+    ) -> [u64; (Self::ROWS * Self::COLS) as usize] {
+        let mut mirrored_codes = Self::mirror_cells(codes);
+
+        for (cell, code) in mirrored_codes.iter_mut().enumerate() {
+            let base = match *code {
+                1..=4 => 1,
+                8..=11 => 8,
+                _ => continue,
+            };
+
+            let col = usize::from(Self::COLS) - 1 - cell % usize::from(Self::COLS);
+            let mirrored_cell = cell - cell % usize::from(Self::COLS) + col;
+
+            let joins_left =
+                col > 0 && matches!(codes[mirrored_cell - 1].checked_sub(base), Some(1 | 3));
+
+            *code = base + u64::from(joins_left) + 2 * ((*code - base) >> 1);
+        }
+
+        mirrored_codes
+    }
+
+    // Exact key of a board with linked blocks from the key codes and link
+    // groups of its cells. The codes are packed in base 14 into the low 77
+    // bits, followed by the rank of the split of the linked cells into link
+    // groups. Groups are numbered in the order of their first cell, so that
+    // the rank does not depend on the ids of the groups, and the number of
+    // each linked cell's group, in row-major order, is at most one more than
+    // the numbers before it, which gives the radix of each digit of the rank.
+    // Since there are at most 18 linked cells in at most 9 groups, the rank is
+    // below 10! * 10^8 < 2^49. The top bit is set, so that these keys are told
+    // apart from the keys of boards without linked blocks.
+    pub fn pack_key(codes: &[u64], link_groups: &[Option<u8>]) -> u128 {
+        let packed_codes = codes
+            .iter()
+            .rev()
+            .fold(0, |acc, &code| acc * 14 + u128::from(code));
+
+        let mut numbered_groups: Vec<u8> = vec![];
+        let (mut rank, mut radix) = (0_u128, 1_u128);
+
+        for &link_group in link_groups.iter().flatten() {
+            let number = numbered_groups
+                .iter()
+                .position(|&numbered_group| numbered_group == link_group)
+                .unwrap_or(numbered_groups.len());
+
+            rank += radix * u128::try_from(number).unwrap();
+            radix *= u128::try_from(numbered_groups.len() + 1).unwrap();
+
+            if number == numbered_groups.len() {
+                numbered_groups.push(link_group);
+            }
+        }
+
+        (1 << 127) | (rank << 77) | packed_codes
+    }
+
+    // Hash of a board with linked blocks, mixed from the two halves of its key
+    pub fn digest_key(key: u128) -> u64 {
+        [key >> 64, key & u128::from(u64::MAX)]
+            .into_iter()
+            .fold(0, |hash, word| {
+                let mut mixed =
+                    (hash ^ u64::try_from(word).unwrap()).wrapping_add(0x9E37_79B9_7F4A_7C15);
+                mixed = (mixed ^ (mixed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                mixed = (mixed ^ (mixed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+                mixed ^ (mixed >> 31)
+            })
+    }
+
+    // Board hash implemented as an exact encoding of the board's blocks and
+    // walls, where the code of each cell is packed into 3 bits, so that
+    // distinct boards without constrained blocks can never share the same hash.
+    // The hash also serves as a compact encoding of the board's grid. Boards
+    // with linked blocks do not fit into 64 bits, so their hash is mixed from
+    // their key, and the key is used wherever boards must be told apart.
+    pub fn hash(&self) -> u64 {
+        if self.num_link_groups() > 0 {
+            return Self::digest_key(self.key());
+        }
+
+        Self::pack_codes(&self.encode_cells())
+    }
+
+    // Hash of the board's horizontal mirror image
+    pub fn mirrored_hash(&self) -> u64 {
+        if self.num_link_groups() > 0 {
+            return Self::digest_key(self.mirrored_key());
+        }
+
+        Self::pack_codes(&Self::mirror_codes(&self.encode_cells()))
+    }
+
+    // Exact key of the board, which is its hash unless the board has linked
+    // blocks, in which case it also tells apart how the linked blocks are split
+    // into blocks and link groups. Searches key the boards they have seen by
+    // it, so that boards sharing a hash by chance are never taken for one
+    // another.
+    pub fn key(&self) -> u128 {
+        if self.num_link_groups() == 0 {
+            return u128::from(self.hash());
+        }
+
+        Self::pack_key(&self.encode_key_cells(), &self.cell_link_groups())
+    }
+
+    // Key of the board's horizontal mirror image
+    pub fn mirrored_key(&self) -> u128 {
+        if self.num_link_groups() == 0 {
+            return u128::from(self.mirrored_hash());
+        }
+
+        Self::pack_key(
+            &Self::mirror_codes(&self.encode_key_cells()),
+            &Self::mirror_cells(&self.cell_link_groups()),
+        )
+    }
+
+    // Whether any block is locked or only allowed to move along an axis
+    pub fn has_constrained_blocks(&self) -> bool {
+        self.blocks.iter().any(|positioned_block| {
+            positioned_block.locked
+                || positioned_block.link_group.is_some()
+                || positioned_block.axis.is_some()
+        })
+    }
+
+    // Whether any block is locked in place
+    pub fn has_locked_blocks(&self) -> bool {
+        self.blocks
+            .iter()
+            .any(|positioned_block| positioned_block.locked)
+    }
+
+    // Number of distinct groups of linked blocks
+    pub fn num_link_groups(&self) -> usize {
+        self.blocks
+            .iter()
+            .filter_map(|positioned_block| positioned_block.link_group)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    // Whether the cell at the given row and column is a wall
+    pub fn is_wall(&self, row: u8, col: u8) -> bool {
+        self.walls
+            .iter()
+            .any(|wall| wall.row == row && wall.col == col)
+    }
+
+    // Text grid of the board with one line per row, where the cells covered by
+    // each block are marked with a letter in the order the blocks were added,
+    // walls are marked with '#', and empty cells with '.'
+    pub fn render_text(&self) -> String {
+        let mut cells = [b'.'; (Self::ROWS * Self::COLS) as usize];
+
+        for wall in &self.walls {
+            cells[usize::from(wall.row * Self::COLS + wall.col)] = b'#';
+        }
+
+        for (letter, positioned_block) in (b'A'..=b'Z').zip(&self.blocks) {
+            for (row, col) in &positioned_block.range {
+                cells[usize::from(row * Self::COLS + col)] = letter;
+            }
+        }
+
+        let mut text = String::new();
+
+        for row in cells.chunks(usize::from(Self::COLS)) {
+            text.push_str(&String::from_utf8_lossy(row));
+            text.push('\n');
+        }
+
+        text
+    }
+
+    // Hash shared by the board and its horizontal mirror image. When the
+    // goal position is in the center columns and the board has no targets, a
+    // board and its mirror image need the same number of moves to be solved.
+    // Otherwise, the board's exact hash is used. It is also used if any block
+    // is locked, since locked cells share the codes of free cells, so a board
+    // could share its mirror image's hash with a board whose locked block is
+    // on the other side.
+    pub fn canonical_hash(&self) -> u64 {
+        if !self.goal.is_symmetric() || !self.targets.is_empty() || self.has_locked_blocks() {
+            return self.hash();
+        }
+
+        self.hash().min(self.mirrored_hash())
+    }
+
+    // Key shared by the board and its horizontal mirror image, in the same
+    // cases as the canonical hash
+    pub fn canonical_key(&self) -> u128 {
+        if !self.goal.is_symmetric() || !self.targets.is_empty() || self.has_locked_blocks() {
+            return self.key();
+        }
+
+        self.key().min(self.mirrored_key())
+    }
+
+    // Logic for changing the board's state
+    pub fn change_state(&mut self, new_state: State) -> Result<(), BoardError> {
+        if self.state == new_state {
+            return Ok(());
+        }
+
+        match (self.state, new_state) {
+            (State::Building, State::ReadyToSolve) => {
+                if !self.is_ready_to_solve() {
+                    return Err(BoardError::BoardStateInvalid);
+                }
+            }
+            (State::ReadyToSolve, State::Building | State::Solving) | (_, State::Archived) => {}
+            (State::Solving, State::ReadyToSolve) => {
+                if !self.moves.is_empty() {
+                    return Err(BoardError::BoardStateInvalid);
+                }
+            }
+            (State::Solving, State::Solved) => {
+                if !self.is_solved() {
+                    return Err(BoardError::BoardStateInvalid);
+                }
+            }
+            (State::Solved, State::Solving) => {
+                if self.is_solved() {
+                    return Err(BoardError::BoardStateInvalid);
+                }
+            }
+            _ => {
+                return Err(BoardError::BoardStateInvalid);
+            }
+        }
+
+        // the board's layout may change while it is being built
+        if new_state == State::Building {
+            self.difficulty = None;
+            self.seed = None;
+        }
+
+        self.state = new_state;
+
+        Ok(())
+    }
+
+    // Board is solved if the goal block is in the goal position, or if the
+    // board has targets, if each target is met by one of its blocks
+    pub fn is_solved(&self) -> bool {
+        if !self.targets.is_empty() {
+            return self.targets.iter().all(|target| {
+                self.blocks
+                    .iter()
+                    .any(|positioned_block| target.is_met_by(positioned_block))
+            });
+        }
+
+        self.blocks.iter().any(|block| {
+            block.block == self.goal.block
+                && block.min_position.row == self.goal.row
+                && block.min_position.col == self.goal.col
+        })
+    }
+
+    // Empty board in the building state with the walls of the given variant
+    pub fn from_variant(variant: Variant) -> Self {
+        Self {
+            walls: variant.walls(),
+            variant,
+            ..Self::default()
+        }
+    }
+
+    // Board in the building state with the given blocks added in order. If any
+    // block cannot be added, the error of the first such block is returned.
+    pub fn try_from_blocks(blocks: Vec<PositionedBlock>) -> Result<Self, BoardError> {
+        Self::try_from_variant_blocks(Variant::Classic, blocks)
+    }
+
+    // Board of the given variant in the building state with the given blocks
+    // added in order, as by try_from_blocks
+    pub fn try_from_variant_blocks(
+        variant: Variant,
+        blocks: Vec<PositionedBlock>,
+    ) -> Result<Self, BoardError> {
+        let mut board = Self::from_variant(variant);
+
+        for positioned_block in blocks {
+            board.add_block(positioned_block)?;
+        }
+
+        Ok(board)
+    }
+
+    // Change the board's goal while in the building state. If the goal block
+    // does not fit on the board at the goal position, the
+    // BlockPlacementInvalid error is returned.
+    pub fn set_goal(&mut self, goal: Goal) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        if !goal.is_valid() {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        self.goal = goal;
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Change the board's targets while in the building state, where no targets
+    // restores the goal position as the condition for the board to be solved.
+    // If there are more than MAX_TARGETS targets, or any target has cells off
+    // the board or no room for its block, the TargetsInvalid error is returned.
+    pub fn set_targets(&mut self, targets: Vec<Target>) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        if targets.len() > Self::MAX_TARGETS || !targets.iter().all(Target::is_valid) {
+            return Err(BoardError::TargetsInvalid);
+        }
+
+        self.targets = targets;
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Change the board's name, description and tags in any state but the
+    // archived state. If the name or description is too long, or there are
+    // too many tags or any tag is empty, too long or repeated, the
+    // MetadataInvalid error is returned.
+    pub fn set_metadata(
+        &mut self,
+        name: Option<String>,
+        description: Option<String>,
+        tags: Vec<String>,
+    ) -> Result<(), BoardError> {
+        if self.state == State::Archived {
+            return Err(BoardError::BoardStateInvalid);
+        }
+
+        let is_valid_tag = |(i, tag): (usize, &String)| {
+            (1..=Self::MAX_TAG_LEN).contains(&tag.chars().count()) && !tags[..i].contains(tag)
+        };
+
+        if name
+            .as_ref()
+            .is_some_and(|name| name.chars().count() > Self::MAX_NAME_LEN)
+            || description
+                .as_ref()
+                .is_some_and(|description| description.chars().count() > Self::MAX_DESCRIPTION_LEN)
+            || tags.len() > Self::MAX_TAGS
+            || !tags.iter().enumerate().all(is_valid_tag)
+        {
+            return Err(BoardError::MetadataInvalid);
+        }
+
+        self.name = name;
+        self.description = description;
+        self.tags = tags;
+
+        Ok(())
+    }
+
+    // Change the number of cells which must be left empty while in the building
+    // state. If the number is less than MIN_EMPTY_CELLS or more than
+    // MAX_EMPTY_CELLS, the EmptyCellsInvalid error is returned, and if the
+    // blocks already added leave fewer cells empty, the BlockPlacementInvalid
+    // error is returned.
+    pub fn set_empty_cells(&mut self, empty_cells: u8) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        if !(Self::MIN_EMPTY_CELLS..=Self::MAX_EMPTY_CELLS).contains(&empty_cells) {
+            return Err(BoardError::EmptyCellsInvalid);
+        }
+
+        if self.num_cells_free() + usize::from(self.empty_cells) < usize::from(empty_cells) {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        self.empty_cells = empty_cells;
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Add block to board while in the building state. If the proposed area
+    // is already covered or if there are not enough free cells, the block is
+    // not added and the BlockPlacementInvalid error is returned.
+    pub fn add_block(&mut self, positioned_block: PositionedBlock) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        if !self.is_range_empty(&positioned_block.range) {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        if self.num_cells_free() < usize::from(positioned_block.block.size()) {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        self.update_grid_range(&positioned_block.range, Some(self.blocks.len()));
+
+        self.blocks.push(positioned_block);
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Add a wall to the board while in the building state. If the position is
+    // outside of the board or already covered, or if there are not enough free
+    // cells, the wall is not added and the BlockPlacementInvalid error is
+    // returned.
+    pub fn add_wall(&mut self, row: u8, col: u8) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        let position = Position::new(row, col).ok_or(BoardError::BlockPlacementInvalid)?;
+
+        if !self.is_cell_empty(row, col) || self.num_cells_free() == 0 {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        self.walls.push(position);
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Remove the wall at the given position while in the building state. If
+    // there is no wall at the position, or the wall belongs to the board's
+    // variant, the BlockPlacementInvalid error is returned.
+    pub fn remove_wall(&mut self, row: u8, col: u8) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        if self
+            .variant
+            .walls()
+            .iter()
+            .any(|wall| wall.row == row && wall.col == col)
+        {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        let wall_idx = self
+            .walls
+            .iter()
+            .position(|wall| wall.row == row && wall.col == col)
+            .ok_or(BoardError::BlockPlacementInvalid)?;
+
+        self.walls.swap_remove(wall_idx);
+
+        let _is_not_ready_to_solve = self.change_state(State::Building).is_ok();
+
+        Ok(())
+    }
+
+    // Change the block at the given index to a new block while in the building
+    // state. If the provided block index is out of bounds, the
+    // BlockIndexOutOfBounds error is returned. If there are not enough free
+    // cells, the position is invalid, or the proposed area is already covered,
+    // the BlockPlacementInvalid error is returned.
+    pub fn change_block(&mut self, block_idx: usize, new_block: Block) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        let positioned_block = self
+            .blocks
+            .get(block_idx)
+            .cloned()
+            .ok_or(BoardError::BlockIndexOutOfBounds)?;
+
+        if positioned_block.block == new_block {
+            return Ok(());
+        }
+
+        let old_size = positioned_block.block.size();
+        let new_size = new_block.size();
+
+        if new_size > old_size && self.num_cells_free() < usize::from(new_size - old_size) {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        let new_positioned_block = PositionedBlock::new(
+            new_block,
+            positioned_block.min_position.row,
+            positioned_block.min_position.col,
+        )
+        .ok_or(BoardError::BlockPlacementInvalid)?
+        .with_axis(positioned_block.axis)
+        .with_locked(positioned_block.locked)
+        .with_link_group(positioned_block.link_group)
+        .with_display(
+            positioned_block.label.clone(),
+            positioned_block.color.clone(),
+        );
+
+        self.update_grid_range(&positioned_block.range, None);
+
+        if !self.is_range_empty(&new_positioned_block.range) {
+            self.update_grid_range(&positioned_block.range, Some(block_idx));
+
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        self.update_grid_range(&new_positioned_block.range, Some(block_idx));
+
+        self.blocks[block_idx] = new_positioned_block;
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Rotate the block at the given index a quarter turn clockwise while in the
+    // building state, keeping the top-left corner of its bounding box in place,
+    // e.g. turning a 1x2 block into a 2x1 block. A block constrained to an axis
+    // is constrained to the other axis once rotated. The same errors as for
+    // change_block are returned if the rotated block does not fit.
+    pub fn rotate_block(&mut self, block_idx: usize) -> Result<(), BoardError> {
+        let positioned_block = self
+            .blocks
+            .get(block_idx)
+            .ok_or(BoardError::BlockIndexOutOfBounds)?;
+
+        let rotated_block = positioned_block.block.rotated();
+        let rotated_axis = positioned_block.axis.map(Axis::rotated);
+
+        self.change_block(block_idx, rotated_block)?;
+
+        self.blocks[block_idx].axis = rotated_axis;
+
+        Ok(())
+    }
+
+    // Lock or unlock the block at the given index while in the building state.
+    // Locked blocks are never moved. If the provided block index is out of
+    // bounds, the BlockIndexOutOfBounds error is returned.
+    pub fn set_locked(&mut self, block_idx: usize, locked: bool) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        self.blocks
+            .get_mut(block_idx)
+            .ok_or(BoardError::BlockIndexOutOfBounds)?
+            .locked = locked;
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Link the blocks at the given indices while in the building state into a
+    // new link group, so that they always move together as a single unit. The
+    // blocks leave the groups they were linked in before. If the provided
+    // block indices are out of bounds, the BlockIndexOutOfBounds error is
+    // returned, and if fewer than two distinct blocks are given, the
+    // LinkInvalid error is returned.
+    pub fn link_blocks(&mut self, block_idxs: &[usize]) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        if block_idxs
+            .iter()
+            .any(|&block_idx| block_idx >= self.blocks.len())
+        {
+            return Err(BoardError::BlockIndexOutOfBounds);
+        }
+
+        if block_idxs.iter().collect::<HashSet<_>>().len() < 2 {
+            return Err(BoardError::LinkInvalid);
+        }
+
+        for &block_idx in block_idxs {
+            self.blocks[block_idx].link_group = None;
+        }
+
+        let link_group = (0..=u8::MAX)
+            .find(|&link_group| {
+                self.blocks
+                    .iter()
+                    .all(|positioned_block| positioned_block.link_group != Some(link_group))
+            })
+            .unwrap();
+
+        for &block_idx in block_idxs {
+            self.blocks[block_idx].link_group = Some(link_group);
+        }
+
+        self.unlink_single_blocks();
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Unlink the block at the given index from its link group while in the
+    // building state. If the provided block index is out of bounds, the
+    // BlockIndexOutOfBounds error is returned.
+    pub fn unlink_block(&mut self, block_idx: usize) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        self.blocks
+            .get_mut(block_idx)
+            .ok_or(BoardError::BlockIndexOutOfBounds)?
+            .link_group = None;
+
+        self.unlink_single_blocks();
+
+        let _is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Unlink every block left linked to no other block, which moves on its own
+    fn unlink_single_blocks(&mut self) {
+        for block_idx in 0..self.blocks.len() {
+            if self.blocks[block_idx].link_group.is_some()
+                && self.linked_indices(block_idx).len() == 1
+            {
+                self.blocks[block_idx].link_group = None;
+            }
+        }
+    }
+
+    // List all possible moves for each block in the board's block property
+    pub fn get_next_moves(&mut self) -> Vec<Vec<FlatMove>> {
+        self.blocks
+            .iter()
+            .map(|block| {
+                let mut moves = self.get_next_moves_for_block(block);
+                moves.dedup();
+                moves
+            })
+            .collect()
+    }
+
+    // Next moves of each block along with the steps taken by each move, where
+    // only the first path found to each position is kept
+    pub fn get_next_detailed_moves(&self) -> Vec<Vec<DetailedMove>> {
+        self.blocks
+            .iter()
+            .map(|block| {
+                let mut seen = HashSet::new();
+
+                self.get_next_paths_for_block(block)
+                    .into_iter()
+                    .map(DetailedMove::from_steps)
+                    .filter(|move_| seen.insert((move_.row_diff, move_.col_diff)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Remove the block at the given index while in the building state. If the
+    // provided block index is out of bounds, the BlockIndexOutOfBounds error is
+    // returned.
+    pub fn remove_block(&mut self, block_idx: usize) -> Result<(), BoardError> {
+        if self.state != State::Building {
+            self.change_state(State::Building)?;
+        }
+
+        let positioned_block = self
+            .blocks
+            .get(block_idx)
+            .cloned()
+            .ok_or(BoardError::BlockIndexOutOfBounds)?;
+
+        self.update_grid_range(&positioned_block.range, None);
+
+        self.blocks.swap_remove(block_idx);
+
+        // The last block takes the place of the removed block
+        if let Some(moved_block) = self.blocks.get(block_idx) {
+            let range = moved_block.range.clone();
+            self.update_grid_range(&range, Some(block_idx));
+        }
+
+        self.unlink_single_blocks();
+
+        let _is_not_ready_to_solve = self.change_state(State::Building).is_ok();
+
+        Ok(())
+    }
+
+    // Move the block at the given index by the given row and column difference
+    // without any error checking. This method is used by the solver when the
+    // provided move is guaranteed to be valid.
+    pub fn move_block_unchecked(&mut self, block_idx: usize, row_diff: i8, col_diff: i8) {
+        self.move_linked_blocks(block_idx, row_diff, col_diff);
+
+        self.moves.push(FlatBoardMove::new(
+            block_idx,
+            &FlatMove::new(row_diff, col_diff).unwrap(),
+        ));
+
+        let _is_solved = self.change_state(State::Solved).is_ok();
+    }
+
+    // Move the block at the given index by the given row and column difference
+    // while in the solveing state. If the provided block index is out of
+    // bounds, the BlockIndexOutOfBounds error is returned. If the provided move
+    // is not present in the board's list of next moves for the corresponding
+    // block, the BlockPlacementInvalid error is returned.
+    pub fn move_block(
+        &mut self,
+        block_idx: usize,
+        row_diff: i8,
+        col_diff: i8,
+    ) -> Result<(), BoardError> {
+        if self.state != State::Solving {
+            self.change_state(State::Solving)?;
+        }
+
+        let next_moves = self.get_next_moves();
+
+        let is_valid_move = next_moves
+            .get(block_idx)
+            .ok_or(BoardError::BlockIndexOutOfBounds)?
+            .iter()
+            .any(|move_| move_.row_diff == row_diff && move_.col_diff == col_diff);
+
+        if !is_valid_move {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        self.move_linked_blocks(block_idx, row_diff, col_diff);
+
+        self.moves.push(FlatBoardMove::new(
+            block_idx,
+            &FlatMove::new(row_diff, col_diff).unwrap(),
+        ));
+
+        let _is_solved = self.change_state(State::Solved).is_ok();
+
+        Ok(())
+    }
+
+    // Make each of the moves in order, as move_block would. If any move is not
+    // valid, none of the moves are made and the error for the first invalid
+    // move is returned.
+    pub fn apply_moves(&mut self, moves: &[FlatBoardMove]) -> Result<(), BoardError> {
+        let mut board = self.clone();
+
+        for move_ in moves {
+            board.move_block(move_.block_idx, move_.row_diff, move_.col_diff)?;
+        }
+
+        *self = board;
+
+        Ok(())
+    }
+
+    // Move the block at the given index by the given steps, as a single move,
+    // while in the solving state. If any step is not allowed for the block or
+    // would cover a cell which is neither empty nor covered by the block before
+    // the move, or if the steps do not make up one of the block's next moves,
+    // the BlockPlacementInvalid error is returned.
+    pub fn move_block_steps(&mut self, block_idx: usize, steps: &[Step]) -> Result<(), BoardError> {
+        if self.state != State::Solving {
+            self.change_state(State::Solving)?;
+        }
+
+        if steps.len() > usize::from(self.empty_cells) {
+            return Err(BoardError::BlockPlacementInvalid);
+        }
+
+        let origins = self.linked_blocks(
+            self.blocks
+                .get(block_idx)
+                .ok_or(BoardError::BlockIndexOutOfBounds)?,
+        );
+
+        let mut blocks = origins.clone();
+
+        for step in steps {
+            if !self.is_step_valid_for_blocks(&origins, &blocks, step) {
+                return Err(BoardError::BlockPlacementInvalid);
+            }
+
+            for block in &mut blocks {
+                block.do_step(step)?;
+            }
+        }
+
+        let move_ = FlatMove::from_steps(steps);
+
+        self.move_block(block_idx, move_.row_diff, move_.col_diff)
+    }
+
+    // Undo the board's last move without any error checking. This method is
+    // used by the solver when there is guaranteed to be a move to undo.
+    pub fn undo_move_unchecked(&mut self) {
+        let opposite_move = self.moves.pop().unwrap().opposite();
+
+        self.move_linked_blocks(
+            opposite_move.block_idx,
+            opposite_move.row_diff,
+            opposite_move.col_diff,
+        );
+
+        let _is_not_solved = self.change_state(State::Solving).is_ok();
+    }
+
+    // Undo the board's last move while in the solving state. If there are no
+    // moves to undo, the NoMovesToUndo error is returned. If the block index
+    // of the last move is out of bounds, the BlockIndexOutOfBounds error is
+    // returned.
+    pub fn undo_move(&mut self) -> Result<(), BoardError> {
+        if ![State::Solving, State::Solved].contains(&self.state) {
+            return Err(BoardError::BoardStateInvalid);
+        }
+
+        let opposite_move = self
+            .moves
+            .pop()
+            .ok_or(BoardError::NoMovesToUndo)?
+            .opposite();
+
+        self.move_linked_blocks(
+            opposite_move.block_idx,
+            opposite_move.row_diff,
+            opposite_move.col_diff,
+        );
+
+        let _is_not_solved = self.change_state(State::Solving).is_ok();
+
+        Ok(())
+    }
+
+    // Undo all board moves while in the solving or solved state. If there are
+    // no moves to undo, the NoMovesToUndo error is returned.
+    pub fn reset(&mut self) -> Result<(), BoardError> {
+        if ![State::Solving, State::Solved].contains(&self.state) {
+            return Err(BoardError::BoardStateInvalid);
+        }
+
+        while !self.moves.is_empty() {
+            self.undo_move()?;
+        }
+
+        let _board_is_ready_to_solve = self.change_state(State::ReadyToSolve).is_ok();
+
+        Ok(())
+    }
+
+    // Archive the board from any state, after which it can no longer be changed
+    pub fn archive(&mut self) -> Result<(), BoardError> {
+        self.change_state(State::Archived)
+    }
+
+    // Update the times at which the board started being solved and was solved
+    // to match its state, given the current time. The start time is kept while
+    // the board is being solved or is solved, and is cleared along with the
+    // finish time once the board is reset or is being built again. The timer
+    // of an archived board is stopped if it was running.
+    pub fn update_timer(&mut self, now: u64) {
+        match self.state {
+            State::Building | State::ReadyToSolve => {
+                self.started_solving_at = None;
+                self.finished_at = None;
+            }
+            State::Solving => {
+                self.started_solving_at.get_or_insert(now);
+                self.finished_at = None;
+            }
+            State::Solved => {
+                self.started_solving_at.get_or_insert(now);
+                self.finished_at.get_or_insert(now);
+            }
+            State::Archived => {
+                if self.started_solving_at.is_some() {
+                    self.finished_at.get_or_insert(now);
+                }
+            }
+        }
+    }
+
+    // Record the given time, if any, as the time of each move made since the
+    // move times were last recorded, and forget the times of undone moves
+    pub fn record_move_times(&mut self, now: Option<u64>) {
+        self.move_times.truncate(self.moves.len());
+        self.move_times.resize(self.moves.len(), now);
+    }
+
+    // Milliseconds spent solving the board, up to the given time if it is not
+    // yet solved, or None if the board has not started being solved
+    pub fn elapsed(&self, now: u64) -> Option<u64> {
+        self.started_solving_at
+            .map(|started| self.finished_at.unwrap_or(now).saturating_sub(started))
+    }
+
+    // Moves taking each block of the board to its position in the other board,
+    // with one move for each block which is in a different position. If the
+    // boards do not have the same blocks in the same order, the
+    // BoardsIncompatible error is returned.
+    pub fn diff(&self, other: &Board) -> Result<Vec<FlatBoardMove>, BoardError> {
+        if self.blocks.len() != other.blocks.len()
+            || self
+                .blocks
+                .iter()
+                .zip(&other.blocks)
+                .any(|(block, other_block)| block.block != other_block.block)
+        {
+            return Err(BoardError::BoardsIncompatible);
+        }
+
+        let diff = |from: u8, to: u8| i8::try_from(to).unwrap() - i8::try_from(from).unwrap();
+
+        Ok(self
+            .blocks
+            .iter()
+            .zip(&other.blocks)
+            .enumerate()
+            .filter(|(_, (block, other_block))| block.min_position != other_block.min_position)
+            .map(|(block_idx, (block, other_block))| FlatBoardMove {
+                block_idx,
+                row_diff: diff(block.min_position.row, other_block.min_position.row),
+                col_diff: diff(block.min_position.col, other_block.min_position.col),
+            })
+            .collect())
+    }
+
+    // Copy of the board as it was after the given number of its moves, leaving
+    // the board itself unchanged. If the board has made fewer moves, the
+    // MoveIndexOutOfBounds error is returned.
+    pub fn at_move(&self, num_moves: usize) -> Result<Board, BoardError> {
+        if num_moves > self.moves.len() {
+            return Err(BoardError::MoveIndexOutOfBounds);
+        }
+
+        let mut board = self.clone();
+
+        while board.moves.len() > num_moves {
+            board.undo_move_unchecked();
+        }
+
+        if board.moves.is_empty() {
+            let _board_is_ready_to_solve = board.change_state(State::ReadyToSolve).is_ok();
+        }
+
+        Ok(board)
+    }
+
+    // Hash of the board's starting position, before any of its moves were made
+    pub fn start_hash(&self) -> u64 {
+        let mut board = self.clone();
+
+        while !board.moves.is_empty() {
+            board.undo_move_unchecked();
+        }
+
+        board.hash()
+    }
+
+    // Key of each position the board has been in, from its starting position
+    // to its current position
+    fn position_keys(&self) -> Vec<u128> {
+        let mut board = self.clone();
+        let mut keys = vec![board.key()];
+
+        while !board.moves.is_empty() {
+            board.undo_move_unchecked();
+            keys.push(board.key());
+        }
+
+        keys.reverse();
+        keys
+    }
+
+    // Number of moves which took the board back to a position it had already
+    // been in, and whether its current position is one it had already been in
+    // before its last move, from a single replay of its moves
+    pub fn repetitions(&self) -> (usize, bool) {
+        let keys = self.position_keys();
+        let mut seen = HashSet::new();
+
+        let repetition_count = keys.iter().filter(|key| !seen.insert(**key)).count();
+
+        let (current, previous) = keys.split_last().unwrap();
+
+        (repetition_count, previous.contains(current))
+    }
+
+    // Copy of the board to be saved as a new board, with its blocks in their
+    // current positions. If the move history is not kept, the copy starts
+    // from the current positions as if no moves had been made, so that it is
+    // ready to solve unless it is already solved, its edit history and timer
+    // are cleared, and so are its difficulty and seed if any moves were made.
+    // A copy of an archived board is no longer archived, and is being built
+    // if its layout is not ready to solve.
+    pub fn fork(&self, with_moves: bool) -> Board {
+        let mut board = Board {
+            id: 0,
+            version: 0,
+            ..self.clone()
+        };
+
+        if !with_moves {
+            if !board.moves.is_empty() {
+                board.difficulty = None;
+                board.seed = None;
+            }
+
+            board.moves.clear();
+            board.edits.clear();
+            board.started_solving_at = None;
+            board.finished_at = None;
+
+            if board.state == State::Archived {
+                board.state = State::Building;
+            }
+
+            let _board_is_ready_to_solve = board.change_state(State::ReadyToSolve).is_ok();
+        }
+
+        board
+    }
+
+    // Record the edit made to the board's blocks at the given time
+    fn record_edit(&mut self, event: EditEvent, at: u64) {
+        self.edits.push(Edit { at, event });
+    }
+
+    // Make the edit to the board's blocks at the given time, recording the
+    // event it leads to, followed by each other block it changes, such as the
+    // blocks unlinked once no other block is left in their link group, so that
+    // replaying the events rebuilds the board's blocks
+    pub fn edit_blocks(&mut self, action: EditAction, at: u64) -> Result<(), BoardError> {
+        let mut expected = self.blocks.clone();
+
+        match action {
+            EditAction::AddBlock(block) => {
+                self.add_block(block.clone())?;
+                expected.push(block.clone());
+                self.record_edit(EditEvent::AddBlock { block }, at);
+            }
+            EditAction::ChangeBlock { block_idx, block } => self.change_block(block_idx, block)?,
+            EditAction::RotateBlock { block_idx } => self.rotate_block(block_idx)?,
+            EditAction::SetLocked { block_idx, locked } => self.set_locked(block_idx, locked)?,
+            EditAction::LinkBlocks { block_idxs } => self.link_blocks(&block_idxs)?,
+            EditAction::UnlinkBlock { block_idx } => self.unlink_block(block_idx)?,
+            EditAction::RemoveBlock { block_idx } => {
+                self.remove_block(block_idx)?;
+                expected.swap_remove(block_idx);
+                self.record_edit(EditEvent::RemoveBlock { block_idx }, at);
+            }
+        }
+
+        self.record_changed_blocks(&expected, at);
+
+        Ok(())
+    }
+
+    // Record each block which differs from the block at the same index before
+    // an edit was made as changed at the given time
+    fn record_changed_blocks(&mut self, before: &[PositionedBlock], at: u64) {
+        let changed_blocks: Vec<(usize, PositionedBlock)> = self
+            .blocks
+            .iter()
+            .enumerate()
+            .filter(|&(block_idx, positioned_block)| {
+                before.get(block_idx) != Some(positioned_block)
+            })
+            .map(|(block_idx, positioned_block)| (block_idx, positioned_block.clone()))
+            .collect();
+
+        for (block_idx, block) in changed_blocks {
+            self.record_edit(EditEvent::ChangeBlock { block_idx, block }, at);
+        }
+    }
+
+    // Copy of the board to be saved as a new board, as forked without its move
+    // history or the seed it was generated from, with each of its blocks,
+    // walls, goal and targets moved by the given transform of a block's shape
+    // and top-left position, which must map the board onto itself
+    fn transformed<F>(&self, transform: F) -> Board
+    where
+        F: Fn(Block, u8, u8) -> (Block, u8, u8),
+    {
+        let mut board = self.fork(false);
+        board.seed = None;
+
+        let transform_cell = |position: &Position| {
+            let (_, row, col) = transform(Block::ONE_BY_ONE, position.row, position.col);
+            Position::new(row, col).unwrap()
+        };
+
+        board.blocks = board
+            .blocks
+            .into_iter()
+            .map(|positioned_block| {
+                let (block, row, col) = transform(
+                    positioned_block.block,
+                    positioned_block.min_position.row,
+                    positioned_block.min_position.col,
+                );
+
+                PositionedBlock {
+                    axis: positioned_block.axis,
+                    locked: positioned_block.locked,
+                    link_group: positioned_block.link_group,
+                    label: positioned_block.label,
+                    color: positioned_block.color,
+                    ..PositionedBlock::new(block, row, col).unwrap()
+                }
+            })
+            .collect();
+        board.grid = Self::grid_from_blocks(&board.blocks);
+        board.walls = board.walls.iter().map(transform_cell).collect();
+
+        let (block, row, col) = transform(board.goal.block, board.goal.row, board.goal.col);
+        board.goal = Goal { block, row, col };
+
+        for target in &mut board.targets {
+            target.block = transform(target.block, 0, 0).0;
+            target.cells = target.cells.iter().map(transform_cell).collect();
+        }
+
+        // The walls of the variant may have moved elsewhere, e.g. the notch of
+        // a rotated board, in which case they are left as ordinary walls
+        if !board
+            .variant
+            .walls()
+            .iter()
+            .all(|wall| board.walls.contains(wall))
+        {
+            board.variant = Variant::Classic;
+        }
+
+        board
+    }
+
+    // Horizontal mirror image of the board, to be saved as a new board
+    pub fn mirrored_horizontal(&self) -> Board {
+        self.transformed(|block, row, col| (block.mirrored(), row, Self::COLS - col - block.cols()))
+    }
+
+    // Board rotated a half turn, to be saved as a new board. Rotating the
+    // board a quarter turn would swap its numbers of rows and columns.
+    pub fn rotated(&self) -> Board {
+        self.transformed(|block, row, col| {
+            (
+                block.rotated().rotated(),
+                Self::ROWS - row - block.rows(),
+                Self::COLS - col - block.cols(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitboard::Bitboard;
+    use crate::game::blocks::Corner;
+
+    #[test]
+    fn grid_from_blocks() {
+        let mut board = Board::default();
+
+        for (block, row, col) in [
+            (Block::TWO_BY_TWO, 0, 1),
+            (Block::LShape(Corner::TopLeft), 2, 0),
+            (Block::ONE_BY_ONE, 4, 3),
+        ] {
+            board
+                .add_block(PositionedBlock::new(block, row, col).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(Board::grid_from_blocks(&board.blocks), board.grid);
+        assert_eq!(Board::grid_from_blocks(&[]), Board::default().grid);
+    }
+
+    #[test]
+    fn update_grid_range() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block.clone());
+
+        assert_eq!(board.grid[0], Some(0));
+
+        board.update_grid_range(&block.range, None);
+
+        assert_eq!(board.grid[0], None);
+    }
+
+    #[test]
+    fn is_range_empty() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let block_two = PositionedBlock::new(Block::ONE_BY_TWO, 1, 0).unwrap();
+
+        assert!(!board.is_range_empty(&block_one.range));
+        assert!(board.is_range_empty(&block_two.range));
+    }
+
+    #[test]
+    fn is_step_valid_for_block() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let block_two = PositionedBlock::new(Block::ONE_BY_TWO, 0, 1).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two.clone());
+
+        assert!(!board.is_step_valid_for_block(&block_one, &block_one, &Step::Left));
+        assert!(!board.is_step_valid_for_block(&block_one, &block_one, &Step::Right));
+        assert!(!board.is_step_valid_for_block(&block_one, &block_one, &Step::Up));
+        assert!(board.is_step_valid_for_block(&block_one, &block_one, &Step::Down));
+
+        assert!(!board.is_step_valid_for_block(&block_two, &block_two, &Step::Left));
+        assert!(!board.is_step_valid_for_block(&block_two, &block_two, &Step::Up));
+        assert!(board.is_step_valid_for_block(&block_two, &block_two, &Step::Right));
+        assert!(board.is_step_valid_for_block(&block_two, &block_two, &Step::Down));
+
+        let block_three = PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap();
+        board.update_grid_range(&block_three.range, Some(board.blocks.len()));
+        board.blocks.push(block_three.clone());
+
+        assert!(!board.is_step_valid_for_block(&block_one, &block_one, &Step::Down));
+
+        assert!(!board.is_step_valid_for_block(&block_three, &block_three, &Step::Up));
+        assert!(!board.is_step_valid_for_block(&block_three, &block_three, &Step::Left));
+        assert!(board.is_step_valid_for_block(&block_three, &block_three, &Step::Right));
+        assert!(board.is_step_valid_for_block(&block_three, &block_three, &Step::Down));
+
+        assert_eq!(
+            board.grid,
+            [
+                Some(0),
+                Some(1),
+                Some(1),
+                None,
+                Some(2),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn get_next_moves_for_block_down_right() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let block_two = PositionedBlock::new(Block::ONE_BY_ONE, 0, 1).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two.clone());
+
+        let block_three = PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap();
+        board.update_grid_range(&block_three.range, Some(board.blocks.len()));
+        board.blocks.push(block_three.clone());
+
+        let block_one_moves = board.get_next_moves_for_block(&block_one);
+
+        assert_eq!(block_one_moves.len(), 0);
+
+        let block_two_moves = board.get_next_moves_for_block(&block_two);
+
+        assert_eq!(
+            board.grid,
+            [
+                Some(0),
+                Some(1),
+                None,
+                None,
+                Some(2),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        let expected_block_two_moves = [
+            FlatMove::new(0, 1).unwrap(),
+            FlatMove::new(0, 2).unwrap(),
+            FlatMove::new(1, 1).unwrap(),
+            FlatMove::new(1, 1).unwrap(),
+            FlatMove::new(1, 0).unwrap(),
+            FlatMove::new(2, 0).unwrap(),
+        ];
+
+        println!("{:?}", block_two_moves);
+
+        assert_eq!(block_two_moves.len(), expected_block_two_moves.len());
+
+        for move_ in block_two_moves {
+            assert!(expected_block_two_moves.contains(&move_));
+        }
+    }
+
+    #[test]
+    fn get_next_moves_for_block_up_left() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let block_two = PositionedBlock::new(Block::ONE_BY_ONE, 4, 2).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two.clone());
+
+        let block_three = PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap();
+        board.update_grid_range(&block_three.range, Some(board.blocks.len()));
+        board.blocks.push(block_three.clone());
+
+        let block_one_moves = board.get_next_moves_for_block(&block_one);
+
+        assert_eq!(block_one_moves.len(), 0);
+
+        let block_two_moves = board.get_next_moves_for_block(&block_two);
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(2),
+                None,
+                None,
+                Some(1),
+                Some(0),
+            ]
+        );
+
+        let expected_block_two_moves = [
+            FlatMove::new(0, -1).unwrap(),
+            FlatMove::new(0, -2).unwrap(),
+            FlatMove::new(-1, -1).unwrap(),
+            FlatMove::new(-1, -1).unwrap(),
+            FlatMove::new(-1, 0).unwrap(),
+            FlatMove::new(-2, 0).unwrap(),
+        ];
+
+        assert_eq!(block_two_moves.len(), expected_block_two_moves.len());
+
+        for move_ in block_two_moves {
+            assert!(expected_block_two_moves.contains(&move_));
+        }
+    }
+
+    #[test]
+    fn get_next_moves_for_l_shape() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::LShape(Corner::BottomRight), 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let block_two = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two.clone());
+
+        let block_one_moves = board.get_next_moves_for_block(&block_one);
+
+        assert_eq!(
+            block_one_moves,
+            vec![
+                FlatMove::new(0, 1).unwrap(),
+                FlatMove::new(1, 1).unwrap(),
+                FlatMove::new(0, 2).unwrap(),
+            ]
+        );
+
+        let block_three = PositionedBlock::new(Block::ONE_BY_ONE, 1, 1).unwrap();
+        board.update_grid_range(&block_three.range, Some(board.blocks.len()));
+        board.blocks.push(block_three.clone());
+
+        assert!(board.get_next_moves_for_block(&block_one).is_empty());
+    }
+
+    #[test]
+    fn get_next_moves_for_constrained_block() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1)
+                .unwrap()
+                .with_axis(Some(Axis::Horizontal)),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0)
+                .unwrap()
+                .with_axis(Some(Axis::Horizontal)),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let next_moves = board.get_next_moves();
+
+        assert!(next_moves[6].is_empty());
+        assert_eq!(
+            next_moves[7],
+            vec![FlatMove::new(1, 0).unwrap(), FlatMove::new(1, -1).unwrap()]
+        );
+        assert_eq!(
+            next_moves[8],
+            vec![FlatMove::new(0, 1).unwrap(), FlatMove::new(0, 2).unwrap()]
+        );
+
+        assert_eq!(
+            board.move_block(6, 1, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert!(board.move_block(8, 0, 2).is_ok());
+        assert_eq!(board.blocks[8].axis, Some(Axis::Horizontal));
+    }
+
+    #[test]
+    fn set_empty_cells() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        assert_eq!(
+            board.readiness_report(),
+            vec![NotReadyReason::CellsFree { count: 1 }]
+        );
+        assert_eq!(board.set_empty_cells(1), Err(BoardError::EmptyCellsInvalid));
+        assert_eq!(
+            board.set_empty_cells(Board::MAX_EMPTY_CELLS + 1),
+            Err(BoardError::EmptyCellsInvalid)
+        );
+        assert!(board.set_empty_cells(3).is_ok());
+        assert_eq!(board.state, State::ReadyToSolve);
+
+        let next_moves = board.get_next_moves();
+
+        assert_eq!(
+            next_moves[7],
+            vec![
+                FlatMove::new(1, 0).unwrap(),
+                FlatMove::new(1, -1).unwrap(),
+                FlatMove::new(1, 1).unwrap(),
+            ]
+        );
+        assert_eq!(
+            next_moves[8],
+            vec![
+                FlatMove::new(0, 1).unwrap(),
+                FlatMove::new(0, 2).unwrap(),
+                FlatMove::new(0, 3).unwrap(),
+            ]
+        );
+        assert!(board.move_block(8, 0, 3).is_ok());
+
+        let mut board = board.at_move(0).unwrap();
+
+        assert_eq!(
+            board.add_block(PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap()),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert!(board.set_empty_cells(2).is_ok());
+        assert!(board
+            .add_block(PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap())
+            .is_ok());
+        assert_eq!(board.state, State::ReadyToSolve);
+        assert_eq!(
+            board.set_empty_cells(3),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+    }
+
+    #[test]
+    fn set_locked() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        assert_eq!(
+            board.set_locked(blocks.len(), true),
+            Err(BoardError::BlockIndexOutOfBounds)
+        );
+        assert!(board.set_locked(6, true).is_ok());
+        assert_eq!(board.state, State::ReadyToSolve);
+        assert!(board.has_constrained_blocks());
+
+        let next_moves = board.get_next_moves();
+
+        assert!(next_moves[6].is_empty());
+        assert!(!next_moves[7].is_empty());
+        assert_eq!(
+            board.move_block(6, 1, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+
+        board.change_state(State::ReadyToSolve).unwrap();
+
+        assert!(board.set_locked(6, false).is_ok());
+        assert!(!board.has_constrained_blocks());
+        assert!(board.move_block(6, 1, 0).is_ok());
+    }
+
+    #[test]
+    fn link_blocks() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        assert_eq!(
+            board.link_blocks(&[6, blocks.len()]),
+            Err(BoardError::BlockIndexOutOfBounds)
+        );
+        assert_eq!(board.link_blocks(&[6, 6]), Err(BoardError::LinkInvalid));
+        assert!(board.link_blocks(&[6, 7]).is_ok());
+        assert_eq!(board.state, State::ReadyToSolve);
+        assert!(board.has_constrained_blocks());
+
+        board.change_state(State::Solving).unwrap();
+
+        let next_moves = board.get_next_moves();
+        let down = FlatMove {
+            row_diff: 1,
+            col_diff: 0,
+        };
+
+        assert_eq!(next_moves[6], vec![down.clone()]);
+        assert_eq!(next_moves[7], vec![down]);
+
+        let hash = board.hash();
+
+        assert!(board.move_block(7, 1, 0).is_ok());
+        assert_eq!(board.blocks[6].min_position.row, 4);
+        assert_eq!(board.blocks[7].min_position.row, 4);
+        assert_eq!(board.grid, Board::grid_from_blocks(&board.blocks));
+
+        assert!(board.undo_move().is_ok());
+        assert_eq!(board.hash(), hash);
+        assert_eq!(board.grid, Board::grid_from_blocks(&board.blocks));
+
+        assert!(board.move_block_steps(6, &[Step::Down]).is_ok());
+        assert_eq!(board.blocks[7].min_position.row, 4);
+
+        board.reset().unwrap();
+        board.change_state(State::ReadyToSolve).unwrap();
+
+        assert!(board.link_blocks(&[8, 9]).is_ok());
+        assert_eq!(board.num_link_groups(), 2);
+
+        board.change_state(State::Solving).unwrap();
+
+        assert!(board.get_next_moves()[8].is_empty());
+        assert!(board.move_block(6, 1, 0).is_ok());
+        assert_eq!(board.blocks[7].min_position.row, 4);
+        assert_eq!(board.blocks[8].min_position.col, 0);
+        assert_eq!(board.blocks[9].min_position.col, 3);
+
+        board.reset().unwrap();
+        board.change_state(State::ReadyToSolve).unwrap();
+
+        assert!(board.link_blocks(&[7, 8]).is_ok());
+        assert_eq!(board.blocks[6].link_group, None);
+        assert_eq!(board.blocks[9].link_group, None);
+        assert!(board.unlink_block(7).is_ok());
+        assert!(!board.has_constrained_blocks());
+    }
+
+    #[test]
+    fn linked_hash_differs_from_walls() {
+        let mut linked_board = Board::default();
+        let mut walled_board = Board::default();
+
+        for (row, col) in [(0, 0), (0, 2)] {
+            linked_board
+                .add_block(PositionedBlock::new(Block::ONE_BY_ONE, row, col).unwrap())
+                .unwrap();
+            walled_board.add_wall(row, col).unwrap();
+        }
+
+        linked_board.link_blocks(&[0, 1]).unwrap();
+
+        assert_ne!(linked_board.hash(), walled_board.hash());
+    }
+
+    #[test]
+    fn linked_key_tells_apart_link_groups() {
+        // Blocks stacked in the first column, linked in the given order
+        let stacked_board = |link_groups: &[&[usize]]| {
+            let mut board = Board::default();
+
+            for row in 0..Board::ROWS {
+                board
+                    .add_block(PositionedBlock::new(Block::ONE_BY_ONE, row, 0).unwrap())
+                    .unwrap();
+            }
+
+            for block_idxs in link_groups {
+                board.link_blocks(block_idxs).unwrap();
+            }
+
+            board
+        };
+
+        // A 3x1 and a 2x1 group in either order, or two groups of alternating
+        // blocks, so that the linked cells and number of link groups match
+        let boards = [
+            stacked_board(&[&[0, 1, 2], &[3, 4]]),
+            stacked_board(&[&[0, 1], &[2, 3, 4]]),
+            stacked_board(&[&[0, 2, 4], &[1, 3]]),
+        ];
+
+        let keys: HashSet<u128> = boards.iter().map(Board::key).collect();
+        let hashes: HashSet<u64> = boards.iter().map(Board::hash).collect();
+
+        assert_eq!(keys.len(), boards.len());
+        assert_eq!(hashes.len(), boards.len());
+
+        for board in &boards {
+            let bitboard = Bitboard::from(board);
+
+            assert_eq!(bitboard.key(), board.key());
+            assert_eq!(bitboard.canonical_key(), board.canonical_key());
+            assert_eq!(bitboard.hash(), board.hash());
+        }
+
+        // The key does not depend on the ids given to the link groups
+        let relinked_board = stacked_board(&[&[1, 3], &[0, 2, 4]]);
+
+        assert_ne!(
+            relinked_board.blocks[0].link_group,
+            boards[2].blocks[0].link_group
+        );
+        assert_eq!(relinked_board.key(), boards[2].key());
+    }
+
+    #[test]
+    fn get_next_moves() {
+        let blocks = vec![
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+        for block in blocks {
+            board.add_block(block).unwrap();
+        }
+
+        assert_eq!(board.state, State::ReadyToSolve);
+
+        assert!(board.change_state(State::Solving).is_ok());
+
+        let next_moves = board.get_next_moves();
+
+        assert_eq!(next_moves.iter().fold(0, |acc, moves| acc + moves.len()), 8);
+
+        let expected_moves = [
+            vec![FlatMove::new(1, 0).unwrap(), FlatMove::new(1, 1).unwrap()],
+            vec![FlatMove::new(1, 0).unwrap(), FlatMove::new(1, -1).unwrap()],
+            vec![FlatMove::new(0, 1).unwrap(), FlatMove::new(0, 2).unwrap()],
+            vec![FlatMove::new(0, -1).unwrap(), FlatMove::new(0, -2).unwrap()],
+        ];
+
+        for i in 0..next_moves.len() {
+            if i < 6 {
+                assert_eq!(next_moves[i].len(), 0);
+            } else {
+                assert_eq!(next_moves[i], expected_moves[i - 6]);
+            }
+        }
+    }
+
+    #[test]
+    fn get_next_detailed_moves() {
+        let mut board = Board::default();
+
+        for (row, col) in [(0, 0), (0, 1), (1, 0)] {
+            board
+                .add_block(PositionedBlock::new(Block::ONE_BY_ONE, row, col).unwrap())
+                .unwrap();
+        }
+
+        let next_moves = board.get_next_detailed_moves();
+
+        assert!(next_moves[0].is_empty());
+        assert_eq!(
+            next_moves[1],
+            vec![
+                DetailedMove::from_steps(vec![Step::Down]),
+                DetailedMove::from_steps(vec![Step::Right]),
+                DetailedMove::from_steps(vec![Step::Down, Step::Down]),
+                DetailedMove::from_steps(vec![Step::Down, Step::Right]),
+                DetailedMove::from_steps(vec![Step::Right, Step::Right]),
+            ]
+        );
+        assert_eq!(next_moves[1][3].row_diff, 1);
+        assert_eq!(next_moves[1][3].col_diff, 1);
+        assert_eq!(
+            next_moves.iter().map(Vec::len).collect::<Vec<_>>(),
+            board
+                .get_next_moves()
+                .iter()
+                .map(Vec::len)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn hash() {
+        let mut board = Board::default();
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        for block in blocks.iter() {
+            board.update_grid_range(&block.range, Some(board.blocks.len()));
+            board.blocks.push(block.clone());
+        }
+
+        assert_eq!(board.hash(), 0o1001_1121_3123_1121_3343);
+    }
+
+    #[test]
+    fn hash_is_exact() {
+        let mut board = Board::default();
+
+        assert_eq!(board.hash(), 0);
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        assert_eq!(board.hash(), 0b001);
+
+        let block_two = PositionedBlock::new(Block::TWO_BY_TWO, 3, 2).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two.clone());
+
+        assert_eq!(board.hash(), 0o1200_3400_0000_0000_0001);
+
+        let mut other_board = Board::default();
+
+        let block_three = PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap();
+        other_board.update_grid_range(&block_three.range, Some(other_board.blocks.len()));
+        other_board.blocks.push(block_three.clone());
+
+        assert_ne!(board.hash(), other_board.hash());
+    }
+
+    #[test]
+    fn canonical_hash() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let block_two = PositionedBlock::new(Block::TWO_BY_ONE, 3, 3).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two.clone());
+
+        let mut mirrored_board = Board::default();
+
+        let block_three = PositionedBlock::new(Block::ONE_BY_TWO, 0, 2).unwrap();
+        mirrored_board.update_grid_range(&block_three.range, Some(mirrored_board.blocks.len()));
+        mirrored_board.blocks.push(block_three.clone());
+
+        let block_four = PositionedBlock::new(Block::TWO_BY_ONE, 3, 0).unwrap();
+        mirrored_board.update_grid_range(&block_four.range, Some(mirrored_board.blocks.len()));
+        mirrored_board.blocks.push(block_four.clone());
+
+        assert_ne!(board.hash(), mirrored_board.hash());
+        assert_eq!(board.mirrored_hash(), mirrored_board.hash());
+        assert_eq!(board.hash(), mirrored_board.mirrored_hash());
+        assert_eq!(board.canonical_hash(), mirrored_board.canonical_hash());
+
+        board.goal = Goal {
+            block: Block::TWO_BY_TWO,
+            row: 3,
+            col: 0,
+        };
+
+        assert_eq!(board.canonical_hash(), board.hash());
+    }
+
+    #[test]
+    fn canonical_hash_l_shape() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::LShape(Corner::TopLeft), 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one.clone());
+
+        let mut mirrored_board = Board::default();
+
+        let block_two = PositionedBlock::new(Block::LShape(Corner::TopRight), 0, 2).unwrap();
+        mirrored_board.update_grid_range(&block_two.range, Some(mirrored_board.blocks.len()));
+        mirrored_board.blocks.push(block_two.clone());
+
+        assert_eq!(board.hash(), 0o12_0030);
+        assert_eq!(board.mirrored_hash(), mirrored_board.hash());
+        assert_eq!(board.canonical_hash(), mirrored_board.canonical_hash());
+
+        board.goal = Goal {
+            block: Block::LShape(Corner::TopLeft),
+            row: 3,
+            col: 1,
+        };
+
+        assert!(!board.goal.is_symmetric());
+        assert_eq!(board.canonical_hash(), board.hash());
+    }
+
+    #[test]
+    fn canonical_hash_locked() {
+        // The second board's cells match the mirror image of the first, but
+        // its locked block is on the left rather than the right
+        let mut boards = [(1, 0), (1, 2)].map(|(row, col)| {
+            let mut board = Board::default();
+
+            for block in [
+                PositionedBlock::new(Block::ONE_BY_ONE, 0, 0)
+                    .unwrap()
+                    .with_locked(true),
+                PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+                PositionedBlock::new(Block::ONE_BY_TWO, row, col).unwrap(),
+            ] {
+                board.add_block(block).unwrap();
+            }
+
+            board
+        });
+
+        assert_eq!(boards[0].mirrored_hash(), boards[1].hash());
+        assert_ne!(boards[0].canonical_hash(), boards[1].canonical_hash());
+        assert_ne!(
+            Bitboard::from(&boards[0]).canonical_hash(),
+            Bitboard::from(&boards[1]).canonical_hash()
+        );
+
+        for board in &mut boards {
+            board.blocks[0].locked = false;
+        }
+
+        assert_eq!(boards[0].canonical_hash(), boards[1].canonical_hash());
+    }
+
+    #[test]
+    fn change_state() {
+        let mut board = Board::default();
+
+        assert!(board.change_state(State::Building).is_ok());
+        assert!(board.change_state(State::Solving).is_err());
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        for block in blocks.iter() {
+            board.update_grid_range(&block.range, Some(board.blocks.len()));
+            board.blocks.push(block.clone());
+        }
+
+        assert!(board.change_state(State::Solving).is_err());
+        assert!(board.change_state(State::ReadyToSolve).is_ok());
+
+        board.difficulty = Some(10);
+
+        assert!(board.change_state(State::Building).is_ok());
+        assert_eq!(board.difficulty, None);
+        assert!(board.change_state(State::Solving).is_err());
+        assert!(board.change_state(State::ReadyToSolve).is_ok());
+        assert!(board.change_state(State::Solving).is_ok());
+
+        let move_ = FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap());
+        board.moves.push(move_);
+
+        assert!(board.change_state(State::ReadyToSolve).is_err());
+        assert!(board.change_state(State::Building).is_err());
+    }
+
+    #[test]
+    fn archive() {
+        let mut board = Board::default();
+        board
+            .add_block(PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap())
+            .unwrap();
+
+        board.state = State::Solving;
+        board.started_solving_at = Some(100);
+        board.archive().unwrap();
+        board.update_timer(500);
+
+        assert_eq!(board.state, State::Archived);
+        assert_eq!(board.elapsed(900), Some(400));
+        assert!(board.archive().is_ok());
+
+        for new_state in [
+            State::Building,
+            State::ReadyToSolve,
+            State::Solving,
+            State::Solved,
+        ] {
+            assert!(board.change_state(new_state).is_err());
+        }
+
+        assert_eq!(
+            board.move_block(0, 1, 0).err(),
+            Some(BoardError::BoardStateInvalid)
+        );
+        assert_eq!(
+            board.add_wall(4, 3).err(),
+            Some(BoardError::BoardStateInvalid)
+        );
+        assert_eq!(board.undo_move().err(), Some(BoardError::BoardStateInvalid));
+        assert_eq!(board.state, State::Archived);
+    }
+
+    #[test]
+    fn try_from_blocks() {
+        let blocks = vec![
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let board = Board::try_from_blocks(blocks.clone()).unwrap();
+
+        assert_eq!(board.blocks, blocks);
+        assert_eq!(board.state, State::ReadyToSolve);
+
+        let mut overlapping = blocks;
+        overlapping.push(PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap());
+
+        assert_eq!(
+            Board::try_from_blocks(overlapping).err(),
+            Some(BoardError::BlockPlacementInvalid)
+        );
+    }
+
+    #[test]
+    fn try_from_variant_blocks() {
+        let blocks = vec![
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+        ];
+
+        let board = Board::try_from_variant_blocks(Variant::Notched, blocks.clone()).unwrap();
+
+        assert_eq!(board.walls, Variant::Notched.walls());
+        assert!(board.is_wall(4, 0) && board.is_wall(4, 3));
+        assert_eq!(board.state, State::ReadyToSolve);
+
+        let mut notch_covered = blocks;
+        notch_covered.push(PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap());
+
+        assert_eq!(
+            Board::try_from_variant_blocks(Variant::Notched, notch_covered).err(),
+            Some(BoardError::BlockPlacementInvalid)
+        );
+    }
+
+    #[test]
+    fn is_ready_to_solve() {
+        let mut board = Board::default();
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+        ];
+        let final_block = PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap();
+
+        for block in blocks.iter() {
+            board.update_grid_range(&block.range, Some(board.blocks.len()));
+            board.blocks.push(block.clone());
+
+            assert!(!board.is_ready_to_solve());
+        }
+
+        board.update_grid_range(&final_block.range, Some(board.blocks.len()));
+        board.blocks.push(final_block);
+
+        assert!(board.is_ready_to_solve());
+    }
+
+    #[test]
+    fn readiness_report() {
+        let mut board = Board::default();
+
+        assert_eq!(
+            board.readiness_report(),
+            vec![
+                NotReadyReason::GoalBlockCount { count: 0 },
+                NotReadyReason::CellsFree { count: 18 },
+            ]
+        );
+
+        board
+            .add_block(PositionedBlock::new(Block::TWO_BY_TWO, 0, 0).unwrap())
+            .unwrap();
+        board
+            .add_block(PositionedBlock::new(Block::TWO_BY_TWO, 0, 2).unwrap())
+            .unwrap();
+        board.add_wall(4, 0).unwrap();
+
+        assert_eq!(
+            board.readiness_report(),
+            vec![
+                NotReadyReason::GoalBlockCount { count: 2 },
+                NotReadyReason::CellsFree { count: 9 },
+            ]
+        );
+
+        board.remove_block(1).unwrap();
+
+        assert_eq!(
+            board.readiness_report(),
+            vec![NotReadyReason::CellsFree { count: 13 }]
+        );
+    }
+
+    #[test]
+    fn is_solved() {
+        let mut board = Board::default();
+        let mut block = PositionedBlock::new(Block::TWO_BY_TWO, 2, 1).unwrap();
+        board.blocks.push(block.clone());
+
+        assert!(!board.is_solved());
+
+        block.do_step(&Step::Down).unwrap();
+        board.blocks[0] = block;
+
+        assert!(board.is_solved())
+    }
+
+    #[test]
+    fn is_solved_custom_goal() {
+        let mut board = Board::default();
+        let block = PositionedBlock::new(Block::TWO_BY_TWO, 3, 1).unwrap();
+        board.blocks.push(block);
+
+        board.goal = Goal {
+            block: Block::TWO_BY_TWO,
+            row: 0,
+            col: 0,
+        };
+
+        assert!(!board.is_solved());
+
+        let block = PositionedBlock::new(Block::TWO_BY_TWO, 0, 0).unwrap();
+        board.blocks[0] = block;
+
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn set_goal() {
+        let mut board = Board::default();
+
+        let goal = Goal {
+            block: Block::ONE_BY_TWO,
+            row: 4,
+            col: 2,
+        };
+
+        assert!(board.set_goal(goal).is_ok());
+        assert_eq!(board.goal, goal);
+        assert!(!goal.is_symmetric());
+
+        let goal = Goal {
+            block: Block::TWO_BY_ONE,
+            row: 4,
+            col: 0,
+        };
+
+        assert_eq!(board.set_goal(goal), Err(BoardError::BlockPlacementInvalid));
+        assert!(Goal::default().is_symmetric());
+    }
+
+    #[test]
+    fn set_metadata() {
+        let mut board = Board::default();
+        let tags = vec![String::from("classic"), String::from("hard")];
+
+        assert!(board
+            .set_metadata(Some(String::from("Daily")), None, tags.clone())
+            .is_ok());
+        assert_eq!(board.name.as_deref(), Some("Daily"));
+        assert_eq!(board.tags, tags);
+
+        for tags in [
+            vec![String::new()],
+            vec![String::from("hard"), String::from("hard")],
+            vec!["x".repeat(Board::MAX_TAG_LEN + 1)],
+            vec![String::from("tag"); Board::MAX_TAGS + 1],
+        ] {
+            assert_eq!(
+                board.set_metadata(None, None, tags),
+                Err(BoardError::MetadataInvalid)
+            );
+        }
+
+        assert_eq!(
+            board.set_metadata(Some("x".repeat(Board::MAX_NAME_LEN + 1)), None, vec![]),
+            Err(BoardError::MetadataInvalid)
+        );
+        assert_eq!(board.tags, tags);
+
+        board.archive().unwrap();
+
+        assert_eq!(
+            board.set_metadata(None, None, vec![]),
+            Err(BoardError::BoardStateInvalid)
+        );
+    }
+
+    #[test]
+    fn set_targets() {
+        let mut board = Board::default();
+        board
+            .add_block(PositionedBlock::new(Block::ONE_BY_TWO, 4, 0).unwrap())
+            .unwrap();
+
+        let target = Target {
+            block: Block::ONE_BY_TWO,
+            cells: vec![Position::new(4, 2).unwrap(), Position::new(4, 3).unwrap()],
+        };
+
+        assert!(board.set_targets(vec![target.clone()]).is_ok());
+        assert!(!board.is_solved());
+
+        board.move_block_unchecked(0, 0, 2);
+
+        assert!(board.is_solved());
+
+        let too_small = Target {
+            block: Block::TWO_BY_TWO,
+            cells: target.cells.clone(),
+        };
+        let off_board = Target {
+            block: Block::ONE_BY_ONE,
+            cells: vec![Position { row: 5, col: 0 }],
+        };
+
+        assert_eq!(
+            board.set_targets(vec![too_small]),
+            Err(BoardError::TargetsInvalid)
+        );
+        assert_eq!(
+            board.set_targets(vec![off_board]),
+            Err(BoardError::TargetsInvalid)
+        );
+        assert_eq!(
+            board.set_targets(vec![target; Board::MAX_TARGETS + 1]),
+            Err(BoardError::TargetsInvalid)
+        );
+        assert!(board.set_targets(vec![]).is_ok());
+        assert!(!board.is_solved());
+    }
+
+    #[test]
+    fn add_block() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap();
+
+        assert!(board.add_block(block_one).is_ok());
+        assert_eq!(board.blocks.len(), 1);
+        assert_eq!(
+            board.grid,
+            [
+                Some(0),
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        let block_two = PositionedBlock::new(Block::ONE_BY_TWO, 0, 1).unwrap();
+
+        assert!(board.add_block(block_two).is_err());
+    }
+
+    #[test]
+    fn add_block_not_enough_cells_free() {
+        let mut board = Board::default();
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let last_block = PositionedBlock::new(Block::ONE_BY_TWO, 4, 0).unwrap();
+
+        for block in blocks.into_iter() {
+            assert!(board.add_block(block).is_ok());
+        }
+
+        assert_eq!(
+            board.add_block(last_block),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+    }
+
+    #[test]
+    fn remove_block() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one);
+
+        assert!(board.remove_block(0).is_ok());
+        assert_eq!(board.blocks.len(), 0);
+        assert_eq!(board.grid, [None; 20]);
+        assert!(board.remove_block(0).is_err());
+    }
+
+    #[test]
+    fn remove_block_reindexes_grid() {
+        let mut board = Board::default();
+
+        for (row, col) in [(0, 0), (0, 1), (0, 2)] {
+            board
+                .add_block(PositionedBlock::new(Block::ONE_BY_ONE, row, col).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(board.grid[..3], [Some(0), Some(1), Some(2)]);
+
+        board.remove_block(0).unwrap();
+
+        assert_eq!(board.grid[..3], [None, Some(1), Some(0)]);
+        assert_eq!(board.grid, Board::grid_from_blocks(&board.blocks));
+    }
+
+    #[test]
+    fn add_wall() {
+        let mut board = Board::default();
+
+        assert!(board.add_wall(0, 0).is_ok());
+        assert!(board.is_wall(0, 0));
+        assert_eq!(board.add_wall(0, 0), Err(BoardError::BlockPlacementInvalid));
+        assert_eq!(
+            board.add_wall(Board::ROWS, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(
+            board.add_block(PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap()),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 0, 1).unwrap();
+        assert!(board.add_block(block.clone()).is_ok());
+        assert!(!board.is_step_valid_for_block(&block, &block, &Step::Left));
+        assert!(board.is_step_valid_for_block(&block, &block, &Step::Right));
+
+        assert_ne!(board.hash(), Board::default().hash());
+    }
+
+    #[test]
+    fn add_wall_not_enough_cells_free() {
+        let mut board = Board::default();
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        for block in blocks {
+            assert!(board.add_block(block).is_ok());
+        }
+
+        assert!(board.add_wall(4, 0).is_ok());
+        assert_eq!(board.state, State::ReadyToSolve);
+        assert_eq!(board.add_wall(4, 1), Err(BoardError::BlockPlacementInvalid));
+    }
+
+    #[test]
+    fn remove_wall() {
+        let mut board = Board::default();
+
+        assert!(board.add_wall(0, 0).is_ok());
+        assert!(board.remove_wall(0, 0).is_ok());
+        assert!(!board.is_wall(0, 0));
+        assert_eq!(board.hash(), Board::default().hash());
+        assert_eq!(
+            board.remove_wall(0, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+
+        let mut board = Board::from_variant(Variant::Notched);
+
+        assert!(board.add_wall(0, 0).is_ok());
+        assert!(board.remove_wall(0, 0).is_ok());
+        assert_eq!(
+            board.remove_wall(4, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(board.walls, Variant::Notched.walls());
+    }
+
+    #[test]
+    fn transforms_keep_variant_walls() {
+        let board = Board::from_variant(Variant::Notched);
+
+        assert_eq!(board.mirrored_horizontal().variant, Variant::Notched);
+
+        let rotated = board.rotated();
+
+        assert_eq!(rotated.variant, Variant::Classic);
+        assert!(rotated.is_wall(0, 0) && rotated.is_wall(0, 3));
+    }
+
+    #[test]
+    fn change_block() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_TWO, 0, 0)
+            .unwrap()
+            .with_display(Some(String::from("A")), Some(String::from("#ff0000")));
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+
+        assert!(board.change_block(0, Block::ONE_BY_ONE).is_ok());
+        assert_eq!(board.blocks[0].label.as_deref(), Some("A"));
+        assert_eq!(board.blocks[0].color.as_deref(), Some("#ff0000"));
+        assert_eq!(
+            board.grid,
+            [
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+        assert!(board.change_block(1, Block::ONE_BY_ONE).is_err());
+    }
+
+    #[test]
+    fn rotate_block() {
+        let mut board = Board::default();
+
+        board
+            .add_block(
+                PositionedBlock::new(Block::ONE_BY_TWO, 0, 0)
+                    .unwrap()
+                    .with_axis(Some(Axis::Horizontal)),
+            )
+            .unwrap();
+        board
+            .add_block(PositionedBlock::new(Block::ONE_BY_TWO, 4, 2).unwrap())
+            .unwrap();
+
+        assert!(board.rotate_block(0).is_ok());
+        assert_eq!(board.blocks[0].block, Block::TWO_BY_ONE);
+        assert_eq!(board.blocks[0].axis, Some(Axis::Vertical));
+        assert_eq!(board.grid[0], Some(0));
+        assert_eq!(board.grid[1], None);
+        assert_eq!(board.grid[4], Some(0));
+
+        board
+            .add_block(PositionedBlock::new(Block::ONE_BY_ONE, 0, 1).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            board.rotate_block(0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(board.blocks[0].block, Block::TWO_BY_ONE);
+        assert_eq!(
+            board.rotate_block(1),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(
+            board.rotate_block(3),
+            Err(BoardError::BlockIndexOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn change_block_not_enough_cells_free() {
+        let mut board = Board::default();
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        for block in blocks.iter() {
+            board.update_grid_range(&block.range, Some(board.blocks.len()));
+            board.blocks.push(block.clone());
+        }
+
+        assert_eq!(
+            board.change_block(8, Block::ONE_BY_TWO),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+    }
+
+    #[test]
+    fn move_block_unchecked() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one);
+        board.state = State::Solving;
+
+        board.move_block_unchecked(0, 0, 1);
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        board.move_block_unchecked(0, 1, 0);
+        board.move_block_unchecked(0, 0, -1);
+
+        let block_two = PositionedBlock::new(Block::TWO_BY_TWO, 3, 2).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two);
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                Some(1),
+            ]
+        );
+
+        board.move_block_unchecked(1, 0, -2);
+        board.move_block_unchecked(1, -1, 1);
+        board.move_block_unchecked(1, -1, 0);
+        board.move_block_unchecked(1, 0, -1);
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn move_block_steps() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        assert!(board.move_block_steps(7, &[Step::Down, Step::Left]).is_ok());
+        assert_eq!(board.blocks[7].min_position, Position::new(4, 1).unwrap());
+        assert_eq!(
+            board.moves,
+            vec![FlatBoardMove {
+                block_idx: 7,
+                row_diff: 1,
+                col_diff: -1
+            }]
+        );
+
+        assert_eq!(
+            board.move_block_steps(6, &[Step::Left]),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(
+            board.move_block_steps(6, &[Step::Right, Step::Left]),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(
+            board.move_block_steps(6, &[Step::Right, Step::Down, Step::Right]),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(
+            board.move_block_steps(blocks.len(), &[Step::Down]),
+            Err(BoardError::BlockIndexOutOfBounds)
+        );
+        assert_eq!(board.moves.len(), 1);
+
+        assert!(board
+            .move_block_steps(6, &[Step::Right, Step::Down])
+            .is_ok());
+        assert_eq!(board.blocks[6].min_position, Position::new(4, 2).unwrap());
+    }
+
+    #[test]
+    fn move_block() {
+        let mut board = Board::default();
+
+        let block_one = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block_one.range, Some(board.blocks.len()));
+        board.blocks.push(block_one);
+        board.state = State::Solving;
+
+        assert!(board.move_block(0, 0, 1).is_ok());
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        assert_eq!(
+            board.move_block(0, -1, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert!(board.move_block(0, 0, -1).is_ok());
+        assert!(board.move_block(0, 1, 0).is_ok());
+        assert_eq!(
+            board.move_block(0, 0, -1),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+
+        let block_two = PositionedBlock::new(Block::TWO_BY_TWO, 3, 2).unwrap();
+        board.update_grid_range(&block_two.range, Some(board.blocks.len()));
+        board.blocks.push(block_two);
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                Some(1),
+            ]
+        );
+
+        assert_eq!(
+            board.move_block(1, 0, 1),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(
+            board.move_block(1, 1, 0),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert!(board.move_block(1, 0, -2).is_ok());
+        assert!(board.move_block(1, -1, 1).is_ok());
+        assert!(board.move_block(1, -1, 0).is_ok());
+        assert_eq!(
+            board.move_block(1, 0, -1),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(0),
+                Some(1),
+                Some(1),
+                None,
+                None,
+                Some(1),
+                Some(1),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn undo_move_unchecked() {
+        let mut board = Board::default();
+
+        board.undo_move_unchecked();
+    }
+
+    #[test]
+    fn apply_moves() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+        board.state = State::Solving;
+
+        let moves = [
+            FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 1).unwrap()),
+        ];
+
+        assert!(board.apply_moves(&moves).is_ok());
+        assert_eq!(board.moves, moves);
+        assert_eq!(board.blocks[0].min_position, Position::new(1, 2).unwrap());
+
+        let hash = board.hash();
+        let invalid_moves = [
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(0, 2).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(-1, 0).unwrap()),
+        ];
+
+        assert_eq!(
+            board.apply_moves(&invalid_moves),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+        assert_eq!(board.hash(), hash);
+        assert_eq!(board.moves, moves);
+        assert_eq!(
+            board.apply_moves(&[FlatBoardMove::new(1, &FlatMove::new(0, 1).unwrap())]),
+            Err(BoardError::BlockIndexOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn undo_move() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+        board.state = State::Solving;
+        board.moves = vec![
+            FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(0, -1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+        ];
+
+        assert!(board.undo_move().is_ok());
+        assert_eq!(board.moves.len(), 3);
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        assert!(board.undo_move().is_ok());
+        assert_eq!(board.moves.len(), 2);
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        assert!(board.undo_move().is_ok());
+        assert_eq!(board.moves.len(), 1);
+        assert_eq!(
+            board.grid,
+            [
+                None,
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        assert!(board.undo_move().is_ok());
+        assert_eq!(board.moves.len(), 0);
+        assert_eq!(
+            board.grid,
+            [
+                Some(0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ]
+        );
+
+        assert!(board.undo_move().is_err());
+    }
+
+    #[test]
+    fn reset() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+
+        assert!(board.reset().is_err());
+
+        board.state = State::Solving;
+        board.moves = vec![
+            FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(0, -1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+        ];
+
+        assert!(board.reset().is_ok());
+        assert_eq!(board.moves.len(), 0);
+    }
+
+    #[test]
+    fn update_timer() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::TWO_BY_TWO, 2, 1).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+        board.state = State::Solving;
+
+        assert_eq!(board.elapsed(100), None);
+
+        board.update_timer(100);
+
+        assert_eq!(board.started_solving_at, Some(100));
+        assert_eq!(board.finished_at, None);
+        assert_eq!(board.elapsed(250), Some(150));
+
+        board.move_block(0, 1, 0).unwrap();
+        board.update_timer(300);
+        board.update_timer(400);
+
+        assert_eq!(board.state, State::Solved);
+        assert_eq!(board.finished_at, Some(300));
+        assert_eq!(board.elapsed(1000), Some(200));
+
+        board.undo_move().unwrap();
+        board.update_timer(500);
+
+        assert_eq!(board.started_solving_at, Some(100));
+        assert_eq!(board.finished_at, None);
+
+        board.reset().unwrap();
+        board.update_timer(600);
+
+        assert_eq!(board.started_solving_at, None);
+        assert_eq!(board.elapsed(600), None);
+    }
+
+    #[test]
+    fn record_move_times() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+        board.state = State::Solving;
+
+        board.move_block(0, 1, 0).unwrap();
+        board.record_move_times(None);
+        board.move_block(0, 0, 1).unwrap();
+        board.record_move_times(Some(100));
+        board.record_move_times(Some(200));
+
+        assert_eq!(board.move_times, vec![None, Some(100)]);
+
+        board.undo_move().unwrap();
+        board.record_move_times(Some(300));
+
+        assert_eq!(board.move_times, vec![None]);
+
+        board.move_block(0, -1, 0).unwrap();
+        board.record_move_times(Some(400));
+
+        assert_eq!(board.move_times, vec![None, Some(400)]);
+
+        board.reset().unwrap();
+        board.record_move_times(Some(500));
+
+        assert!(board.move_times.is_empty());
+    }
+
+    #[test]
+    fn at_move() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+
+        board.state = State::Solving;
+        board.moves = vec![
+            FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(0, -1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+        ];
+
+        let snapshot = board.at_move(2).unwrap();
+
+        assert_eq!(snapshot.moves, board.moves[..2]);
+        assert_eq!(
+            snapshot.blocks[0].min_position,
+            Position::new(1, 1).unwrap()
+        );
+        assert_eq!(snapshot.state, State::Solving);
+
+        let snapshot = board.at_move(0).unwrap();
+
+        assert!(snapshot.moves.is_empty());
+        assert_eq!(
+            snapshot.blocks[0].min_position,
+            Position::new(0, 0).unwrap()
+        );
+
+        assert_eq!(board.at_move(4).unwrap().hash(), board.hash());
+        assert_eq!(
+            board.at_move(5).err(),
+            Some(BoardError::MoveIndexOutOfBounds)
+        );
+        assert_eq!(board.moves.len(), 4);
+        assert_eq!(board.blocks[0].min_position, Position::new(2, 0).unwrap());
+    }
+
+    #[test]
+    fn fork() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+
+        board.id = 7;
+        board.version = 3;
+        board.state = State::Solving;
+        board.started_solving_at = Some(1_000);
+        board.moves = vec![FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap())];
+
+        let fork = board.fork(true);
+
+        assert_eq!(fork.id, 0);
+        assert_eq!(fork.version, 0);
+        assert_eq!(fork.moves, board.moves);
+        assert_eq!(fork.state, State::Solving);
+        assert_eq!(fork.started_solving_at, Some(1_000));
+
+        let fork = board.fork(false);
+
+        assert_eq!(fork.id, 0);
+        assert!(fork.moves.is_empty());
+        assert_eq!(fork.blocks, board.blocks);
+        assert_eq!(fork.state, State::ReadyToSolve);
+        assert_eq!(fork.started_solving_at, None);
+        assert_eq!(board.moves.len(), 1);
+
+        board.state = State::Archived;
+
+        assert_eq!(board.fork(true).state, State::Archived);
+        assert_eq!(board.fork(false).state, State::Building);
+
+        let mut board = crate::game::layouts::find("Pennant").unwrap().board();
+        board.change_state(State::Archived).unwrap();
+
+        assert_eq!(board.fork(false).state, State::ReadyToSolve);
+        assert_eq!(board.mirrored_horizontal().state, State::ReadyToSolve);
+    }
+
+    #[test]
+    fn edit_blocks() {
+        let mut board = Board::default();
+
+        for (row, col) in [(0, 0), (0, 1), (0, 2)] {
+            board
+                .add_block(PositionedBlock::new(Block::ONE_BY_ONE, row, col).unwrap())
+                .unwrap();
+        }
+
+        board
+            .edit_blocks(
+                EditAction::SetLocked {
+                    block_idx: 1,
+                    locked: true,
+                },
+                5,
+            )
+            .unwrap();
+
+        assert_eq!(
+            board.edits,
+            vec![Edit {
+                at: 5,
+                event: EditEvent::ChangeBlock {
+                    block_idx: 1,
+                    block: board.blocks[1].clone(),
+                },
+            }]
+        );
+        assert_eq!(board.fork(true).edits, board.edits);
+        assert!(board.fork(false).edits.is_empty());
+
+        board
+            .edit_blocks(
+                EditAction::LinkBlocks {
+                    block_idxs: vec![0, 2],
+                },
+                6,
+            )
+            .unwrap();
+        board.edits.clear();
+        board
+            .edit_blocks(EditAction::RemoveBlock { block_idx: 0 }, 7)
+            .unwrap();
+
+        // The last block takes the place of the removed block, and is left
+        // linked to no other block
+        assert_eq!(board.blocks[0].link_group, None);
+        assert_eq!(
+            board.edits,
+            vec![
+                Edit {
+                    at: 7,
+                    event: EditEvent::RemoveBlock { block_idx: 0 },
+                },
+                Edit {
+                    at: 7,
+                    event: EditEvent::ChangeBlock {
+                        block_idx: 0,
+                        block: board.blocks[0].clone(),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let mut board = Board::default();
+
+        for positioned_block in [
+            PositionedBlock::new(Block::LShape(Corner::TopLeft), 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3)
+                .unwrap()
+                .with_axis(Some(Axis::Horizontal)),
+        ] {
+            board.add_block(positioned_block).unwrap();
+        }
+
+        board.add_wall(4, 0).unwrap();
+
+        let mirrored = board.mirrored_horizontal();
+
+        assert_eq!(mirrored.hash(), board.mirrored_hash());
+        assert_eq!(mirrored.blocks[0].block, Block::LShape(Corner::TopRight));
+        assert_eq!(mirrored.mirrored_horizontal().blocks, board.blocks);
+        assert_eq!(mirrored.goal, board.goal);
+
+        let rotated = board.rotated();
+
+        assert_eq!(rotated.blocks[0].block, Block::LShape(Corner::BottomRight));
+        assert_eq!(rotated.blocks[0].min_position, Position::new(3, 2).unwrap());
+        assert_eq!(rotated.blocks[2].axis, Some(Axis::Horizontal));
+        assert_eq!(rotated.walls, vec![Position::new(0, 3).unwrap()]);
+        assert_eq!(
+            rotated.goal,
+            Goal {
+                block: Block::TWO_BY_TWO,
+                row: 0,
+                col: 1,
+            }
+        );
+        assert_eq!(rotated.grid, Board::grid_from_blocks(&rotated.blocks));
+        assert_eq!(rotated.rotated().blocks, board.blocks);
+    }
+
+    #[test]
+    fn repetitions() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+        board.state = State::Solving;
+
+        assert_eq!(board.repetitions(), (0, false));
+
+        board.move_block(0, 1, 0).unwrap();
+        board.move_block(0, 0, 1).unwrap();
+
+        assert_eq!(board.repetitions(), (0, false));
+
+        board.move_block(0, -1, -1).unwrap();
+
+        assert_eq!(board.repetitions(), (1, true));
+
+        board.move_block(0, 0, 1).unwrap();
+        board.move_block(0, 1, 0).unwrap();
+
+        assert_eq!(board.repetitions(), (2, true));
+
+        board.move_block(0, 1, 0).unwrap();
+
+        assert_eq!(board.repetitions(), (2, false));
+    }
+
+    #[test]
+    fn start_hash() {
+        let mut board = Board::default();
+
+        board
+            .add_block(PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap())
+            .unwrap();
+        board.state = State::Solving;
+
+        let hash = board.hash();
+
+        board.move_block(0, 1, 0).unwrap();
+        board.move_block(0, 0, 1).unwrap();
+
+        assert_ne!(board.hash(), hash);
+        assert_eq!(board.start_hash(), hash);
+    }
+
+    #[test]
+    fn render_text() {
+        let mut board = Board::default();
+
+        board.add_wall(4, 3).unwrap();
+        board
+            .add_block(PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap())
+            .unwrap();
+        board
+            .add_block(PositionedBlock::new(Block::ONE_BY_TWO, 2, 2).unwrap())
+            .unwrap();
+        board
+            .add_block(PositionedBlock::new(Block::LShape(Corner::TopLeft), 3, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(board.render_text(), ".AA.\n.AA.\n..BB\n.C..\nCC.#\n");
+    }
+
+    #[test]
+    fn diff() {
+        let mut board = Board::default();
+
+        let block = PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+
+        let block = PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap();
+        board.update_grid_range(&block.range, Some(board.blocks.len()));
+        board.blocks.push(block);
+
+        board.state = State::Solving;
+        board.moves = vec![
+            FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(1, 0).unwrap()),
+            FlatBoardMove::new(0, &FlatMove::new(0, 1).unwrap()),
+        ];
+
+        let start = board.at_move(0).unwrap();
+
+        assert_eq!(
+            start.diff(&board).unwrap(),
+            vec![FlatBoardMove {
+                block_idx: 0,
+                row_diff: 1,
+                col_diff: 2,
+            }]
+        );
+        assert_eq!(
+            board.diff(&start).unwrap(),
+            vec![FlatBoardMove {
+                block_idx: 0,
+                row_diff: -1,
+                col_diff: -2,
+            }]
+        );
+        assert!(board.diff(&board).unwrap().is_empty());
+        assert_eq!(
+            board.diff(&Board::default()).err(),
+            Some(BoardError::BoardsIncompatible)
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::blocks::{Block, Positioned as PositionedBlock};
+
+// Change made to the board's blocks while building it, where changed blocks
+// are recorded as they are once changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = EditEvent)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event {
+    AddBlock {
+        block: PositionedBlock,
+    },
+    ChangeBlock {
+        block_idx: usize,
+        block: PositionedBlock,
+    },
+    // the last block takes the place of the removed block
+    RemoveBlock {
+        block_idx: usize,
+    },
+}
+
+// Edit to make to the board's blocks while building it, which the board
+// records as the events it leads to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    AddBlock(PositionedBlock),
+    ChangeBlock { block_idx: usize, block: Block },
+    RotateBlock { block_idx: usize },
+    SetLocked { block_idx: usize, locked: bool },
+    LinkBlocks { block_idxs: Vec<usize> },
+    UnlinkBlock { block_idx: usize },
+    RemoveBlock { block_idx: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Edit {
+    // time, in milliseconds since the Unix epoch, at which the edit was made
+    pub at: u64,
+    pub event: Event,
+}
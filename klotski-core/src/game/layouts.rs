@@ -0,0 +1,99 @@
+use super::{board::Board, notation};
+
+// Well-known starting layout, given as its name, the length of its optimal
+// solution, the difficulty of that solution and its notation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub name: &'static str,
+    pub optimal_moves: usize,
+    pub difficulty: u32,
+    pub notation: &'static str,
+}
+
+impl Layout {
+    // Board of the layout, ready to solve
+    pub fn board(self) -> Board {
+        notation::parse(self.notation).unwrap()
+    }
+}
+
+// Well-known starting layouts, including the classic Chinese layouts, where
+// the 2x2 block must reach the bottom middle of the board, and the Pennant
+// puzzle, where it must reach the bottom left
+pub const LAYOUTS: [Layout; 5] = [
+    Layout {
+        name: "Heng Dao Li Ma",
+        optimal_moves: 81,
+        difficulty: 171,
+        notation: "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1",
+    },
+    Layout {
+        name: "Zhi Hui Ruo Ding",
+        optimal_moves: 70,
+        difficulty: 141,
+        notation: "ABBC/ABBC/DEEF/GHIJ/G2J 2x2@3,1",
+    },
+    Layout {
+        name: "Qi Tou Bing Jin",
+        optimal_moves: 60,
+        difficulty: 124,
+        notation: "ABBC/ABBC/DEFG/HIIJ/H2J 2x2@3,1",
+    },
+    Layout {
+        name: "Bing Fen San Lu",
+        optimal_moves: 72,
+        difficulty: 148,
+        notation: "ABBC/DBBE/DFFE/GHIJ/G2J 2x2@3,1",
+    },
+    Layout {
+        name: "Pennant",
+        optimal_moves: 57,
+        difficulty: 104,
+        notation: "AABB/AACC/2DE/FGHH/FGII 2x2@3,0",
+    },
+];
+
+// Layout with the given name, ignoring case
+pub fn find(name: &str) -> Option<Layout> {
+    LAYOUTS
+        .into_iter()
+        .find(|layout| layout.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis;
+    use crate::game::board::State as BoardState;
+    use crate::solver::{self, Options};
+
+    #[test]
+    fn test_boards_are_optimal() {
+        for layout in LAYOUTS {
+            let board = layout.board();
+
+            assert_eq!(board.state, BoardState::ReadyToSolve, "{}", layout.name);
+
+            let options = Options {
+                deterministic: true,
+                ..Options::default()
+            };
+            let moves = solver::solve(&board, &options).unwrap().unwrap();
+
+            assert_eq!(moves.len(), layout.optimal_moves, "{}", layout.name);
+            assert_eq!(
+                analysis::difficulty(&board, &moves),
+                layout.difficulty,
+                "{}",
+                layout.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find("pennant"), Some(LAYOUTS[4]));
+        assert_eq!(find("Heng Dao Li Ma"), Some(LAYOUTS[0]));
+        assert_eq!(find("Forget-Me-Not Two"), None);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod blocks;
+pub mod board;
+pub mod history;
+pub mod layouts;
+pub mod moves;
+pub mod notation;
+pub mod utils;
+pub mod validation;
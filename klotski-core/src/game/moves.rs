@@ -3,7 +3,8 @@ use utoipa::ToSchema;
 
 use super::board::Board;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum Step {
     Up,
     Down,
@@ -53,7 +54,7 @@ pub struct FlatMove {
 }
 
 impl FlatMove {
-    const MAX_DIFF: u8 = Board::MIN_EMPTY_CELLS;
+    const MAX_DIFF: u8 = Board::MAX_EMPTY_CELLS;
 
     pub fn new(row_diff: i8, col_diff: i8) -> Option<Self> {
         if u8::try_from(row_diff.abs() + col_diff.abs()).unwrap() <= Self::MAX_DIFF {
@@ -69,6 +70,32 @@ impl FlatMove {
             col_diff: steps.iter().fold(0, |acc, step| acc + step.col_diff()),
         }
     }
+
+    // Number of unit steps made by the move
+    pub fn steps(&self) -> usize {
+        usize::from(self.row_diff.unsigned_abs() + self.col_diff.unsigned_abs())
+    }
+}
+
+// Move along with the path the block takes, in the order its unit steps are
+// made
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DetailedMove {
+    pub row_diff: i8,
+    pub col_diff: i8,
+    pub steps: Vec<Step>,
+}
+
+impl DetailedMove {
+    pub fn from_steps(steps: Vec<Step>) -> Self {
+        let flat_move = FlatMove::from_steps(&steps);
+
+        Self {
+            row_diff: flat_move.row_diff,
+            col_diff: flat_move.col_diff,
+            steps,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -94,6 +121,11 @@ impl FlatBoardMove {
             col_diff: -self.col_diff,
         }
     }
+
+    // Number of unit steps made by the move
+    pub fn steps(&self) -> usize {
+        usize::from(self.row_diff.unsigned_abs() + self.col_diff.unsigned_abs())
+    }
 }
 
 #[cfg(test)]
@@ -113,6 +145,8 @@ mod tests {
         assert_eq!(flat_move_two.col_diff, -1);
 
         assert_eq!(flat_move_one, flat_move_two);
+        assert_eq!(flat_move_one.steps(), 2);
+        assert_eq!(FlatMove::from_steps(&[Step::Down]).steps(), 1);
     }
 
     #[test]
@@ -0,0 +1,509 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use super::{
+    blocks::{Axis, Block, Corner, Positioned as PositionedBlock},
+    board::{Board, Goal, Target},
+    utils::Position,
+};
+use crate::errors::board::Error as BoardError;
+
+const CORNERS: [(Corner, &str); 4] = [
+    (Corner::TopLeft, "tl"),
+    (Corner::TopRight, "tr"),
+    (Corner::BottomLeft, "bl"),
+    (Corner::BottomRight, "br"),
+];
+
+// Notation of the block's shape, e.g. "2x1" for the block covering two rows
+// and one column, or "Ltr" for the L-shaped block missing its top-right corner
+fn shape(block: Block) -> String {
+    match block {
+        Block::Rectangle { rows, cols } => format!("{rows}x{cols}"),
+        Block::LShape(corner) => {
+            let (_, code) = CORNERS.iter().find(|(other, _)| *other == corner).unwrap();
+            format!("L{code}")
+        }
+    }
+}
+
+fn parse_shape(notation: &str) -> Option<Block> {
+    if let Some(code) = notation.strip_prefix('L') {
+        return CORNERS
+            .iter()
+            .find(|(_, other)| *other == code)
+            .map(|(corner, _)| Block::LShape(*corner));
+    }
+
+    let (rows, cols) = notation.split_once('x')?;
+
+    Some(Block::Rectangle {
+        rows: rows.parse().ok()?,
+        cols: cols.parse().ok()?,
+    })
+}
+
+// Block covering exactly the given cells, if there is one
+fn block_from_cells(cells: &[(u8, u8)]) -> Option<PositionedBlock> {
+    let min_row = cells.iter().map(|(row, _)| *row).min()?;
+    let max_row = cells.iter().map(|(row, _)| *row).max()?;
+    let min_col = cells.iter().map(|(_, col)| *col).min()?;
+    let max_col = cells.iter().map(|(_, col)| *col).max()?;
+
+    let (rows, cols) = (max_row - min_row + 1, max_col - min_col + 1);
+
+    let block = if cells.len() == usize::from(rows * cols) {
+        Block::Rectangle { rows, cols }
+    } else {
+        CORNERS
+            .iter()
+            .map(|(corner, _)| Block::LShape(*corner))
+            .find(|block| {
+                block.cells().len() == cells.len()
+                    && block
+                        .cells()
+                        .iter()
+                        .all(|(row, col)| cells.contains(&(min_row + row, min_col + col)))
+            })?
+    };
+
+    PositionedBlock::new(block, min_row, min_col)
+}
+
+// Single-line notation of the board's layout, of the form
+// "<rows> <goal> <constraints>". The rows are separated by '/', with each
+// block's cells marked by a letter, walls by '#', and runs of empty cells by
+// their length. Letters are assigned in the order of each block's first cell,
+// so that boards with the same layout share the same notation. The goal is the
+// goal block's shape followed by '@' and its row and column, and the
+// constraints, which are left out if no block is constrained, list the letter
+// of each constrained block followed by '-' if it only moves horizontally, '|'
+// if it only moves vertically, '!' if it is locked, and '+' if it is linked,
+// followed by the number of its link group. Link groups are numbered from 1 in
+// the order of their first letter, and a '+' without a number is in group 1.
+// Each of the board's targets follows as a field of its own, of the form
+// "*<shape>@<cells>", where the cells are given by their row and column and
+// separated by ';'. The number of cells left empty is not written, since it is
+// the number of cells covered by neither blocks nor walls once every block has
+// been added.
+//
+// For example, the classic layout is "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1", and
+// the layout whose goal block may exit on either side of the bottom row is
+// "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 *2x2@3,0;3,1;4,0;4,1 *2x2@3,2;3,3;4,2;4,3".
+pub fn serialize(board: &Board) -> String {
+    let mut order: Vec<usize> = (0..board.blocks.len()).collect();
+    order.sort_by_key(|&block_idx| board.blocks[block_idx].range.iter().min().copied());
+
+    let mut cells = [b'.'; (Board::ROWS * Board::COLS) as usize];
+
+    for wall in &board.walls {
+        cells[usize::from(wall.row * Board::COLS + wall.col)] = b'#';
+    }
+
+    for (letter, &block_idx) in (b'A'..=b'Z').zip(&order) {
+        for (row, col) in &board.blocks[block_idx].range {
+            cells[usize::from(row * Board::COLS + col)] = letter;
+        }
+    }
+
+    let mut rows = vec![];
+
+    for row in cells.chunks(usize::from(Board::COLS)) {
+        let mut text = String::new();
+        let mut num_empty = 0;
+
+        for &cell in row {
+            if cell == b'.' {
+                num_empty += 1;
+                continue;
+            }
+
+            if num_empty > 0 {
+                write!(text, "{num_empty}").unwrap();
+                num_empty = 0;
+            }
+
+            text.push(char::from(cell));
+        }
+
+        if num_empty > 0 {
+            write!(text, "{num_empty}").unwrap();
+        }
+
+        rows.push(text);
+    }
+
+    let mut notation = format!(
+        "{} {}@{},{}",
+        rows.join("/"),
+        shape(board.goal.block),
+        board.goal.row,
+        board.goal.col
+    );
+
+    let mut constraints = vec![];
+    let mut link_groups = BTreeMap::new();
+
+    for (letter, &block_idx) in (b'A'..=b'Z').zip(&order) {
+        let positioned_block = &board.blocks[block_idx];
+
+        if positioned_block.axis.is_none()
+            && !positioned_block.locked
+            && positioned_block.link_group.is_none()
+        {
+            continue;
+        }
+
+        let mut constraint = String::from(char::from(letter));
+
+        match positioned_block.axis {
+            Some(Axis::Horizontal) => constraint.push('-'),
+            Some(Axis::Vertical) => constraint.push('|'),
+            None => {}
+        }
+
+        if positioned_block.locked {
+            constraint.push('!');
+        }
+
+        if let Some(link_group) = positioned_block.link_group {
+            let num_link_groups = link_groups.len();
+            let number = *link_groups.entry(link_group).or_insert(num_link_groups + 1);
+
+            write!(constraint, "+{number}").unwrap();
+        }
+
+        constraints.push(constraint);
+    }
+
+    if !constraints.is_empty() {
+        write!(notation, " {}", constraints.join(",")).unwrap();
+    }
+
+    for target in &board.targets {
+        let cells: Vec<String> = target
+            .cells
+            .iter()
+            .map(|cell| format!("{},{}", cell.row, cell.col))
+            .collect();
+
+        write!(notation, " *{}@{}", shape(target.block), cells.join(";")).unwrap();
+    }
+
+    notation
+}
+
+// Goal of the notation of the form "<shape>@<row>,<col>"
+fn parse_goal(notation: &str) -> Option<Goal> {
+    let (block, position) = notation.split_once('@')?;
+    let (row, col) = position.split_once(',')?;
+
+    Some(Goal {
+        block: parse_shape(block)?,
+        row: row.parse().ok()?,
+        col: col.parse().ok()?,
+    })
+}
+
+// Target of the notation of the form "<shape>@<cells>", as produced by
+// serialize without the leading '*'
+fn parse_target(notation: &str) -> Option<Target> {
+    let (block, cells) = notation.split_once('@')?;
+
+    let cells = cells
+        .split(';')
+        .map(|cell| {
+            let (row, col) = cell.split_once(',')?;
+
+            Position::new(row.parse().ok()?, col.parse().ok()?)
+        })
+        .collect::<Option<_>>()?;
+
+    Some(Target {
+        block: parse_shape(block)?,
+        cells,
+    })
+}
+
+// Axis, locking and link group of each constrained letter, from the
+// constraints of the notation of the form "<letter><flags>,..."
+#[derive(Default)]
+struct Constraints {
+    axes: BTreeMap<u8, Axis>,
+    locked: BTreeSet<u8>,
+    link_groups: BTreeMap<u8, u8>,
+}
+
+fn parse_constraints(notation: &str, letters: &BTreeMap<u8, Vec<(u8, u8)>>) -> Option<Constraints> {
+    let mut constraints = Constraints::default();
+
+    for constraint in notation.split(',').filter(|text| !text.is_empty()) {
+        let (letter, flags) = constraint.as_bytes().split_first()?;
+
+        if !letters.contains_key(letter) {
+            return None;
+        }
+
+        let mut flags = flags.iter().peekable();
+
+        while let Some(flag) = flags.next() {
+            match flag {
+                b'-' => {
+                    constraints.axes.insert(*letter, Axis::Horizontal);
+                }
+                b'|' => {
+                    constraints.axes.insert(*letter, Axis::Vertical);
+                }
+                b'!' => {
+                    constraints.locked.insert(*letter);
+                }
+                b'+' => {
+                    let link_group = flags
+                        .next_if(|byte| byte.is_ascii_digit())
+                        .map_or(1, |byte| byte - b'0');
+
+                    constraints.link_groups.insert(*letter, link_group);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    let link_groups = constraints.link_groups.values();
+
+    // a single block cannot be linked to any other block
+    if link_groups.clone().any(|link_group| {
+        link_groups
+            .clone()
+            .filter(|other| *other == link_group)
+            .count()
+            == 1
+    }) {
+        return None;
+    }
+
+    Some(constraints)
+}
+
+// Build the board from its notation, as produced by serialize. The blocks are
+// added in the order of their letters, after which the number of cells left
+// empty is set to the number of cells covered by neither blocks nor walls, if
+// it is allowed, and the targets are set. If the notation is malformed or any
+// letter does not cover the cells of a single block, the NotationInvalid error
+// is returned, if the goal, walls or blocks cannot be placed, the
+// BlockPlacementInvalid error is returned, and if the targets are not valid,
+// the TargetsInvalid error is returned.
+pub fn parse(notation: &str) -> Result<Board, BoardError> {
+    let mut fields = notation.split(' ');
+
+    let (Some(layout), Some(goal)) = (fields.next(), fields.next()) else {
+        return Err(BoardError::NotationInvalid);
+    };
+
+    let mut fields = fields.peekable();
+
+    let constraints = fields
+        .next_if(|field| !field.starts_with('*'))
+        .unwrap_or_default();
+
+    let targets = fields
+        .map(|field| {
+            field
+                .strip_prefix('*')
+                .and_then(parse_target)
+                .ok_or(BoardError::NotationInvalid)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rows: Vec<&str> = layout.split('/').collect();
+
+    if rows.len() != usize::from(Board::ROWS) {
+        return Err(BoardError::NotationInvalid);
+    }
+
+    let mut walls = vec![];
+    let mut letters: BTreeMap<u8, Vec<(u8, u8)>> = BTreeMap::new();
+
+    for (row, text) in (0..).zip(rows) {
+        let mut col: u8 = 0;
+
+        for byte in text.bytes() {
+            match byte {
+                b'1'..=b'9' => {
+                    col = col
+                        .checked_add(byte - b'0')
+                        .ok_or(BoardError::NotationInvalid)?;
+                    continue;
+                }
+                b'#' => walls.push((row, col)),
+                b'A'..=b'Z' => letters.entry(byte).or_default().push((row, col)),
+                _ => return Err(BoardError::NotationInvalid),
+            }
+
+            col += 1;
+
+            if col > Board::COLS {
+                return Err(BoardError::NotationInvalid);
+            }
+        }
+
+        if col != Board::COLS {
+            return Err(BoardError::NotationInvalid);
+        }
+    }
+
+    let goal = parse_goal(goal).ok_or(BoardError::NotationInvalid)?;
+
+    let constraints =
+        parse_constraints(constraints, &letters).ok_or(BoardError::NotationInvalid)?;
+
+    let mut board = Board::default();
+    board.set_goal(goal)?;
+
+    for (row, col) in walls {
+        board.add_wall(row, col)?;
+    }
+
+    for (letter, cells) in letters {
+        let positioned_block = block_from_cells(&cells)
+            .ok_or(BoardError::NotationInvalid)?
+            .with_axis(constraints.axes.get(&letter).copied())
+            .with_locked(constraints.locked.contains(&letter))
+            .with_link_group(constraints.link_groups.get(&letter).copied());
+
+        board.add_block(positioned_block)?;
+    }
+
+    let num_empty = board.grid.iter().filter(|cell| cell.is_none()).count() - board.walls.len();
+
+    if let Ok(empty_cells) = u8::try_from(num_empty) {
+        if empty_cells != board.empty_cells
+            && (Board::MIN_EMPTY_CELLS..=Board::MAX_EMPTY_CELLS).contains(&empty_cells)
+        {
+            board.set_empty_cells(empty_cells)?;
+        }
+    }
+
+    if !targets.is_empty() {
+        board.set_targets(targets)?;
+    }
+
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::State as BoardState;
+
+    const CLASSIC: &str = "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1";
+
+    #[test]
+    fn parse_classic() {
+        let board = parse(CLASSIC).unwrap();
+
+        assert_eq!(board.blocks.len(), 10);
+        assert_eq!(board.blocks[1].block, Block::TWO_BY_TWO);
+        assert_eq!(board.blocks[4].block, Block::ONE_BY_TWO);
+        assert_eq!(board.goal, Goal::default());
+        assert_eq!(serialize(&board), CLASSIC);
+    }
+
+    #[test]
+    fn serialize_is_canonical() {
+        let mut board = Board::default();
+
+        for (block, row, col) in [
+            (Block::ONE_BY_ONE, 4, 3),
+            (Block::TWO_BY_TWO, 0, 1),
+            (Block::ONE_BY_ONE, 4, 0),
+        ] {
+            board
+                .add_block(PositionedBlock::new(block, row, col).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(serialize(&board), "1AA1/1AA1/4/4/B2C 2x2@3,1");
+    }
+
+    #[test]
+    fn round_trip() {
+        let notations = [
+            "ABBC/ABBC/D2E/DFGG/#HHG 2x2@3,1",
+            "AAAB/CDDE/CDDE/FFGH/I2J 2x2@3,1 B|,D-,J!",
+            "ABBC/ABBC/DEFG/DDGG/H2I 2x2@3,1 A|!",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 G+1,H+1",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 A+1,C+1,G-+2,H+2",
+            "ABBC/ABBC/DEFG/2FF/H2I Lbl@2,1",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 *2x2@3,0;3,1;4,0;4,1 *2x2@3,2;3,3;4,2;4,3",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 J! *1x1@0,0",
+        ];
+
+        for notation in notations {
+            assert_eq!(serialize(&parse(notation).unwrap()), notation);
+        }
+
+        assert_eq!(
+            serialize(&parse("ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 G+,H+").unwrap()),
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 G+1,H+1"
+        );
+    }
+
+    #[test]
+    fn round_trip_board() {
+        let mut board = parse("ABBC/ABBC/DEEF/DG1F/I2J 2x2@3,1").unwrap();
+
+        assert_eq!(board.empty_cells, 3);
+        assert_eq!(board.state, BoardState::ReadyToSolve);
+
+        board
+            .set_targets(vec![Target {
+                block: Block::ONE_BY_ONE,
+                cells: vec![Position::new(0, 0).unwrap()],
+            }])
+            .unwrap();
+
+        let parsed = parse(&serialize(&board)).unwrap();
+
+        assert_eq!(parsed.empty_cells, board.empty_cells);
+        assert_eq!(parsed.targets, board.targets);
+        assert_eq!(parsed.hash(), board.hash());
+        assert_eq!(parsed.state, board.state);
+    }
+
+    #[test]
+    fn invalid_notation() {
+        let notations = [
+            "",
+            "ABBC/ABBC/DEEF/DGHF/I2J",
+            "ABBC/ABBC/DEEF/DGHF 2x2@3,1",
+            "ABBC/ABBC/DEEF/DGHF/I3J 2x2@3,1",
+            "ABBC/ABBC/DEEF/DGHF/I1J 2x2@3,1",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3",
+            "ABBC/ABBC/DEEF/DGHF/I2J Lxx@3,1",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 K!",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 A?",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 G+",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 G+1,H+2",
+            "ABBC/ABBC/DEEF/DGHF/I2A 2x2@3,1",
+            "ABBC/ABBC/DEEF/DGHF/i2j 2x2@3,1",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 *1x1",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 *1x1@0",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 *1x1@0,0 J!",
+            "ABBC/ABBC/DEEF/DGHF/I2J 2x2@3,1 J! G!",
+        ];
+
+        for notation in notations {
+            assert_eq!(
+                parse(notation).err(),
+                Some(BoardError::NotationInvalid),
+                "{notation}"
+            );
+        }
+
+        assert_eq!(
+            parse("ABBC/ABBC/DEEF/DGHF/I2J 2x2@4,1").err(),
+            Some(BoardError::BlockPlacementInvalid)
+        );
+    }
+}
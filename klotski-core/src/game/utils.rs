@@ -1,4 +1,5 @@
 use std::fmt::{self, Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -47,6 +48,13 @@ impl Position {
     }
 }
 
+// Current time in milliseconds since the Unix epoch
+pub fn unix_millis() -> u64 {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+    u64::try_from(elapsed.as_millis()).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
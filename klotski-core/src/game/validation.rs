@@ -0,0 +1,161 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::{
+    blocks::Positioned as PositionedBlock,
+    board::{Board, Goal, NotReadyReason},
+};
+
+// Problem with the layout which keeps a board from being created from it,
+// where blocks with a problem are left out of the rest of the validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[schema(as = LayoutIssue)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum Issue {
+    // the goal block does not fit within the board at the goal position
+    GoalOutOfBounds,
+    // the block does not fit within the board
+    OutOfBounds { block_idx: usize },
+    // the block covers a cell which is already covered by the other block
+    Overlap { block_idx: usize, other_idx: usize },
+    // the block covers more cells than are left free once the board's empty
+    // cells are kept free
+    TooFewCellsFree { block_idx: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub issues: Vec<Issue>,
+    // number of blocks of the goal block's shape
+    pub goal_block_count: usize,
+    pub not_ready_reasons: Vec<NotReadyReason>,
+}
+
+impl Report {
+    // Whether a board can be created from the layout and solved as is
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty() && self.not_ready_reasons.is_empty()
+    }
+}
+
+// Validate the layout of the given blocks, where blocks which do not fit
+// within the board are None, as if they were added in order to a new board
+// with the given goal, without stopping at the first problem
+pub fn validate(blocks: &[Option<PositionedBlock>], goal: Goal) -> Report {
+    let mut board = Board::default();
+    let mut issues = vec![];
+
+    if board.set_goal(goal).is_err() {
+        issues.push(Issue::GoalOutOfBounds);
+    }
+
+    // index in blocks of each block added to the board
+    let mut added_idxs = vec![];
+
+    for (block_idx, positioned_block) in blocks.iter().enumerate() {
+        let Some(positioned_block) = positioned_block else {
+            issues.push(Issue::OutOfBounds { block_idx });
+            continue;
+        };
+
+        let overlapped_idx = positioned_block
+            .range
+            .iter()
+            .find_map(|(row, col)| board.grid[usize::from(row * Board::COLS + col)]);
+
+        if let Some(overlapped_idx) = overlapped_idx {
+            issues.push(Issue::Overlap {
+                block_idx,
+                other_idx: added_idxs[overlapped_idx],
+            });
+            continue;
+        }
+
+        if board.add_block(positioned_block.clone()).is_err() {
+            issues.push(Issue::TooFewCellsFree { block_idx });
+            continue;
+        }
+
+        added_idxs.push(block_idx);
+    }
+
+    let goal_block_count = board
+        .blocks
+        .iter()
+        .filter(|positioned_block| positioned_block.block == board.goal.block)
+        .count();
+
+    Report {
+        issues,
+        goal_block_count,
+        not_ready_reasons: board.readiness_report(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::blocks::Block;
+
+    #[test]
+    fn valid_layout() {
+        let blocks = [
+            (Block::TWO_BY_ONE, 0, 0),
+            (Block::TWO_BY_TWO, 0, 1),
+            (Block::TWO_BY_ONE, 0, 3),
+            (Block::TWO_BY_ONE, 2, 0),
+            (Block::ONE_BY_TWO, 2, 1),
+            (Block::TWO_BY_ONE, 2, 3),
+            (Block::ONE_BY_ONE, 3, 1),
+            (Block::ONE_BY_ONE, 3, 2),
+            (Block::ONE_BY_ONE, 4, 0),
+            (Block::ONE_BY_ONE, 4, 3),
+        ]
+        .map(|(block, row, col)| PositionedBlock::new(block, row, col));
+
+        let report = validate(&blocks, Goal::default());
+
+        assert!(report.is_valid());
+        assert_eq!(report.goal_block_count, 1);
+    }
+
+    #[test]
+    fn invalid_layout() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1),
+            PositionedBlock::new(Block::TWO_BY_ONE, 4, 0),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 2),
+            PositionedBlock::new(Block::TWO_BY_TWO, 3, 2),
+        ];
+
+        let report = validate(
+            &blocks,
+            Goal {
+                block: Block::TWO_BY_TWO,
+                row: 4,
+                col: 1,
+            },
+        );
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.issues,
+            vec![
+                Issue::GoalOutOfBounds,
+                Issue::OutOfBounds { block_idx: 1 },
+                Issue::Overlap {
+                    block_idx: 2,
+                    other_idx: 0
+                },
+            ]
+        );
+        assert_eq!(report.goal_block_count, 2);
+        assert_eq!(
+            report.not_ready_reasons,
+            vec![
+                NotReadyReason::GoalBlockCount { count: 2 },
+                NotReadyReason::CellsFree { count: 10 },
+            ]
+        );
+    }
+}
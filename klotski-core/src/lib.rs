@@ -0,0 +1,16 @@
+#![warn(clippy::pedantic)]
+#![allow(
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc,
+    clippy::must_use_candidate,
+    clippy::return_self_not_must_use
+)]
+
+pub mod analysis;
+pub mod bitboard;
+pub mod errors;
+pub mod game;
+pub mod ordering;
+pub mod randomizer;
+pub mod seen;
+pub mod solver;
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::game::{blocks::Positioned as PositionedBlock, board::Board, moves::FlatMove};
+
+// Order in which the solver expands the next moves of a board. Moves with a
+// lower rank are expanded first, and moves with equal ranks are expanded in the
+// order of the board's next moves.
+pub trait MoveOrdering: Send + Sync {
+    fn rank(&self, board: &Board, block_idx: usize, move_: &FlatMove) -> usize;
+}
+
+// Expands the next moves in the order of the board's next moves
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Unordered;
+
+impl MoveOrdering for Unordered {
+    fn rank(&self, _board: &Board, _block_idx: usize, _move: &FlatMove) -> usize {
+        0
+    }
+}
+
+// Expands the moves which push the goal block toward the goal position first,
+// followed by the moves which free cells between the goal block and the goal
+// position, followed by every other move
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GoalFirst;
+
+impl GoalFirst {
+    // Cells covered by the positioned block after making the move
+    fn moved_cells(positioned_block: &PositionedBlock, move_: &FlatMove) -> Vec<(u8, u8)> {
+        positioned_block
+            .range
+            .iter()
+            .filter_map(|(row, col)| {
+                Some((
+                    row.checked_add_signed(move_.row_diff)?,
+                    col.checked_add_signed(move_.col_diff)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn distance(board: &Board, (row, col): (u8, u8)) -> u8 {
+        row.abs_diff(board.goal.row) + col.abs_diff(board.goal.col)
+    }
+}
+
+impl MoveOrdering for GoalFirst {
+    fn rank(&self, board: &Board, block_idx: usize, move_: &FlatMove) -> usize {
+        let Some(goal_idx) = board
+            .blocks
+            .iter()
+            .position(|positioned_block| positioned_block.block == board.goal.block)
+        else {
+            return 0;
+        };
+
+        let goal_block = &board.blocks[goal_idx];
+
+        let positioned_block = &board.blocks[block_idx];
+
+        if block_idx == goal_idx {
+            let position = (
+                positioned_block.min_position.row,
+                positioned_block.min_position.col,
+            );
+
+            return match Self::moved_cells(positioned_block, move_).first() {
+                Some(cell) if Self::distance(board, *cell) < Self::distance(board, position) => 0,
+                _ => 2,
+            };
+        }
+
+        // Cells of the smallest rectangle containing both the goal block and
+        // the goal position, other than those covered by the goal block
+        let rows = goal_block.min_position.row.min(board.goal.row)
+            ..=goal_block
+                .max_position
+                .row
+                .max(board.goal.row + goal_block.block.rows() - 1);
+        let cols = goal_block.min_position.col.min(board.goal.col)
+            ..=goal_block
+                .max_position
+                .col
+                .max(board.goal.col + goal_block.block.cols() - 1);
+
+        let in_path = |cell: &(u8, u8)| {
+            rows.contains(&cell.0) && cols.contains(&cell.1) && !goal_block.range.contains(cell)
+        };
+
+        let cells_in_path = positioned_block.range.iter().filter(|cell| in_path(cell));
+        let moved_cells_in_path = Self::moved_cells(positioned_block, move_)
+            .into_iter()
+            .filter(in_path);
+
+        if moved_cells_in_path.count() < cells_in_path.count() {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+// Move ordering selected by a request
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = MoveOrderingStrategy)]
+#[serde(rename_all = "snake_case")]
+pub enum Strategy {
+    #[default]
+    GoalFirst,
+    Unordered,
+}
+
+impl Strategy {
+    pub fn ordering(self) -> Arc<dyn MoveOrdering> {
+        match self {
+            Self::GoalFirst => Arc::new(GoalFirst),
+            Self::Unordered => Arc::new(Unordered),
+        }
+    }
+}
+
+// Next moves of the board as pairs of block index and move, along with their
+// rank, sorted by rank
+pub fn ordered_moves(
+    ordering: &dyn MoveOrdering,
+    board: &mut Board,
+) -> Vec<(usize, usize, FlatMove)> {
+    let next_moves = board.get_next_moves();
+
+    let mut moves: Vec<(usize, usize, FlatMove)> = next_moves
+        .into_iter()
+        .enumerate()
+        .flat_map(|(block_idx, moves)| moves.into_iter().map(move |move_| (block_idx, move_)))
+        .map(|(block_idx, move_)| (ordering.rank(board, block_idx, &move_), block_idx, move_))
+        .collect();
+
+    moves.sort_by_key(|(rank, _, _)| *rank);
+
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{blocks::Block, board::State as BoardState};
+
+    #[test]
+    fn test_goal_first() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        let moves = ordered_moves(&GoalFirst, &mut board);
+
+        assert_eq!(
+            moves.len(),
+            board.get_next_moves().iter().map(Vec::len).sum::<usize>()
+        );
+        assert!(moves.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+        // The goal block is blocked by the two 1x1 blocks in front of it, so
+        // the moves of those blocks out of its way are expanded first
+        for (rank, block_idx, _) in &moves {
+            assert_eq!(*rank, if *block_idx <= 2 { 1 } else { 2 });
+        }
+
+        assert_eq!(moves[0].1, 1);
+        assert_eq!(
+            ordered_moves(&*Strategy::Unordered.ordering(), &mut board).len(),
+            moves.len()
+        );
+    }
+}
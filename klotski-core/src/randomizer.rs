@@ -0,0 +1,435 @@
+use std::ops::RangeInclusive;
+
+use rand::{distributions::uniform::SampleUniform, seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::errors::board::Error as BoardError;
+use crate::game::{
+    blocks::{Block, Positioned as PositionedBlock},
+    board::{Board, State as BoardState},
+    moves::FlatMove,
+    utils::Position,
+};
+use crate::solver::{self, Options};
+
+// Most moves made after the requested number of moves when shuffling, for the
+// board to no longer be solved
+const MAX_EXTRA_MOVES: usize = 1000;
+
+// Most random moves which may be requested when shuffling, which bounds the
+// time spent shuffling a board
+pub const MAX_SHUFFLE_MOVES: usize = 1000;
+
+// Most layouts tried when shuffling, for the goal block to be moved away from
+// the goal position
+const MAX_SHUFFLE_ATTEMPTS: usize = 10;
+
+// Most candidate boards generated and solved when randomizing for a
+// difficulty, for the optimal solution to be within the requested range
+const MAX_DIFFICULTY_ATTEMPTS: u32 = 20;
+
+// Relative probabilities of the remaining blocks
+const BLOCK_WEIGHTS: [(Block, usize); 5] = [
+    (Block::ONE_BY_ONE, 6),
+    (Block::TWO_BY_ONE, 4),
+    (Block::ONE_BY_TWO, 2),
+    (Block::Rectangle { rows: 3, cols: 1 }, 1),
+    (Block::Rectangle { rows: 1, cols: 3 }, 1),
+];
+
+fn get_random<T>(min: T, max: T, rng: &mut ChaCha8Rng) -> T
+where
+    T: PartialOrd + Copy + SampleUniform,
+{
+    rng.gen_range(min..=max)
+}
+
+fn get_cells_free(board: &Board) -> Vec<u8> {
+    board
+        .grid
+        .iter()
+        .enumerate()
+        .map(|(i, &cell)| (u8::try_from(i).unwrap(), cell))
+        .filter(|(i, cell)| cell.is_none() && !board.is_wall(i / Board::COLS, i % Board::COLS))
+        .map(|(i, _)| i)
+        .collect::<Vec<u8>>()
+}
+
+fn get_random_free_cell(free_cells: &[u8], rng: &mut ChaCha8Rng) -> Option<Position> {
+    let free_cell = free_cells[get_random(0, free_cells.len() - 1, rng)];
+
+    let min_row = free_cell / Board::COLS;
+    let min_col = free_cell % Board::COLS;
+
+    Position::new(min_row, min_col)
+}
+
+fn add_remaining_blocks(board: &mut Board, rng: &mut ChaCha8Rng) {
+    let goal_block = board.goal.block;
+
+    let mut blocks = BLOCK_WEIGHTS
+        .into_iter()
+        .filter(|(block, _)| *block != goal_block)
+        .flat_map(|(block, weight)| vec![block; weight])
+        .collect::<Vec<Block>>();
+
+    let mut free_cells = get_cells_free(board);
+
+    while free_cells.len() > usize::from(board.empty_cells) {
+        if let Some(position) = get_random_free_cell(&free_cells, rng) {
+            blocks.shuffle(rng);
+
+            let mut seen = vec![];
+
+            for block in &blocks {
+                if seen.contains(&block) {
+                    continue;
+                }
+
+                seen.push(block);
+
+                if let Some(positioned_block) =
+                    PositionedBlock::new(*block, position.row, position.col)
+                {
+                    if board.add_block(positioned_block).is_ok() {
+                        free_cells = get_cells_free(board);
+
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn add_goal_block(board: &mut Board, rng: &mut ChaCha8Rng) -> Result<(), BoardError> {
+    let block = board.goal.block;
+
+    let max_row = Board::ROWS
+        .checked_sub(block.rows() + 2)
+        .ok_or(BoardError::BlockPlacementInvalid)?;
+
+    let goal_blocks: Vec<PositionedBlock> = (0..=max_row)
+        .flat_map(|row| (0..=Board::COLS - block.cols()).map(move |col| (row, col)))
+        .filter_map(|(row, col)| PositionedBlock::new(block, row, col))
+        .filter(|goal_block| {
+            goal_block
+                .range
+                .iter()
+                .all(|(row, col)| !board.is_wall(*row, *col))
+        })
+        .collect();
+
+    let goal_block = goal_blocks
+        .choose(rng)
+        .cloned()
+        .ok_or(BoardError::BlockPlacementInvalid)?;
+
+    board.add_block(goal_block)
+}
+
+// Largest seed drawn at random, which is the largest integer that every JSON
+// client can represent exactly. Larger seeds may still be given.
+pub const MAX_RANDOM_SEED: u64 = (1 << 53) - 1;
+
+// Seed from which a random board can be recreated, of at most MAX_RANDOM_SEED
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen_range(0..=MAX_RANDOM_SEED)
+}
+
+// Randomly add block to the board in the building state, drawing from the given
+// seed so that the same board is added for the same seed, and record the seed
+// on the board. Seeds are expanded by ChaCha8, whose output, unlike that of
+// rand's StdRng, does not change between versions of rand, since seeds are
+// stored to recreate boards and derive the daily puzzles. Add the goal block
+// (2x2 by default) to a random valid position above the bottom two rows which
+// is not covered by a wall, or return the BlockPlacementInvalid error if there
+// is none. Then add remaining blocks at random until the board has no remaining
+// empty cells. Remaining block probabilities are given by BLOCK_WEIGHTS,
+// favouring 1x1 blocks and rarely choosing 3x1 or 1x3 blocks, excluding the
+// goal block. This is done to reduce the risk of the board being unsolvable.
+// Since 1x1 blocks are needed to fill the board's remaining cells, boards with
+// a 1x1 goal block cannot be randomized and the BlockInvalid error is returned.
+pub fn randomize(board: &mut Board, seed: u64) -> Result<(), BoardError> {
+    if board.goal.block == Block::ONE_BY_ONE {
+        return Err(BoardError::BlockInvalid);
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    add_goal_block(board, &mut rng)?;
+    add_remaining_blocks(board, &mut rng);
+
+    board.change_state(BoardState::ReadyToSolve)?;
+    board.seed = Some(seed);
+
+    Ok(())
+}
+
+// Make the given number of random moves, continuing while the board is still
+// solved, or until MAX_EXTRA_MOVES more moves are made. The same block is not
+// moved twice in a row unless it is the only block which can move. Returns
+// whether the board is no longer solved.
+fn make_random_moves(board: &mut Board, num_moves: usize, rng: &mut ChaCha8Rng) -> bool {
+    let mut last_block_idx = None;
+    let mut num_moves_made = 0;
+
+    while num_moves_made < num_moves
+        || (board.is_solved() && num_moves_made < num_moves + MAX_EXTRA_MOVES)
+    {
+        let next_moves: Vec<(usize, FlatMove)> = board
+            .get_next_moves()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(block_idx, moves)| moves.into_iter().map(move |move_| (block_idx, move_)))
+            .collect();
+
+        let other_moves: Vec<(usize, FlatMove)> = next_moves
+            .iter()
+            .filter(|(block_idx, _)| Some(*block_idx) != last_block_idx)
+            .cloned()
+            .collect();
+
+        let candidates = if other_moves.is_empty() {
+            &next_moves
+        } else {
+            &other_moves
+        };
+
+        let Some((block_idx, move_)) = candidates.choose(rng).cloned() else {
+            return false;
+        };
+
+        board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+        last_block_idx = Some(block_idx);
+        num_moves_made += 1;
+    }
+
+    !board.is_solved()
+}
+
+// Randomly add blocks to the board in the building state such that the board
+// is solvable by construction. The goal block is added at the goal position and
+// the remaining blocks are added as by randomize, giving a solved board, from
+// which random moves are made as by make_random_moves. Since every move can be
+// made in reverse, the board can be solved by undoing the moves, which are then
+// forgotten. Some layouts keep the goal block in place whatever moves are made,
+// in which case the remaining blocks are added again, up to
+// MAX_SHUFFLE_ATTEMPTS times. If the goal block cannot be placed at the goal
+// position or no attempt moves the goal block away, the BlockPlacementInvalid
+// error is returned, and as with randomize, boards with a 1x1 goal block
+// cannot be shuffled. The seed is used and recorded as by randomize.
+pub fn shuffle(board: &mut Board, num_moves: usize, seed: u64) -> Result<(), BoardError> {
+    if board.goal.block == Block::ONE_BY_ONE {
+        return Err(BoardError::BlockInvalid);
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let goal_block = PositionedBlock::new(board.goal.block, board.goal.row, board.goal.col)
+        .ok_or(BoardError::BlockPlacementInvalid)?;
+
+    let empty_board = board.clone();
+
+    for _ in 0..MAX_SHUFFLE_ATTEMPTS {
+        *board = empty_board.clone();
+
+        board.add_block(goal_block.clone())?;
+        add_remaining_blocks(board, &mut rng);
+
+        board.change_state(BoardState::ReadyToSolve)?;
+
+        if make_random_moves(board, num_moves, &mut rng) {
+            board.moves.clear();
+            board.seed = Some(seed);
+
+            return Ok(());
+        }
+    }
+
+    *board = empty_board;
+
+    Err(BoardError::BlockPlacementInvalid)
+}
+
+// Difficulty of a random board, given by the range of lengths its optimal
+// solution falls in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn optimal_moves(self) -> RangeInclusive<usize> {
+        match self {
+            Self::Easy => 1..=15,
+            Self::Medium => 16..=30,
+            Self::Hard => 31..=usize::MAX,
+        }
+    }
+}
+
+// Randomize the board with the given function, e.g. randomize or shuffle,
+// until its optimal solution has a length within the given range. Candidates
+// are drawn from consecutive seeds starting from the given seed, so that the
+// seed recorded on the board recreates it in a single attempt. Candidates which
+// are unsolvable, or which cannot be solved within the solver's budget, are
+// skipped. If no candidate matches within MAX_DIFFICULTY_ATTEMPTS attempts,
+// the board is left as it was and the DifficultyNotFound error is returned.
+pub fn randomize_within<F>(
+    board: &mut Board,
+    optimal_moves: &RangeInclusive<usize>,
+    seed: u64,
+    randomize: F,
+) -> Result<(), BoardError>
+where
+    F: Fn(&mut Board, u64) -> Result<(), BoardError>,
+{
+    let options = Options {
+        deterministic: true,
+        ..Options::default()
+    };
+
+    for attempt in 0..MAX_DIFFICULTY_ATTEMPTS {
+        let mut candidate = board.clone();
+
+        randomize(&mut candidate, seed.wrapping_add(u64::from(attempt)))?;
+
+        if let Ok(Some(moves)) = solver::solve(&candidate, &options) {
+            if optimal_moves.contains(&moves.len()) {
+                *board = candidate;
+
+                return Ok(());
+            }
+        }
+    }
+
+    Err(BoardError::DifficultyNotFound)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::board::Board;
+    use crate::solver;
+
+    #[test]
+    fn randomize_() {
+        let mut board = Board::default();
+        assert!(randomize(&mut board, random_seed()).is_ok());
+    }
+
+    #[test]
+    fn randomize_custom_goal() {
+        let mut board = Board::default();
+        board.goal.block = Block::ONE_BY_TWO;
+        assert!(randomize(&mut board, random_seed()).is_ok());
+        assert!(!board
+            .blocks
+            .iter()
+            .any(|positioned_block| positioned_block.block == Block::TWO_BY_TWO));
+
+        let mut board = Board::default();
+        board.goal.block = Block::ONE_BY_ONE;
+        assert_eq!(
+            randomize(&mut board, random_seed()),
+            Err(BoardError::BlockInvalid)
+        );
+
+        let mut board = Board::default();
+        board.goal.block = Block::Rectangle { rows: 4, cols: 1 };
+        assert_eq!(
+            randomize(&mut board, random_seed()),
+            Err(BoardError::BlockPlacementInvalid)
+        );
+    }
+
+    #[test]
+    fn shuffle_() {
+        let mut board = Board::default();
+        board.add_wall(0, 0).unwrap();
+
+        assert!(shuffle(&mut board, 30, random_seed()).is_ok());
+        assert_eq!(board.state, BoardState::ReadyToSolve);
+        assert!(board.moves.is_empty());
+        assert!(!board.is_solved());
+        assert_eq!(
+            solver::is_solvable(
+                &board,
+                solver::DEFAULT_NODE_BUDGET,
+                &solver::Cancellation::default()
+            ),
+            Some(true)
+        );
+
+        let mut board = Board::default();
+        board.goal.block = Block::ONE_BY_ONE;
+        assert_eq!(
+            shuffle(&mut board, 30, random_seed()),
+            Err(BoardError::BlockInvalid)
+        );
+    }
+
+    #[test]
+    fn randomize_walls() {
+        let mut board = Board::default();
+        board.add_wall(0, 0).unwrap();
+        board.add_wall(1, 1).unwrap();
+        assert!(randomize(&mut board, random_seed()).is_ok());
+        assert!(board.blocks.iter().all(|positioned_block| positioned_block
+            .range
+            .iter()
+            .all(|(row, col)| !board.is_wall(*row, *col))));
+    }
+
+    #[test]
+    fn same_seed_same_board() {
+        let mut board = Board::default();
+        let mut other_board = Board::default();
+
+        randomize(&mut board, 42).unwrap();
+        randomize(&mut other_board, 42).unwrap();
+
+        assert_eq!(board.seed, Some(42));
+        assert_eq!(board.blocks, other_board.blocks);
+
+        let mut board = Board::default();
+        let mut other_board = Board::default();
+
+        shuffle(&mut board, 30, 7).unwrap();
+        shuffle(&mut other_board, 30, 7).unwrap();
+
+        assert_eq!(board.seed, Some(7));
+        assert_eq!(board.blocks, other_board.blocks);
+    }
+
+    #[test]
+    fn randomize_within_() {
+        let shuffle_ = |board: &mut Board, seed| shuffle(board, 30, seed);
+        let optimal_moves = 3..=5;
+
+        let mut board = Board::default();
+        randomize_within(&mut board, &optimal_moves, 11, shuffle_).unwrap();
+
+        let moves = solver::solve(&board, &Options::default()).unwrap().unwrap();
+        assert!(optimal_moves.contains(&moves.len()));
+
+        let mut other_board = Board::default();
+        shuffle_(&mut other_board, board.seed.unwrap()).unwrap();
+        assert_eq!(board.blocks, other_board.blocks);
+
+        let mut board = Board::default();
+        assert_eq!(
+            randomize_within(&mut board, &(500..=usize::MAX), 11, shuffle_),
+            Err(BoardError::DifficultyNotFound)
+        );
+        assert!(board.blocks.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+// Multiplier of Fibonacci hashing, used to spread the board keys across the
+// shards, since the top bits of a board key only describe the bottom row
+const SPREAD: u64 = 0x9E37_79B9_7F4A_7C15;
+
+// Set of board keys shared between the threads of a parallel search, split
+// into independently locked shards so that threads inserting keys into
+// different shards do not contend for the same lock. The shard of a key is
+// given by the top bits of its spread halves.
+pub struct ShardedSet {
+    shards: Vec<Mutex<HashSet<u128>>>,
+    shift: u32,
+}
+
+impl ShardedSet {
+    // Create a set with at least the given number of shards, rounded up to a
+    // power of two
+    pub fn new(num_shards: usize) -> Self {
+        let num_shards = num_shards.max(1).next_power_of_two();
+
+        Self {
+            shards: (0..num_shards).map(|_| Mutex::default()).collect(),
+            shift: u64::BITS - num_shards.trailing_zeros(),
+        }
+    }
+
+    // Create a set with a few shards per thread of rayon's thread pool
+    pub fn for_current_threads() -> Self {
+        Self::new(4 * rayon::current_num_threads())
+    }
+
+    fn shard(&self, key: u128) -> &Mutex<HashSet<u128>> {
+        let halves =
+            u64::try_from(key >> 64).unwrap() ^ u64::try_from(key & u128::from(u64::MAX)).unwrap();
+
+        // A shift by the full width of the halves is an overflow, so a single
+        // shard is handled separately
+        let idx = halves
+            .wrapping_mul(SPREAD)
+            .checked_shr(self.shift)
+            .unwrap_or(0);
+
+        &self.shards[usize::try_from(idx).unwrap()]
+    }
+
+    // Insert the key, returning whether it was not already in the set
+    pub fn insert(&self, key: u128) -> bool {
+        self.shard(key).lock().unwrap().insert(key)
+    }
+
+    pub fn contains(&self, key: u128) -> bool {
+        self.shard(key).lock().unwrap().contains(&key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().len())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    #[test]
+    fn test_num_shards() {
+        assert_eq!(ShardedSet::new(0).shards.len(), 1);
+        assert_eq!(ShardedSet::new(1).shards.len(), 1);
+        assert_eq!(ShardedSet::new(5).shards.len(), 8);
+        assert_eq!(ShardedSet::new(64).shards.len(), 64);
+    }
+
+    #[test]
+    fn test_insert() {
+        for num_shards in [1, 2, 16] {
+            let set = ShardedSet::new(num_shards);
+
+            assert!(set.is_empty());
+            assert!(set.insert(42));
+            assert!(!set.insert(42));
+            assert!(set.contains(42));
+            assert!(!set.contains(43));
+            assert_eq!(set.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_parallel_insert() {
+        let set = ShardedSet::new(16);
+
+        let inserted = (0..10_000u128)
+            .into_par_iter()
+            .chain((0..10_000u128).into_par_iter())
+            .filter(|key| set.insert(*key))
+            .count();
+
+        assert_eq!(inserted, 10_000);
+        assert_eq!(set.len(), 10_000);
+        assert!(set
+            .shards
+            .iter()
+            .all(|shard| !shard.lock().unwrap().is_empty()));
+    }
+}
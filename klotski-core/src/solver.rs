@@ -0,0 +1,2883 @@
+use std::cmp::Ordering;
+use std::collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet};
+use std::sync::{
+    atomic::{AtomicBool, Ordering as AtomicOrdering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::errors::{board::Error as BoardError, solver::Error as SolverError};
+use crate::game::{
+    blocks::{Block, Positioned as PositionedBlock},
+    board::{Board, Goal, State as BoardState},
+    moves::{FlatBoardMove, FlatMove},
+    utils::Position,
+};
+use crate::{
+    analysis,
+    bitboard::{Bitboard, MAX_BLOCKS},
+    ordering::{self, MoveOrdering, Strategy as OrderingStrategy},
+    seen::ShardedSet,
+};
+
+pub const DEFAULT_NODE_BUDGET: usize = 5_000_000;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const MAX_REJOIN_DEPTH: usize = 4;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = SolveAlgorithm)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    #[serde(rename = "astar")]
+    AStar,
+    Bfs,
+    #[default]
+    Bidirectional,
+    Greedy,
+    #[serde(rename = "ida_star")]
+    IdaStar,
+}
+
+// Quantity minimized by the solver. With the Moves objective, every flattened
+// move counts once, while with the Steps objective, a move counts once for
+// each unit step it makes, e.g. an L-shaped move counts twice.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = SolveObjective)]
+#[serde(rename_all = "snake_case")]
+pub enum Objective {
+    #[default]
+    Moves,
+    Steps,
+}
+
+impl Objective {
+    fn cost(self, move_: &FlatMove) -> usize {
+        match self {
+            Self::Moves => 1,
+            Self::Steps => move_.steps(),
+        }
+    }
+
+    fn heuristic(self, board: &Board) -> usize {
+        match self {
+            Self::Moves => winning_block_heuristic(board),
+            Self::Steps => goal_distance(board),
+        }
+    }
+}
+
+// Cancellation of a running search, triggered either once the deadline has
+// passed or once cancel is called on any of its clones
+#[derive(Debug, Default, Clone)]
+pub struct Cancellation {
+    deadline: Option<Instant>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Cancellation {
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            deadline: Instant::now().checked_add(timeout),
+            cancelled: Arc::default(),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Relaxed) || self.is_timed_out()
+    }
+
+    fn is_timed_out(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    fn check(&self) -> Result<(), SolverError> {
+        if self.cancelled.load(AtomicOrdering::Relaxed) {
+            return Err(SolverError::Cancelled);
+        }
+
+        if self.is_timed_out() {
+            return Err(SolverError::TimedOut);
+        }
+
+        Ok(())
+    }
+}
+
+// Guard which cancels the cancellation when dropped, e.g. when the future of a
+// request handler is dropped because the client disconnected
+pub struct CancelOnDrop(pub Cancellation);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[schema(as = SolveProgress)]
+pub struct Progress {
+    pub depth: usize,
+    pub nodes_expanded: usize,
+    pub queue_size: usize,
+}
+
+pub type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+
+// Result of solving a board along with statistics about the search. If the
+// search was truncated at the maximum depth, the moves lead to the board
+// closest to the goal rather than to a solved board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub moves: Option<Vec<FlatBoardMove>>,
+    pub nodes_expanded: usize,
+    pub truncated: bool,
+}
+
+#[derive(Clone)]
+pub struct Options {
+    pub algorithm: Algorithm,
+    pub objective: Objective,
+    pub node_budget: usize,
+    pub cancellation: Cancellation,
+    pub progress: Option<ProgressCallback>,
+    pub count_solutions: bool,
+    pub deterministic: bool,
+    pub move_ordering: Arc<dyn MoveOrdering>,
+    pub max_depth: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            algorithm: Algorithm::default(),
+            objective: Objective::default(),
+            node_budget: DEFAULT_NODE_BUDGET,
+            cancellation: Cancellation::default(),
+            progress: None,
+            count_solutions: false,
+            deterministic: deterministic_by_default(),
+            move_ordering: OrderingStrategy::default().ordering(),
+            max_depth: None,
+        }
+    }
+}
+
+impl Options {
+    // Whether the solutions found with these options are guaranteed to be
+    // optimal, which is the case for every algorithm except Greedy, unless a
+    // maximum depth is set, in which case AStar is used instead
+    pub fn is_optimal(&self) -> bool {
+        self.algorithm != Algorithm::Greedy || self.max_depth.is_some()
+    }
+}
+
+// Whether the solver runs in deterministic mode unless the request says
+// otherwise, set using the SOLVER_DETERMINISTIC environment variable
+pub fn deterministic_by_default() -> bool {
+    std::env::var("SOLVER_DETERMINISTIC").is_ok_and(|value| value == "true")
+}
+
+impl Options {
+    fn report(&self, depth: usize, nodes_expanded: usize, queue_size: usize) {
+        if let Some(progress) = &self.progress {
+            progress(Progress {
+                depth,
+                nodes_expanded,
+                queue_size,
+            });
+        }
+    }
+}
+
+// Result of a search, i.e. the solved board if one was reached, or the board
+// closest to the goal if the search was truncated at the maximum depth, along
+// with the number of nodes expanded
+type Search = (Option<Board>, usize);
+
+// Lightweight board used by the breadth-first search, so that the levels only
+// store small fixed-size structures rather than full boards. The position of
+// each block is packed into the index of its top-left cell, with the blocks in
+// the same order as the blocks of the root board, along with the index of the
+// parent node and the move which reached the node from its parent.
+#[derive(Debug, Clone)]
+struct BfsNode {
+    cells: [u8; MAX_BLOCKS],
+    parent: Option<(usize, FlatBoardMove)>,
+    solved: bool,
+}
+
+// Generate the children of the node which have not been seen yet, using the
+// bitboard representation of the node for move generation and keying
+fn expand_node(root: &Bitboard, node: &BfsNode, idx: usize, seen: &ShardedSet) -> Vec<BfsNode> {
+    let mut children = vec![];
+
+    let bitboard = root.with_cells(&node.cells);
+
+    for (block_idx, moves) in bitboard.get_next_moves().into_iter().enumerate() {
+        for move_ in moves {
+            let mut child = bitboard;
+            child.move_block(block_idx, &move_);
+
+            if seen.insert(child.canonical_key()) {
+                children.push(BfsNode {
+                    cells: child.cells(),
+                    parent: Some((idx, FlatBoardMove::new(block_idx, &move_))),
+                    solved: child.is_solved(),
+                });
+            }
+        }
+    }
+
+    children
+}
+
+// Follow the parents of the node back to the root and make the moves which
+// reached the node on the root board
+fn reconstruct(mut root: Board, nodes: &[BfsNode], idx: usize) -> Board {
+    let mut moves = vec![];
+    let mut current = &nodes[idx];
+
+    while let Some((parent, last_move)) = &current.parent {
+        moves.push(last_move.clone());
+        current = &nodes[*parent];
+    }
+
+    for move_ in moves.iter().rev() {
+        root.move_block_unchecked(move_.block_idx, move_.row_diff, move_.col_diff);
+    }
+
+    root
+}
+
+fn parallel_bfs(root: Board, options: &Options) -> Result<Search, SolverError> {
+    let cancellation = &options.cancellation;
+
+    let root_bitboard = Bitboard::from(&root);
+
+    let seen = ShardedSet::for_current_threads();
+    seen.insert(root_bitboard.canonical_key());
+
+    let mut nodes = vec![BfsNode {
+        cells: root_bitboard.cells(),
+        parent: None,
+        solved: root.state == BoardState::Solved,
+    }];
+    let mut level = 0..nodes.len();
+    let mut depth = 0;
+    let mut nodes_expanded = 0;
+
+    while !level.is_empty() {
+        if let Some(idx) = nodes[level.clone()].iter().position(|node| node.solved) {
+            return Ok((
+                Some(reconstruct(root, &nodes, level.start + idx)),
+                nodes_expanded,
+            ));
+        }
+
+        nodes_expanded += level.len();
+
+        let expand = |(idx, node): (usize, &BfsNode)| {
+            if cancellation.is_cancelled() {
+                return vec![];
+            }
+
+            expand_node(&root_bitboard, node, level.start + idx, &seen)
+        };
+
+        let children: Vec<BfsNode> = if options.deterministic {
+            nodes[level.clone()]
+                .iter()
+                .enumerate()
+                .flat_map(expand)
+                .collect()
+        } else {
+            nodes[level.clone()]
+                .par_iter()
+                .enumerate()
+                .flat_map_iter(expand)
+                .collect()
+        };
+
+        cancellation.check()?;
+
+        let start = nodes.len();
+        nodes.extend(children);
+        level = start..nodes.len();
+
+        depth += 1;
+
+        options.report(depth, nodes_expanded, level.len());
+    }
+
+    Ok((None, nodes_expanded))
+}
+
+// Recursively place the remaining blocks on the goal board in every possible
+// arrangement, visiting cells in row-major order. Each cell is either covered
+// by a block whose first covered cell in row-major order is that cell or
+// skipped, where at most the goal board's number of empty cells may be skipped
+// in total.
+fn add_goal_blocks(
+    goal: &mut Board,
+    cell: u8,
+    empty_cells_skipped: u8,
+    remaining: &mut Vec<(Block, usize)>,
+    goals: &mut Vec<Board>,
+) {
+    if remaining.iter().all(|(_, count)| *count == 0) {
+        goals.push(goal.clone());
+        return;
+    }
+
+    if cell == Board::ROWS * Board::COLS {
+        return;
+    }
+
+    if goal.grid[usize::from(cell)].is_some()
+        || goal.is_wall(cell / Board::COLS, cell % Board::COLS)
+    {
+        add_goal_blocks(goal, cell + 1, empty_cells_skipped, remaining, goals);
+        return;
+    }
+
+    if empty_cells_skipped < goal.empty_cells {
+        add_goal_blocks(goal, cell + 1, empty_cells_skipped + 1, remaining, goals);
+    }
+
+    for i in 0..remaining.len() {
+        let (block, count) = remaining[i];
+
+        if count == 0 {
+            continue;
+        }
+
+        // The first covered cell of an L-shaped block missing its top-left
+        // corner is to the right of the top-left corner of its bounding box
+        let (row_offset, col_offset) = block.cells()[0];
+
+        if let Some(positioned_block) = (cell / Board::COLS)
+            .checked_sub(row_offset)
+            .zip((cell % Board::COLS).checked_sub(col_offset))
+            .and_then(|(row, col)| PositionedBlock::new(block, row, col))
+        {
+            if goal.add_block(positioned_block).is_ok() {
+                remaining[i].1 -= 1;
+
+                add_goal_blocks(goal, cell + 1, empty_cells_skipped, remaining, goals);
+
+                remaining[i].1 += 1;
+
+                goal.remove_block(goal.blocks.len() - 1).unwrap();
+            }
+        }
+    }
+}
+
+// Build every board containing the given blocks and walls in which the goal
+// block is in the goal position, where the cells left empty by the blocks and
+// walls are the goal boards' empty cells
+pub fn get_goal_boards(blocks: &[Block], board_goal: Goal, walls: &[Position]) -> Vec<Board> {
+    let mut remaining: Vec<(Block, usize)> = vec![];
+
+    for block in blocks {
+        match remaining
+            .iter_mut()
+            .find(|(remaining_block, _)| remaining_block == block)
+        {
+            Some((_, count)) => *count += 1,
+            None => remaining.push((*block, 1)),
+        }
+    }
+
+    let Some((_, winning_count)) = remaining
+        .iter_mut()
+        .find(|(block, _)| *block == board_goal.block)
+    else {
+        return vec![];
+    };
+
+    *winning_count -= 1;
+
+    let num_cells = blocks
+        .iter()
+        .map(|block| usize::from(block.size()))
+        .sum::<usize>();
+
+    let Some(empty_cells) = usize::from(Board::ROWS * Board::COLS)
+        .checked_sub(num_cells + walls.len())
+        .and_then(|empty_cells| u8::try_from(empty_cells).ok())
+        .filter(|empty_cells| {
+            (Board::MIN_EMPTY_CELLS..=Board::MAX_EMPTY_CELLS).contains(empty_cells)
+        })
+    else {
+        return vec![];
+    };
+
+    let mut goal = Board {
+        goal: board_goal,
+        empty_cells,
+        ..Board::default()
+    };
+
+    for wall in walls {
+        if goal.add_wall(wall.row, wall.col).is_err() {
+            return vec![];
+        }
+    }
+
+    let Some(winning_block) =
+        PositionedBlock::new(board_goal.block, board_goal.row, board_goal.col)
+    else {
+        return vec![];
+    };
+
+    if goal.add_block(winning_block).is_err() {
+        return vec![];
+    }
+
+    let mut goals = vec![];
+
+    add_goal_blocks(&mut goal, 0, 0, &mut remaining, &mut goals);
+
+    for goal in &mut goals {
+        let _is_solving = goal.change_state(BoardState::Solving).is_ok();
+        let _is_solved = goal.change_state(BoardState::Solved).is_ok();
+    }
+
+    goals
+}
+
+// Run a breadth-first search backwards from every goal board of the given
+// blocks at once, so that the level at which a board is first reached is the
+// length of its optimal solution. Boards which are mirror images of each other
+// are only visited once. The on_level callback is called with the distance and
+// boards of each level, starting with the goal boards, and the distance and
+// boards of the last level are returned. If the blocks do not contain exactly
+// one goal block or do not fill the board except for between MIN_EMPTY_CELLS
+// and MAX_EMPTY_CELLS cells, the BoardStateInvalid error is returned.
+pub fn retrograde_bfs<F>(
+    blocks: &[Block],
+    goal: Goal,
+    cancellation: &Cancellation,
+    mut on_level: F,
+) -> Result<(usize, Vec<Board>), SolverError>
+where
+    F: FnMut(usize, &[Board]),
+{
+    let num_goal_blocks = blocks.iter().filter(|block| **block == goal.block).count();
+    let num_cells = blocks.iter().map(|block| block.size()).sum::<u8>();
+
+    let num_empty_cells = (Board::ROWS * Board::COLS).checked_sub(num_cells);
+
+    if num_goal_blocks != 1
+        || !num_empty_cells.is_some_and(|num_empty_cells| {
+            (Board::MIN_EMPTY_CELLS..=Board::MAX_EMPTY_CELLS).contains(&num_empty_cells)
+        })
+    {
+        return Err(BoardError::BoardStateInvalid.into());
+    }
+
+    let mut level = get_goal_boards(blocks, goal, &[]);
+
+    if level.is_empty() {
+        return Err(BoardError::BoardStateInvalid.into());
+    }
+
+    let mut seen: HashSet<u64> = level.iter().map(Board::canonical_hash).collect();
+
+    let mut distance = 0;
+
+    loop {
+        cancellation.check()?;
+
+        on_level(distance, &level);
+
+        let mut next_level = vec![];
+
+        for board in &mut level {
+            let next_moves = board.get_next_moves();
+
+            for (block_idx, moves) in next_moves.into_iter().enumerate() {
+                for move_ in moves {
+                    board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                    if seen.insert(board.canonical_hash()) {
+                        let mut next_board = board.clone();
+                        next_board.moves.clear();
+
+                        next_level.push(next_board);
+                    }
+
+                    board.undo_move_unchecked();
+                }
+            }
+        }
+
+        if next_level.is_empty() {
+            return Ok((distance, level));
+        }
+
+        level = next_level;
+        distance += 1;
+    }
+}
+
+// Boards from either direction of the bidirectional search with equal grids
+type Meeting = (Board, Board);
+
+// Expand every board in the frontier by one move. Children which have not been
+// seen from this direction are added to the seen map and the next frontier. If
+// a child has already been seen from the other direction, the two searches
+// meet, and the pair of boards forming the shortest path through the current
+// level is returned once the level has been fully expanded.
+fn expand_level(
+    frontier: Vec<Board>,
+    seen: &mut HashMap<u128, Board>,
+    other_seen: &HashMap<u128, Board>,
+    cancellation: &Cancellation,
+) -> Result<(Vec<Board>, Option<Meeting>), SolverError> {
+    let mut next_frontier = vec![];
+    let mut meeting: Option<Meeting> = None;
+
+    for mut board in frontier {
+        cancellation.check()?;
+
+        let next_moves = board.get_next_moves();
+
+        for (block_idx, moves) in next_moves.into_iter().enumerate() {
+            for move_ in moves {
+                board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                let key = board.key();
+
+                if let Entry::Vacant(entry) = seen.entry(key) {
+                    entry.insert(board.clone());
+
+                    if let Some(other_board) = other_seen.get(&key) {
+                        let is_shorter = match &meeting {
+                            Some((this, other)) => {
+                                board.moves.len() + other_board.moves.len()
+                                    < this.moves.len() + other.moves.len()
+                            }
+                            None => true,
+                        };
+
+                        if is_shorter {
+                            meeting = Some((board.clone(), other_board.clone()));
+                        }
+                    }
+
+                    next_frontier.push(board.clone());
+                }
+
+                board.undo_move_unchecked();
+            }
+        }
+    }
+
+    Ok((next_frontier, meeting))
+}
+
+// Append the moves of the backward search to the forward board in reverse
+// order. Since boards with equal grids can order their blocks differently, each
+// backward move is mapped to the forward board's block of the same shape at the
+// same position. The position alone is not enough, since an L-shape missing its
+// top-left corner shares its min position with the block in that corner.
+fn join_paths(mut forward: Board, mut backward: Board) -> Board {
+    while let Some(last_move) = backward.moves.last().cloned() {
+        let moved_block = &backward.blocks[last_move.block_idx];
+
+        let block_idx = forward
+            .blocks
+            .iter()
+            .position(|positioned_block| {
+                positioned_block.block == moved_block.block
+                    && positioned_block.min_position == moved_block.min_position
+            })
+            .unwrap();
+
+        let opposite_move = last_move.opposite();
+
+        forward.move_block_unchecked(block_idx, opposite_move.row_diff, opposite_move.col_diff);
+
+        backward.undo_move_unchecked();
+    }
+
+    forward
+}
+
+fn bidirectional_bfs(root: Board, options: &Options) -> Result<Search, SolverError> {
+    if root.state == BoardState::Solved {
+        return Ok((Some(root), 0));
+    }
+
+    let blocks: Vec<Block> = root
+        .blocks
+        .iter()
+        .map(|positioned_block| positioned_block.block)
+        .collect();
+
+    let goals = get_goal_boards(&blocks, root.goal, &root.walls);
+
+    // Exact keys are used rather than canonical keys, since the two halves
+    // of the path can only be joined if they meet at the same board
+    let mut forward_seen = HashMap::from([(root.key(), root.clone())]);
+    let mut forward_frontier = vec![root];
+
+    let mut backward_seen: HashMap<u128, Board> = goals
+        .iter()
+        .map(|goal| (goal.key(), goal.clone()))
+        .collect();
+    let mut backward_frontier = goals;
+
+    let mut depth = 0;
+    let mut nodes_expanded = 0;
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        depth += 1;
+        nodes_expanded += forward_frontier.len().min(backward_frontier.len());
+
+        if forward_frontier.len() <= backward_frontier.len() {
+            let (next_frontier, meeting) = expand_level(
+                forward_frontier,
+                &mut forward_seen,
+                &backward_seen,
+                &options.cancellation,
+            )?;
+
+            if let Some((forward, backward)) = meeting {
+                return Ok((Some(join_paths(forward, backward)), nodes_expanded));
+            }
+
+            forward_frontier = next_frontier;
+        } else {
+            let (next_frontier, meeting) = expand_level(
+                backward_frontier,
+                &mut backward_seen,
+                &forward_seen,
+                &options.cancellation,
+            )?;
+
+            if let Some((backward, forward)) = meeting {
+                return Ok((Some(join_paths(forward, backward)), nodes_expanded));
+            }
+
+            backward_frontier = next_frontier;
+        }
+
+        options.report(
+            depth,
+            nodes_expanded,
+            forward_frontier.len() + backward_frontier.len(),
+        );
+    }
+
+    Ok((None, nodes_expanded))
+}
+
+// Manhattan distance of the goal block to the goal position, which is a lower
+// bound on the number of unit steps needed to solve the board. Boards with
+// targets have no single goal position, so no steps are counted.
+fn goal_distance(board: &Board) -> usize {
+    if !board.targets.is_empty() {
+        return 0;
+    }
+
+    board
+        .blocks
+        .iter()
+        .find(|positioned_block| positioned_block.block == board.goal.block)
+        .map_or(0, |positioned_block| {
+            usize::from(
+                positioned_block.min_position.row.abs_diff(board.goal.row)
+                    + positioned_block.min_position.col.abs_diff(board.goal.col),
+            )
+        })
+}
+
+// Lower bound on the number of moves needed to solve the board, computed as the
+// Manhattan distance of the goal block to the goal position. Since a
+// single move can shift a block by up to the board's number of empty cells, the
+// distance is divided by that amount (rounding up) to keep the heuristic
+// admissible.
+fn winning_block_heuristic(board: &Board) -> usize {
+    goal_distance(board).div_ceil(usize::from(board.empty_cells))
+}
+
+// Cheap lower bound on the length of the board's optimal solution, which does
+// not search the board's moves
+pub fn lower_bound(board: &Board) -> usize {
+    winning_block_heuristic(board)
+}
+
+struct Node {
+    priority: usize,
+    // rank of the move which led to the board, used to break ties between
+    // boards with the same priority
+    rank: usize,
+    cost: usize,
+    board: Board,
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.rank == other.rank
+    }
+}
+
+impl Eq for Node {}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Reversed so that the BinaryHeap pops the node with the lowest priority first,
+// and the node with the lowest rank among those with the same priority
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| other.rank.cmp(&self.rank))
+    }
+}
+
+fn solve_astar(root: Board, options: &Options) -> Result<Search, SolverError> {
+    let objective = options.objective;
+
+    let mut best_costs = HashMap::from([(root.canonical_key(), 0)]);
+
+    let mut depth = 0;
+    let mut nodes_expanded = 0;
+
+    // Board closest to the goal among those expanded, along with whether any
+    // board was left unexpanded because it was at the maximum depth
+    let mut closest: Option<(usize, Board)> = None;
+    let mut truncated = false;
+
+    let mut queue = BinaryHeap::from([Node {
+        priority: objective.heuristic(&root),
+        rank: 0,
+        cost: 0,
+        board: root,
+    }]);
+
+    while let Some(Node {
+        priority,
+        cost,
+        mut board,
+        ..
+    }) = queue.pop()
+    {
+        options.cancellation.check()?;
+
+        if priority > depth {
+            depth = priority;
+
+            options.report(depth, nodes_expanded, queue.len());
+        }
+
+        if board.state == BoardState::Solved {
+            return Ok((Some(board), nodes_expanded));
+        }
+
+        if best_costs
+            .get(&board.canonical_key())
+            .is_some_and(|best_cost| *best_cost < cost)
+        {
+            continue;
+        }
+
+        if let Some(max_depth) = options.max_depth {
+            let heuristic = objective.heuristic(&board);
+
+            if !closest
+                .as_ref()
+                .is_some_and(|(closest_heuristic, closest_board)| {
+                    (*closest_heuristic, closest_board.moves.len())
+                        <= (heuristic, board.moves.len())
+                })
+            {
+                closest = Some((heuristic, board.clone()));
+            }
+
+            if board.moves.len() >= max_depth {
+                truncated = true;
+                continue;
+            }
+        }
+
+        nodes_expanded += 1;
+
+        for (rank, block_idx, move_) in ordering::ordered_moves(&*options.move_ordering, &mut board)
+        {
+            let next_cost = cost + objective.cost(&move_);
+
+            board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+            let best_cost = best_costs
+                .entry(board.canonical_key())
+                .or_insert(usize::MAX);
+
+            if next_cost < *best_cost {
+                *best_cost = next_cost;
+
+                queue.push(Node {
+                    priority: next_cost + objective.heuristic(&board),
+                    rank,
+                    cost: next_cost,
+                    board: board.clone(),
+                });
+            }
+
+            board.undo_move_unchecked();
+        }
+    }
+
+    if truncated {
+        return Ok((closest.map(|(_, board)| board), nodes_expanded));
+    }
+
+    Ok((None, nodes_expanded))
+}
+
+// Greedy best-first search, which always expands the board whose goal block
+// is closest to the goal position, ignoring the number of moves made so far.
+// Each board is expanded at most once, so a solution is found quickly, but it
+// is not necessarily optimal.
+fn solve_greedy(root: Board, options: &Options) -> Result<Search, SolverError> {
+    let mut seen = HashSet::from([root.canonical_key()]);
+    let mut nodes_expanded = 0;
+
+    let mut queue = BinaryHeap::from([Node {
+        priority: goal_distance(&root),
+        rank: 0,
+        cost: 0,
+        board: root,
+    }]);
+
+    while let Some(Node {
+        cost, mut board, ..
+    }) = queue.pop()
+    {
+        options.cancellation.check()?;
+
+        if board.state == BoardState::Solved {
+            return Ok((Some(board), nodes_expanded));
+        }
+
+        nodes_expanded += 1;
+
+        for (rank, block_idx, move_) in ordering::ordered_moves(&*options.move_ordering, &mut board)
+        {
+            board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+            if seen.insert(board.canonical_key()) {
+                queue.push(Node {
+                    priority: goal_distance(&board),
+                    rank,
+                    cost: cost + 1,
+                    board: board.clone(),
+                });
+            }
+
+            board.undo_move_unchecked();
+        }
+    }
+
+    Ok((None, nodes_expanded))
+}
+
+struct IdaStar<'a> {
+    threshold: usize,
+    next_threshold: usize,
+    nodes_expanded: usize,
+    visited: HashMap<u128, usize>,
+    options: &'a Options,
+}
+
+impl IdaStar<'_> {
+    // Depth-first search bounded by the current threshold on the number of
+    // moves made plus the winning block heuristic. Boards which were already
+    // visited in the current iteration with no more moves are pruned. Returns
+    // true when the board has been moved into a solved state.
+    fn search(&mut self, board: &mut Board) -> Result<bool, SolverError> {
+        let cost = board.moves.len();
+        let estimate = cost + winning_block_heuristic(board);
+
+        if estimate > self.threshold {
+            self.next_threshold = self.next_threshold.min(estimate);
+            return Ok(false);
+        }
+
+        if board.state == BoardState::Solved {
+            return Ok(true);
+        }
+
+        if self.nodes_expanded >= self.options.node_budget {
+            return Err(SolverError::BudgetExceeded);
+        }
+
+        self.options.cancellation.check()?;
+
+        self.nodes_expanded += 1;
+
+        let next_moves = ordering::ordered_moves(&*self.options.move_ordering, board);
+
+        for (_, block_idx, move_) in next_moves {
+            board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+            let key = board.canonical_key();
+
+            let is_cheaper = match self.visited.get(&key) {
+                Some(visited_cost) => *visited_cost > cost + 1,
+                None => true,
+            };
+
+            if is_cheaper {
+                self.visited.insert(key, cost + 1);
+
+                if self.search(board)? {
+                    return Ok(true);
+                }
+            }
+
+            board.undo_move_unchecked();
+        }
+
+        Ok(false)
+    }
+}
+
+// Iterative-deepening A* search, which only keeps the current path and a table
+// of the boards visited in the current iteration in memory. The threshold
+// starts at the heuristic estimate of the root and is raised to the smallest
+// estimate which exceeded it after each iteration. If no estimate exceeded the
+// threshold, every reachable board was visited and the board is unsolvable.
+// The total number of nodes expanded is limited by the node budget.
+fn solve_ida_star(mut root: Board, options: &Options) -> Result<Search, SolverError> {
+    let mut ida_star = IdaStar {
+        threshold: winning_block_heuristic(&root),
+        next_threshold: usize::MAX,
+        nodes_expanded: 0,
+        visited: HashMap::new(),
+        options,
+    };
+
+    loop {
+        ida_star.next_threshold = usize::MAX;
+        ida_star.visited = HashMap::from([(root.canonical_key(), 0)]);
+
+        if ida_star.search(&mut root)? {
+            return Ok((Some(root), ida_star.nodes_expanded));
+        }
+
+        if ida_star.next_threshold == usize::MAX {
+            return Ok((None, ida_star.nodes_expanded));
+        }
+
+        ida_star.threshold = ida_star.next_threshold;
+
+        options.report(
+            ida_star.threshold,
+            ida_star.nodes_expanded,
+            ida_star.visited.len(),
+        );
+    }
+}
+
+// Check whether the board can be solved using a depth-first search which stops
+// as soon as a solved board is reached. Unlike solve, the moves which reached
+// each board are not kept, so the check is cheaper than finding a solution. A
+// board which is not ready to solve is not solvable. If the search expands
+// more boards than the node budget allows or the cancellation is triggered
+// before the answer is known, None is returned.
+pub fn is_solvable(board: &Board, node_budget: usize, cancellation: &Cancellation) -> Option<bool> {
+    let mut root = board.clone();
+    root.moves.clear();
+
+    if root.state == BoardState::Solved {
+        return Some(true);
+    }
+
+    if root.state == BoardState::Building && root.change_state(BoardState::ReadyToSolve).is_err() {
+        return Some(false);
+    }
+
+    if root.change_state(BoardState::Solving).is_err() {
+        return Some(false);
+    }
+
+    if root.change_state(BoardState::Solved).is_ok() {
+        return Some(true);
+    }
+
+    if analysis::is_unsolvable(&root) {
+        return Some(false);
+    }
+
+    let mut seen = HashSet::from([root.canonical_key()]);
+
+    let mut stack = vec![root];
+
+    let mut nodes_expanded = 0;
+
+    while let Some(mut board) = stack.pop() {
+        if nodes_expanded >= node_budget || cancellation.is_cancelled() {
+            return None;
+        }
+
+        nodes_expanded += 1;
+
+        let next_moves = board.get_next_moves();
+
+        for (block_idx, moves) in next_moves.into_iter().enumerate() {
+            for move_ in moves {
+                board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                if board.state == BoardState::Solved {
+                    return Some(true);
+                }
+
+                if seen.insert(board.canonical_key()) {
+                    let mut next_board = board.clone();
+                    next_board.moves.clear();
+
+                    stack.push(next_board);
+                }
+
+                board.undo_move_unchecked();
+            }
+        }
+    }
+
+    Some(false)
+}
+
+// Count the distinct optimal solutions for the board using a breadth-first
+// search which tracks the number of shortest paths to each board in the
+// current level. The paths to a board are the sum of the paths to each of its
+// parents in the previous level. The search stops at the first level which
+// contains a solved board, returning the sum of the paths to each solved board
+// in that level, or 0 if the board is unsolvable. The count saturates at
+// u64::MAX.
+pub fn count_solutions(board: &Board, options: &Options) -> Result<u64, SolverError> {
+    let mut root = board.clone();
+    root.moves.clear();
+
+    root.change_state(BoardState::Solving)?;
+
+    if root.change_state(BoardState::Solved).is_ok() {
+        return Ok(1);
+    }
+
+    let mut seen = HashSet::from([root.key()]);
+
+    let mut level = vec![(root, 1_u64)];
+
+    while !level.is_empty() {
+        options.cancellation.check()?;
+
+        let mut next_level: HashMap<u128, (Board, u64)> = HashMap::new();
+
+        for (mut board, paths) in level {
+            let next_moves = board.get_next_moves();
+
+            for (block_idx, moves) in next_moves.into_iter().enumerate() {
+                for move_ in moves {
+                    board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                    let key = board.key();
+
+                    if !seen.contains(&key) {
+                        match next_level.entry(key) {
+                            Entry::Occupied(mut entry) => {
+                                entry.get_mut().1 = entry.get().1.saturating_add(paths);
+                            }
+                            Entry::Vacant(entry) => {
+                                entry.insert((board.clone(), paths));
+                            }
+                        }
+                    }
+
+                    board.undo_move_unchecked();
+                }
+            }
+        }
+
+        let solutions = next_level
+            .values()
+            .filter(|(board, _)| board.state == BoardState::Solved)
+            .fold(0_u64, |acc, (_, paths)| acc.saturating_add(*paths));
+
+        if solutions > 0 {
+            return Ok(solutions);
+        }
+
+        seen.extend(next_level.keys());
+
+        level = next_level.into_values().collect();
+    }
+
+    Ok(0)
+}
+
+// Find an optimal solution for the board and return an optional list of moves
+// depending on whether the board is solvable. The board is solved from its
+// current position, so for a board part way through being solved, only the
+// remaining moves are returned and the moves already made are left out.
+// Boards which can be proven to be unsolvable without searching, e.g. because
+// the goal block can never move, are returned as unsolvable straight away.
+//
+// With the Bfs algorithm, the solution is found using a parallel breadth-first
+// search algorithm. The root of the breadth-first search is the board passed as
+// an argument. Rather than full boards, each level stores lightweight nodes
+// holding the position of each block, the node's parent and the move which
+// reached it. The nodes of each level are expanded in parallel using rayon's
+// thread pool, unless the deterministic option is set, in which case they are
+// expanded in order on the current thread. Since the parent which claims a
+// child first depends on the scheduling of the threads, only the deterministic
+// mode always returns the same solution. The other algorithms are always
+// deterministic. The algorithm rebuilds the board of each node and generates
+// its children using the board's get_next_moves method. For each of these
+// moves, the move is applied to the board, and a node for the resulting board
+// is added to the next level if the board has not been seen. The move is then
+// undone. No board is pruned as dead while searching: since every move can be
+// undone, every board reached can be taken back to the root, so a board reached
+// is unsolvable only if the root is, which is checked once before searching by
+// analysis::is_unsolvable. The algorithm continues until a level is empty. Once
+// a solved node is found, the path to it is rebuilt by following the parents of
+// the node back to the root. The seen set contains the canonical keys of each
+// board encountered, so that a board is skipped if it or its horizontal mirror
+// image has already been seen. Keys are exact, so a board is never skipped for
+// sharing a hash with another. The set is sharded by key, with each shard
+// locked separately, so that the threads rarely wait on each other to insert
+// keys. The AStar and IdaStar algorithms key the boards they have visited in
+// the same way.
+//
+// With the Bidirectional algorithm, a second breadth-first search is run
+// backwards from every goal configuration of the board's blocks. The smaller of
+// the two frontiers is expanded one level at a time until the searches meet,
+// at which point the two halves of the path are joined.
+//
+// With the AStar algorithm, boards are expanded in order of the number of moves
+// made so far plus the winning block heuristic, using a priority queue.
+//
+// With the IdaStar algorithm, an iterative-deepening A* search is used. If the
+// search expands more nodes than the node budget allows, the BudgetExceeded
+// error is returned.
+//
+// With the Greedy algorithm, a greedy best-first search is used, which finds a
+// solution quickly but does not guarantee that it is optimal, whichever
+// objective is passed in the options. No progress is reported.
+//
+// With the Steps objective, the breadth-first searches no longer find optimal
+// solutions, since moves no longer have equal costs, so the AStar algorithm is
+// used whichever algorithm is passed in the options. The cost of each board is
+// the number of unit steps made so far, and the winning block heuristic is
+// replaced by the goal block's Manhattan distance to the goal position.
+//
+// If a maximum depth is set in the options, the AStar algorithm is also used
+// whichever algorithm is passed in the options, and boards reached after the
+// maximum number of moves are not expanded. If no solution is found within the
+// maximum depth, the DepthExceeded error is returned.
+//
+// Every algorithm periodically checks the cancellation passed in the options
+// and returns the TimedOut error once its deadline has passed, or the
+// Cancelled error once it has been cancelled. If a progress
+// callback is passed in the options, it is called whenever the search reaches
+// a new depth, i.e. after each level of the breadth-first searches, when the
+// lowest priority in the A* queue increases, and after each IDA* iteration.
+pub fn solve(board: &Board, options: &Options) -> Result<Option<Vec<FlatBoardMove>>, SolverError> {
+    let outcome = solve_with_stats(board, options)?;
+
+    if outcome.truncated {
+        return Err(SolverError::DepthExceeded);
+    }
+
+    Ok(outcome.moves)
+}
+
+// Same as solve, but also returns the number of nodes expanded by the search,
+// e.g. to compare the algorithms on the same board. Boards rejected as
+// unsolvable before searching expand no nodes. If the maximum depth is
+// exceeded, the moves to the board closest to the goal are returned, flagged
+// as truncated, rather than the DepthExceeded error.
+pub fn solve_with_stats(board: &Board, options: &Options) -> Result<Outcome, SolverError> {
+    let mut start_board = board.clone();
+    start_board.moves.clear();
+
+    start_board.change_state(BoardState::Solving)?;
+    let _board_is_already_solved = start_board.change_state(BoardState::Solved).is_ok();
+
+    if analysis::is_unsolvable(&start_board) {
+        return Ok(Outcome {
+            moves: None,
+            nodes_expanded: 0,
+            truncated: false,
+        });
+    }
+
+    let algorithm = match (options.algorithm, options.objective, options.max_depth) {
+        (algorithm, Objective::Moves, None) | (algorithm @ Algorithm::Greedy, _, None) => algorithm,
+        (_, Objective::Steps, _) | (_, _, Some(_)) => Algorithm::AStar,
+    };
+
+    // The goal boards searched backwards from cannot know along which axis
+    // each block was constrained, nor where the blocks meeting the targets are
+    let algorithm = match algorithm {
+        Algorithm::Bidirectional
+            if start_board.has_constrained_blocks() || !start_board.targets.is_empty() =>
+        {
+            Algorithm::AStar
+        }
+        algorithm => algorithm,
+    };
+
+    let (solved_board, nodes_expanded) = match algorithm {
+        Algorithm::AStar => solve_astar(start_board, options)?,
+        Algorithm::Bfs => parallel_bfs(start_board, options)?,
+        Algorithm::Bidirectional => bidirectional_bfs(start_board, options)?,
+        Algorithm::Greedy => solve_greedy(start_board, options)?,
+        Algorithm::IdaStar => solve_ida_star(start_board, options)?,
+    };
+
+    Ok(Outcome {
+        truncated: solved_board
+            .as_ref()
+            .is_some_and(|board| board.state != BoardState::Solved),
+        moves: solved_board.map(|solved_board| solved_board.moves),
+        nodes_expanded,
+    })
+}
+
+// Find a solution for the board using a known solution of another board with
+// the same blocks, e.g. the board before the player's last move, without
+// searching the whole state space. Boards up to max_depth moves away from the
+// board are searched breadth-first for a board along the known solution, and
+// the rest of the known solution is followed from there. Among the boards
+// reached, the one giving the shortest solution is used. Returns None if no
+// board along the known solution can be reached within max_depth moves.
+pub fn rejoin(
+    board: &Board,
+    solved_board: &Board,
+    solution: &[FlatBoardMove],
+    max_depth: usize,
+    cancellation: &Cancellation,
+) -> Result<Option<Vec<FlatBoardMove>>, SolverError> {
+    let mut path_board = solved_board.clone();
+    path_board.moves.clear();
+
+    let _board_is_solving = path_board.change_state(BoardState::Solving).is_ok();
+
+    // Boards are keyed by the position of each block rather than by their
+    // hashes, since the known solution's moves refer to specific blocks
+    let positions = |board: &Board| -> Vec<(u8, u8)> {
+        board
+            .blocks
+            .iter()
+            .map(|positioned_block| {
+                (
+                    positioned_block.min_position.row,
+                    positioned_block.min_position.col,
+                )
+            })
+            .collect()
+    };
+
+    let mut path_indices = HashMap::from([(positions(&path_board), 0)]);
+
+    for (i, move_) in solution.iter().enumerate() {
+        path_board.move_block_unchecked(move_.block_idx, move_.row_diff, move_.col_diff);
+        path_indices.entry(positions(&path_board)).or_insert(i + 1);
+    }
+
+    let mut root = board.clone();
+    root.moves.clear();
+
+    root.change_state(BoardState::Solving)?;
+    let _board_is_already_solved = root.change_state(BoardState::Solved).is_ok();
+
+    let mut seen = HashSet::from([positions(&root)]);
+    let mut level = vec![root];
+    let mut best: Option<Vec<FlatBoardMove>> = None;
+
+    for depth in 0..=max_depth {
+        if best.as_ref().is_some_and(|best| best.len() <= depth) {
+            break;
+        }
+
+        cancellation.check()?;
+
+        let mut next_level = vec![];
+
+        for mut board in level {
+            if let Some(i) = path_indices.get(&positions(&board)) {
+                if best.as_ref().map_or(usize::MAX, Vec::len) > depth + solution.len() - i {
+                    best = Some(board.moves.iter().chain(&solution[*i..]).cloned().collect());
+                }
+            }
+
+            if depth == max_depth {
+                continue;
+            }
+
+            for (block_idx, moves) in board.get_next_moves().into_iter().enumerate() {
+                for move_ in moves {
+                    board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                    if seen.insert(positions(&board)) {
+                        next_level.push(board.clone());
+                    }
+
+                    board.undo_move_unchecked();
+                }
+            }
+        }
+
+        level = next_level;
+    }
+
+    Ok(best)
+}
+
+// Merge consecutive moves of the same block in the solution into a single
+// flattened move wherever the merged move is one of the board's next moves, and
+// drop consecutive moves which cancel each other out. The moves are replayed
+// from the board's current position, so the compacted solution still solves
+// the board. If the board cannot be solved, the moves are returned unchanged.
+pub fn compact(board: &Board, moves: &[FlatBoardMove]) -> Vec<FlatBoardMove> {
+    let mut current = board.clone();
+    current.moves.clear();
+
+    if current.state != BoardState::Solving && current.change_state(BoardState::Solving).is_err() {
+        return moves.to_vec();
+    }
+
+    for move_ in moves {
+        if let Some(last_move) = current
+            .moves
+            .last()
+            .filter(|last_move| last_move.block_idx == move_.block_idx)
+            .cloned()
+        {
+            current.undo_move_unchecked();
+
+            let merged = FlatMove {
+                row_diff: last_move.row_diff + move_.row_diff,
+                col_diff: last_move.col_diff + move_.col_diff,
+            };
+
+            if merged.steps() == 0 {
+                continue;
+            }
+
+            if current.get_next_moves()[move_.block_idx].contains(&merged) {
+                current.move_block_unchecked(move_.block_idx, merged.row_diff, merged.col_diff);
+                continue;
+            }
+
+            current.move_block_unchecked(
+                last_move.block_idx,
+                last_move.row_diff,
+                last_move.col_diff,
+            );
+        }
+
+        current.move_block_unchecked(move_.block_idx, move_.row_diff, move_.col_diff);
+    }
+
+    current.moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        blocks::{Axis, Block, Corner, Positioned as PositionedBlock},
+        board::{Board, Goal, Target},
+    };
+    use std::sync::Mutex;
+
+    const ALGORITHMS: [Algorithm; 3] = [Algorithm::AStar, Algorithm::Bfs, Algorithm::Bidirectional];
+
+    fn options(algorithm: Algorithm) -> Options {
+        Options {
+            algorithm,
+            ..Options::default()
+        }
+    }
+
+    #[test]
+    fn test_not_ready_board() {
+        let board = Board::default();
+
+        for algorithm in ALGORITHMS {
+            assert!(solve(&board, &options(algorithm)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_is_solvable() {
+        let cancellation = Cancellation::default();
+
+        assert_eq!(
+            is_solvable(&Board::default(), DEFAULT_NODE_BUDGET, &cancellation),
+            Some(false)
+        );
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 1, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        assert_eq!(
+            is_solvable(&board, DEFAULT_NODE_BUDGET, &cancellation),
+            Some(false)
+        );
+        assert_eq!(solve(&board, &options(Algorithm::Bfs)), Ok(None));
+
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        assert_eq!(
+            is_solvable(&board, DEFAULT_NODE_BUDGET, &cancellation),
+            Some(true)
+        );
+        assert_eq!(is_solvable(&board, 1, &cancellation), None);
+
+        cancellation.cancel();
+
+        assert_eq!(
+            is_solvable(&board, DEFAULT_NODE_BUDGET, &cancellation),
+            None
+        );
+    }
+
+    #[test]
+    fn test_goal_boards() {
+        let mut board = Board::default();
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let goals = get_goal_boards(
+            &blocks.iter().map(|block| block.block).collect::<Vec<_>>(),
+            board.goal,
+            &[],
+        );
+
+        assert!(!goals.is_empty());
+
+        let hashes: HashSet<u64> = goals.iter().map(Board::hash).collect();
+
+        assert_eq!(hashes.len(), goals.len());
+
+        for goal in &goals {
+            assert!(goal.is_solved());
+            assert_eq!(goal.blocks.len(), blocks.len());
+            assert_eq!(
+                goal.grid.iter().filter(|cell| cell.is_none()).count(),
+                usize::from(Board::MIN_EMPTY_CELLS)
+            );
+        }
+    }
+
+    #[test]
+    fn test_winning_block_heuristic() {
+        let mut board = Board::default();
+
+        board
+            .add_block(PositionedBlock::new(Block::TWO_BY_TWO, 0, 0).unwrap())
+            .unwrap();
+
+        assert_eq!(winning_block_heuristic(&board), 2);
+
+        board.change_block(0, Block::ONE_BY_ONE).unwrap();
+
+        assert_eq!(winning_block_heuristic(&board), 0);
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        assert_eq!(lower_bound(&board), 0);
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let moves = solve(&board, &options(Algorithm::AStar)).unwrap().unwrap();
+
+        assert_eq!(lower_bound(&board), 2);
+        assert!(lower_bound(&board) <= moves.len());
+    }
+
+    #[test]
+    fn test_ida_star() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let moves = solve(&board, &options(Algorithm::IdaStar))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(moves.len(), 17);
+
+        let limited_options = Options {
+            algorithm: Algorithm::IdaStar,
+            node_budget: 10,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            solve(&board, &limited_options),
+            Err(SolverError::BudgetExceeded)
+        );
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let count = count_solutions(&board, &Options::default()).unwrap();
+
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn test_progress() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let reports = Arc::new(Mutex::new(vec![]));
+        let reports_clone = Arc::clone(&reports);
+
+        let progress_options = Options {
+            algorithm: Algorithm::Bfs,
+            progress: Some(Arc::new(move |progress| {
+                reports_clone.lock().unwrap().push(progress);
+            })),
+            ..Options::default()
+        };
+
+        let moves = solve(&board, &progress_options).unwrap().unwrap();
+
+        let reports = reports.lock().unwrap();
+
+        assert!(!reports.is_empty());
+        assert!(reports.len() <= moves.len());
+
+        for (i, progress) in reports.iter().enumerate() {
+            assert_eq!(progress.depth, i + 1);
+            assert!(progress.queue_size > 0);
+        }
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS.into_iter().chain([Algorithm::IdaStar]) {
+            let timed_out_options = Options {
+                algorithm,
+                cancellation: Cancellation::with_timeout(Duration::ZERO),
+                ..Options::default()
+            };
+
+            assert_eq!(
+                solve(&board, &timed_out_options),
+                Err(SolverError::TimedOut)
+            );
+        }
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS.into_iter().chain([Algorithm::IdaStar]) {
+            let cancellation = Cancellation::default();
+            drop(CancelOnDrop(cancellation.clone()));
+
+            let cancelled_options = Options {
+                algorithm,
+                cancellation,
+                ..Options::default()
+            };
+
+            assert_eq!(
+                solve(&board, &cancelled_options),
+                Err(SolverError::Cancelled)
+            );
+        }
+    }
+
+    fn test_board_is_optimal(blocks: &[PositionedBlock], expected_moves: usize) {
+        let mut board = Board::default();
+
+        for block in blocks.iter() {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS {
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            assert_eq!(moves.len(), expected_moves);
+        }
+    }
+
+    fn test_solution_works(blocks: &[PositionedBlock]) {
+        for algorithm in ALGORITHMS.into_iter().chain([Algorithm::Greedy]) {
+            let mut board = Board::default();
+
+            for block in blocks.iter() {
+                board.add_block(block.clone()).unwrap();
+            }
+
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            for move_ in moves.iter() {
+                board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(board.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_mid_game_board() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        for algorithm in ALGORITHMS {
+            let mut board = Board::default();
+
+            for block in &blocks {
+                board.add_block(block.clone()).unwrap();
+            }
+
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            for move_ in &moves[..5] {
+                board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            let remaining_moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            assert_eq!(board.moves.len(), 5);
+            assert_eq!(remaining_moves.len(), moves.len() - 5);
+
+            for move_ in &remaining_moves {
+                board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(board.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_custom_goal() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let goal = Goal {
+            block: Block::TWO_BY_TWO,
+            row: 3,
+            col: 2,
+        };
+
+        let mut num_moves = None;
+
+        for algorithm in ALGORITHMS {
+            let mut board = Board::default();
+            board.set_goal(goal).unwrap();
+
+            for block in &blocks {
+                board.add_block(block.clone()).unwrap();
+            }
+
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            assert_eq!(*num_moves.get_or_insert(moves.len()), moves.len());
+
+            for move_ in &moves {
+                board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(board.is_solved());
+            assert_eq!(board.blocks[1].min_position.col, 2);
+        }
+    }
+
+    #[test]
+    fn test_targets() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        // The 2x2 block must reach the right side of the board, and the 1x1
+        // block must reach the top-left corner
+        let targets = vec![
+            Target {
+                block: Block::TWO_BY_TWO,
+                cells: (0..Board::ROWS)
+                    .flat_map(|row| {
+                        [
+                            Position::new(row, 2).unwrap(),
+                            Position::new(row, 3).unwrap(),
+                        ]
+                    })
+                    .collect(),
+            },
+            Target {
+                block: Block::ONE_BY_ONE,
+                cells: vec![Position::new(0, 0).unwrap()],
+            },
+        ];
+
+        let mut num_moves = None;
+
+        for algorithm in ALGORITHMS.into_iter().chain([Algorithm::IdaStar]) {
+            let mut board = Board::default();
+            board.set_targets(targets.clone()).unwrap();
+
+            for block in &blocks {
+                board.add_block(block.clone()).unwrap();
+            }
+
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            assert_eq!(*num_moves.get_or_insert(moves.len()), moves.len());
+
+            for move_ in &moves {
+                board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(board.is_solved());
+            assert_eq!(board.blocks[1].min_position.col, 2);
+        }
+    }
+
+    #[test]
+    fn test_steps_objective() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let steps = |moves: &[FlatBoardMove]| moves.iter().map(FlatBoardMove::steps).sum::<usize>();
+
+        let fewest_moves = solve(&board, &options(Algorithm::Bfs)).unwrap().unwrap();
+
+        for algorithm in ALGORITHMS {
+            let fewest_steps = solve(
+                &board,
+                &Options {
+                    objective: Objective::Steps,
+                    ..options(algorithm)
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+            assert!(steps(&fewest_steps) <= steps(&fewest_moves));
+            assert!(fewest_steps.len() >= fewest_moves.len());
+
+            let mut solved_board = board.clone();
+
+            for move_ in &fewest_steps {
+                solved_board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(solved_board.is_solved());
+        }
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let options = Options {
+            deterministic: true,
+            ..options(Algorithm::Bfs)
+        };
+
+        let moves = solve(&board, &options).unwrap().unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(solve(&board, &options).unwrap().unwrap(), moves);
+        }
+    }
+
+    #[test]
+    fn test_move_ordering() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in [Algorithm::AStar, Algorithm::IdaStar] {
+            let unordered_options = Options {
+                move_ordering: OrderingStrategy::Unordered.ordering(),
+                ..options(algorithm)
+            };
+
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+            let unordered_moves = solve(&board, &unordered_options).unwrap().unwrap();
+
+            assert_eq!(moves.len(), 17);
+            assert_eq!(unordered_moves.len(), 17);
+        }
+
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 2).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        // The goal block is blocked by the two 1x1 blocks below it, whose moves
+        // out of its way are expanded first with the GoalFirst ordering, while
+        // the moves of the 2x1 blocks come first in the board's next moves. The
+        // boards reached by moving blocks 5 and 8 are the mirror images of those
+        // reached by moving blocks 4 and 7, so they are never expanded.
+        let down = FlatBoardMove {
+            block_idx: 4,
+            row_diff: 1,
+            col_diff: 0,
+        };
+        let left = FlatBoardMove {
+            block_idx: 7,
+            row_diff: 0,
+            col_diff: -1,
+        };
+
+        assert_eq!(
+            expanded_first_moves(&board, Algorithm::AStar, ordering::GoalFirst),
+            [left.clone(), down.clone()]
+        );
+        assert_eq!(
+            expanded_first_moves(&board, Algorithm::IdaStar, ordering::GoalFirst),
+            std::slice::from_ref(&left)
+        );
+
+        for algorithm in [Algorithm::AStar, Algorithm::IdaStar] {
+            assert_eq!(
+                expanded_first_moves(&board, algorithm, ordering::Unordered),
+                [down.clone(), left.clone()]
+            );
+        }
+    }
+
+    // Ranks moves with the inner ordering, recording the moves which reached
+    // each board whose moves are ranked, i.e. each board expanded, in order
+    struct Recording<O> {
+        inner: O,
+        expanded: Mutex<Vec<Vec<FlatBoardMove>>>,
+    }
+
+    impl<O: MoveOrdering> MoveOrdering for Recording<O> {
+        fn rank(&self, board: &Board, block_idx: usize, move_: &FlatMove) -> usize {
+            let mut expanded = self.expanded.lock().unwrap();
+
+            if expanded.last() != Some(&board.moves) {
+                expanded.push(board.moves.clone());
+            }
+
+            self.inner.rank(board, block_idx, move_)
+        }
+    }
+
+    // Moves of the root expanded while solving the board with the ordering, in
+    // the order the boards they reach were expanded. The root is expanded again
+    // in each iteration of IdaStar, so only the last iteration is kept.
+    fn expanded_first_moves<O: MoveOrdering + 'static>(
+        board: &Board,
+        algorithm: Algorithm,
+        ordering: O,
+    ) -> Vec<FlatBoardMove> {
+        let recording = Arc::new(Recording {
+            inner: ordering,
+            expanded: Mutex::default(),
+        });
+
+        let options = Options {
+            move_ordering: recording.clone(),
+            ..options(algorithm)
+        };
+
+        solve(board, &options).unwrap().unwrap();
+
+        let expanded = recording.expanded.lock().unwrap();
+        let last_root = expanded.iter().rposition(Vec::is_empty).unwrap();
+
+        expanded[last_root..]
+            .iter()
+            .filter(|moves| moves.len() == 1)
+            .map(|moves| moves[0].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_solve_with_stats() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS
+            .into_iter()
+            .chain([Algorithm::Greedy, Algorithm::IdaStar])
+        {
+            let outcome = solve_with_stats(&board, &options(algorithm)).unwrap();
+
+            let expected_moves = solve(&board, &options(algorithm)).unwrap();
+
+            assert_eq!(
+                outcome.moves.map(|moves| moves.len()),
+                expected_moves.map(|moves| moves.len())
+            );
+            assert!(outcome.nodes_expanded > 0);
+            assert!(!outcome.truncated);
+        }
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let truncated_options = Options {
+            max_depth: Some(5),
+            ..options(Algorithm::Bfs)
+        };
+
+        assert_eq!(
+            solve(&board, &truncated_options),
+            Err(SolverError::DepthExceeded)
+        );
+
+        let outcome = solve_with_stats(&board, &truncated_options).unwrap();
+        let moves = outcome.moves.unwrap();
+
+        assert!(outcome.truncated);
+        assert!(!moves.is_empty() && moves.len() <= 5);
+
+        let mut partial_board = board.clone();
+
+        for move_ in &moves {
+            partial_board
+                .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                .unwrap();
+        }
+
+        assert!(goal_distance(&partial_board) < goal_distance(&board));
+
+        let deep_options = Options {
+            max_depth: Some(17),
+            ..options(Algorithm::Bfs)
+        };
+
+        let outcome = solve_with_stats(&board, &deep_options).unwrap();
+
+        assert!(!outcome.truncated);
+        assert_eq!(outcome.moves.map(|moves| moves.len()), Some(17));
+    }
+
+    #[test]
+    fn test_walls() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.add_wall(4, 0).unwrap();
+
+        let mut lengths = HashSet::new();
+
+        for algorithm in ALGORITHMS {
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            let mut solved_board = board.clone();
+
+            for move_ in &moves {
+                solved_board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(solved_board.is_solved());
+            assert!(solved_board
+                .blocks
+                .iter()
+                .all(|positioned_block| positioned_block
+                    .range
+                    .iter()
+                    .all(|(row, col)| !solved_board.is_wall(*row, *col))));
+
+            lengths.insert(moves.len());
+        }
+
+        assert_eq!(lengths.len(), 1);
+    }
+
+    #[test]
+    fn test_l_shapes() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::LShape(Corner::TopRight), 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::LShape(Corner::BottomLeft), 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS {
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            let mut solved_board = board.clone();
+
+            for move_ in &moves {
+                solved_board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(solved_board.is_solved());
+            assert_eq!(moves.len(), 41);
+        }
+    }
+
+    #[test]
+    fn test_join_paths() {
+        // The backward board moved the 1x1 block into the corner of the
+        // L-shape, and the forward board lists the L-shape first
+        let l_shape = PositionedBlock::new(Block::LShape(Corner::TopLeft), 1, 1).unwrap();
+
+        let mut backward = Board::default();
+        backward
+            .add_block(PositionedBlock::new(Block::ONE_BY_ONE, 0, 1).unwrap())
+            .unwrap();
+        backward.add_block(l_shape.clone()).unwrap();
+        backward.move_block_unchecked(0, 1, 0);
+
+        let mut forward = Board::default();
+        forward.add_block(l_shape).unwrap();
+        forward
+            .add_block(PositionedBlock::new(Block::ONE_BY_ONE, 1, 1).unwrap())
+            .unwrap();
+
+        let joined = join_paths(forward, backward);
+
+        assert_eq!(joined.moves.len(), 1);
+        assert_eq!(joined.moves[0].block_idx, 1);
+        assert_eq!(joined.blocks[0].min_position, Position::new(1, 1).unwrap());
+        assert_eq!(joined.blocks[1].min_position, Position::new(0, 1).unwrap());
+    }
+
+    #[test]
+    fn test_l_shape_sharing_min_position() {
+        // The L-shape missing its top-left corner has the same min position as
+        // the block in that corner, whichever of them comes first
+        let l_shape = PositionedBlock::new(Block::LShape(Corner::TopLeft), 2, 0).unwrap();
+        let corner = PositionedBlock::new(Block::ONE_BY_ONE, 2, 0).unwrap();
+
+        for first_blocks in [[corner.clone(), l_shape.clone()], [l_shape, corner]] {
+            let blocks: Vec<PositionedBlock> = first_blocks
+                .into_iter()
+                .chain([
+                    PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 0, 1).unwrap(),
+                    PositionedBlock::new(Block::TWO_BY_TWO, 0, 2).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 1, 1).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 4, 1).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 4, 2).unwrap(),
+                    PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+                ])
+                .collect();
+
+            test_solution_works(&blocks);
+        }
+    }
+
+    #[test]
+    fn test_rectangles() {
+        let blocks = [
+            PositionedBlock::new(Block::Rectangle { rows: 3, cols: 1 }, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS {
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            let mut solved_board = board.clone();
+
+            for move_ in &moves {
+                solved_board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(solved_board.is_solved());
+            assert_eq!(moves.len(), 18);
+        }
+    }
+
+    #[test]
+    fn test_three_empty_cells() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board.set_empty_cells(3).unwrap();
+
+        let mut lengths = vec![];
+
+        for algorithm in ALGORITHMS {
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            let mut solved_board = board.clone();
+
+            for move_ in &moves {
+                solved_board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(solved_board.is_solved());
+
+            lengths.push(moves.len());
+        }
+
+        assert!(lengths.iter().all(|length| *length == lengths[0]));
+        assert!(lengths[0] < 81);
+    }
+
+    #[test]
+    fn test_constrained_blocks() {
+        let blocks = [
+            PositionedBlock::new(Block::Rectangle { rows: 3, cols: 1 }, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3)
+                .unwrap()
+                .with_axis(Some(Axis::Vertical)),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        for algorithm in ALGORITHMS {
+            let moves = solve(&board, &options(algorithm)).unwrap().unwrap();
+
+            let mut solved_board = board.clone();
+
+            for move_ in &moves {
+                solved_board
+                    .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+            }
+
+            assert!(solved_board.is_solved());
+            assert_eq!(moves.len(), 29);
+        }
+
+        let mut board = Board::default();
+
+        for (block_idx, block) in blocks.iter().enumerate() {
+            let axis = (block_idx == 1).then_some(Axis::Horizontal);
+            board.add_block(block.clone().with_axis(axis)).unwrap();
+        }
+
+        assert!(analysis::is_unsolvable(&board));
+
+        for algorithm in ALGORITHMS {
+            assert_eq!(solve(&board, &options(algorithm)), Ok(None));
+        }
+    }
+
+    #[test]
+    fn test_rejoin() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let solution = solve(&board, &Options::default()).unwrap().unwrap();
+        let cancellation = Cancellation::default();
+
+        board.change_state(BoardState::Solving).unwrap();
+
+        for (block_idx, moves) in board.clone().get_next_moves().into_iter().enumerate() {
+            for move_ in moves {
+                let mut deviated_board = board.clone();
+                deviated_board
+                    .move_block(block_idx, move_.row_diff, move_.col_diff)
+                    .unwrap();
+
+                let rejoined_moves = rejoin(
+                    &deviated_board,
+                    &board,
+                    &solution,
+                    MAX_REJOIN_DEPTH,
+                    &cancellation,
+                )
+                .unwrap()
+                .unwrap();
+
+                if FlatBoardMove::new(block_idx, &move_) == solution[0] {
+                    assert_eq!(rejoined_moves, solution[1..]);
+                }
+
+                let optimal_moves = solve(&deviated_board, &Options::default())
+                    .unwrap()
+                    .unwrap();
+
+                assert!(rejoined_moves.len() >= optimal_moves.len());
+                assert!(rejoined_moves.len() <= solution.len() + 1);
+
+                for move_ in &rejoined_moves {
+                    deviated_board
+                        .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                        .unwrap();
+                }
+
+                assert!(deviated_board.is_solved());
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        let moves = solve(&board, &Options::default()).unwrap().unwrap();
+
+        assert_eq!(compact(&board, &moves), moves);
+
+        // Split every straight two-step move into two one-step moves, and
+        // follow the first move by a move there and back
+        let mut split_moves = vec![moves[0].clone(), moves[0].opposite(), moves[0].clone()];
+
+        for move_ in &moves[1..] {
+            if move_.steps() == 2 && (move_.row_diff == 0 || move_.col_diff == 0) {
+                let half_move = FlatBoardMove {
+                    block_idx: move_.block_idx,
+                    row_diff: move_.row_diff / 2,
+                    col_diff: move_.col_diff / 2,
+                };
+
+                split_moves.extend([half_move.clone(), half_move]);
+            } else {
+                split_moves.push(move_.clone());
+            }
+        }
+
+        assert!(split_moves.len() > moves.len());
+
+        let compacted_moves = compact(&board, &split_moves);
+
+        assert_eq!(compacted_moves.len(), moves.len());
+
+        for move_ in &compacted_moves {
+            board
+                .move_block(move_.block_idx, move_.row_diff, move_.col_diff)
+                .unwrap();
+        }
+
+        assert!(board.is_solved());
+    }
+
+    #[test]
+    fn test_solved_board() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_TWO, 0, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 0, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 1, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+        ];
+
+        test_board_is_optimal(&blocks, 0);
+    }
+
+    #[test]
+    fn test_classic_board_solution_works() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        test_solution_works(&blocks);
+    }
+
+    #[test]
+    fn test_classic_board_is_optimal() {
+        let blocks = [
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        test_board_is_optimal(&blocks, 81);
+    }
+
+    #[test]
+    fn test_easy_board_solution_works() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        test_solution_works(&blocks);
+    }
+
+    #[test]
+    fn test_easy_board_is_optimal() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        test_board_is_optimal(&blocks, 17);
+    }
+
+    #[test]
+    fn test_medium_board_solution_works() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 4, 1).unwrap(),
+        ];
+
+        test_solution_works(&blocks);
+    }
+
+    #[test]
+    fn test_medium_board_is_optimal() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 4, 1).unwrap(),
+        ];
+
+        test_board_is_optimal(&blocks, 40);
+    }
+
+    #[test]
+    fn test_hard_board_solution_works() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 4, 1).unwrap(),
+        ];
+
+        test_solution_works(&blocks);
+    }
+
+    #[test]
+    fn test_hard_board_is_optimal() {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_TWO, 4, 1).unwrap(),
+        ];
+
+        test_board_is_optimal(&blocks, 120);
+    }
+}
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+DELETE FROM solutions
@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE jobs (
+    id        SERIAL PRIMARY KEY,
+    board_id  INT4 NOT NULL,
+    status    VARCHAR(20) NOT NULL,
+    params    TEXT NOT NULL,
+    moves     TEXT
+)
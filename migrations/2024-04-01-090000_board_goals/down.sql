@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE solutions DROP COLUMN goal;
+ALTER TABLE boards DROP COLUMN goal
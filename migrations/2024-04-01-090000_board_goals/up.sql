@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE boards ADD COLUMN goal TEXT NOT NULL DEFAULT '{"block":"two_by_two","row":3,"col":1}';
+ALTER TABLE solutions ADD COLUMN goal TEXT NOT NULL DEFAULT '{"block":"two_by_two","row":3,"col":1}'
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+CREATE TABLE distances (
+    hash      BIGINT PRIMARY KEY,
+    distance  INT4 NOT NULL
+)
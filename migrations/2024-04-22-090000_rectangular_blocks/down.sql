@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+UPDATE boards SET
+    blocks = REPLACE(REPLACE(REPLACE(REPLACE(blocks, '{"rectangle":{"rows":1,"cols":1}}', '"one_by_one"'), '{"rectangle":{"rows":1,"cols":2}}', '"one_by_two"'), '{"rectangle":{"rows":2,"cols":1}}', '"two_by_one"'), '{"rectangle":{"rows":2,"cols":2}}', '"two_by_two"'),
+    grid = REPLACE(REPLACE(REPLACE(REPLACE(grid, '{"rectangle":{"rows":1,"cols":1}}', '"one_by_one"'), '{"rectangle":{"rows":1,"cols":2}}', '"one_by_two"'), '{"rectangle":{"rows":2,"cols":1}}', '"two_by_one"'), '{"rectangle":{"rows":2,"cols":2}}', '"two_by_two"'),
+    goal = REPLACE(REPLACE(REPLACE(REPLACE(goal, '{"rectangle":{"rows":1,"cols":1}}', '"one_by_one"'), '{"rectangle":{"rows":1,"cols":2}}', '"one_by_two"'), '{"rectangle":{"rows":2,"cols":1}}', '"two_by_one"'), '{"rectangle":{"rows":2,"cols":2}}', '"two_by_two"');
+ALTER TABLE boards ALTER COLUMN goal SET DEFAULT '{"block":"two_by_two","row":3,"col":1}';
+ALTER TABLE solutions ALTER COLUMN goal SET DEFAULT '{"block":"two_by_two","row":3,"col":1}';
+-- Board hashes are encoded differently, so hashed solutions and distances are stale
+DELETE FROM solutions;
+DELETE FROM distances
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+UPDATE boards SET
+    blocks = REPLACE(REPLACE(REPLACE(REPLACE(blocks, '"one_by_one"', '{"rectangle":{"rows":1,"cols":1}}'), '"one_by_two"', '{"rectangle":{"rows":1,"cols":2}}'), '"two_by_one"', '{"rectangle":{"rows":2,"cols":1}}'), '"two_by_two"', '{"rectangle":{"rows":2,"cols":2}}'),
+    grid = REPLACE(REPLACE(REPLACE(REPLACE(grid, '"one_by_one"', '{"rectangle":{"rows":1,"cols":1}}'), '"one_by_two"', '{"rectangle":{"rows":1,"cols":2}}'), '"two_by_one"', '{"rectangle":{"rows":2,"cols":1}}'), '"two_by_two"', '{"rectangle":{"rows":2,"cols":2}}'),
+    goal = REPLACE(REPLACE(REPLACE(REPLACE(goal, '"one_by_one"', '{"rectangle":{"rows":1,"cols":1}}'), '"one_by_two"', '{"rectangle":{"rows":1,"cols":2}}'), '"two_by_one"', '{"rectangle":{"rows":2,"cols":1}}'), '"two_by_two"', '{"rectangle":{"rows":2,"cols":2}}');
+ALTER TABLE boards ALTER COLUMN goal SET DEFAULT '{"block":{"rectangle":{"rows":2,"cols":2}},"row":3,"col":1}';
+ALTER TABLE solutions ALTER COLUMN goal SET DEFAULT '{"block":{"rectangle":{"rows":2,"cols":2}},"row":3,"col":1}';
+-- Board hashes are encoded differently, so hashed solutions and distances are stale
+DELETE FROM solutions;
+DELETE FROM distances
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE boards ADD COLUMN empty_cells SMALLINT NOT NULL DEFAULT 2
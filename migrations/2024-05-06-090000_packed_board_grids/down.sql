@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- Packed grids cannot be unpacked here, so boards are left with empty grids
+ALTER TABLE boards ALTER COLUMN grid TYPE TEXT USING '[null,null,null,null,null,null,null,null,null,null,null,null,null,null,null,null,null,null,null,null]'
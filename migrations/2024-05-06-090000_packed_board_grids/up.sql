@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Grids are rebuilt from the blocks when boards are loaded, so the packed grids
+-- of existing boards are filled in the next time the boards are saved
+ALTER TABLE boards ALTER COLUMN grid TYPE BIGINT USING 0
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE boards DROP COLUMN started_solving_at;
+ALTER TABLE boards DROP COLUMN finished_at
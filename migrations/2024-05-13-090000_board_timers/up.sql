@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE boards ADD COLUMN started_solving_at BIGINT;
+ALTER TABLE boards ADD COLUMN finished_at BIGINT
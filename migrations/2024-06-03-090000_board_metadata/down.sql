@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE boards DROP COLUMN name;
+ALTER TABLE boards DROP COLUMN description;
+ALTER TABLE boards DROP COLUMN tags
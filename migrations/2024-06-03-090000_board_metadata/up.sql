@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TABLE boards ADD COLUMN name VARCHAR(100);
+ALTER TABLE boards ADD COLUMN description TEXT;
+ALTER TABLE boards ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}'
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE boards ADD COLUMN difficulty INTEGER
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX boards_start_hash_idx;
+ALTER TABLE boards DROP COLUMN start_hash
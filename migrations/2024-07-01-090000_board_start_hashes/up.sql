@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- Boards which have made no moves start from their current position, and the
+-- starting hashes of other boards are filled in at startup, once their moves
+-- have been undone
+ALTER TABLE boards ADD COLUMN start_hash BIGINT;
+UPDATE boards SET start_hash = grid WHERE moves = '[]';
+CREATE INDEX boards_start_hash_idx ON boards (start_hash)
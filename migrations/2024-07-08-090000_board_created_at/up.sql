@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- The default is only set once the column exists, so that the creation times
+-- of existing boards are left unknown rather than set to now
+ALTER TABLE boards ADD COLUMN created_at BIGINT;
+ALTER TABLE boards ALTER COLUMN created_at SET DEFAULT (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE api_keys (
+    id                    SERIAL PRIMARY KEY,
+    key                   VARCHAR(64) NOT NULL UNIQUE,
+    name                  VARCHAR(100) NOT NULL,
+    rate_limit            INT4,
+    expensive_rate_limit  INT4,
+    requests              INT8 NOT NULL DEFAULT 0,
+    last_used_at          INT8,
+    revoked               BOOLEAN NOT NULL DEFAULT FALSE
+)
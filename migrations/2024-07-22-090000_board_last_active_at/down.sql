@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX boards_last_active_at_idx;
+ALTER TABLE boards DROP COLUMN last_active_at
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Existing boards are treated as last active when the column is added, so
+-- that they are not expired as soon as the migration runs
+ALTER TABLE boards ADD COLUMN last_active_at BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT;
+CREATE INDEX boards_last_active_at_idx ON boards (last_active_at)
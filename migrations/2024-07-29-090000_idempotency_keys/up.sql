@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- Keys are scoped to the API key the request was made with, where 0 stands
+-- for requests made without an API key, and the response is null until the
+-- request has been handled
+CREATE TABLE idempotency_keys (
+    key         VARCHAR(255) NOT NULL,
+    api_key_id  INT4 NOT NULL,
+    method      VARCHAR(10) NOT NULL,
+    path        TEXT NOT NULL,
+    status      INT2,
+    headers     TEXT,
+    body        BYTEA,
+    created_at  INT8 NOT NULL,
+    PRIMARY KEY (key, api_key_id)
+);
+CREATE INDEX idempotency_keys_created_at_idx ON idempotency_keys (created_at)
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- Solves of the puzzles in the library of layouts, whose moves have been
+-- verified, ranked by their number of moves or their duration
+CREATE TABLE scores (
+    id           SERIAL PRIMARY KEY,
+    puzzle       VARCHAR(100) NOT NULL,
+    player       VARCHAR(50) NOT NULL,
+    num_moves    INT4 NOT NULL,
+    duration_ms  INT8 NOT NULL,
+    created_at   INT8 NOT NULL
+);
+CREATE INDEX scores_puzzle_num_moves_idx ON scores (puzzle, num_moves, duration_ms);
+CREATE INDEX scores_puzzle_duration_ms_idx ON scores (puzzle, duration_ms, num_moves)
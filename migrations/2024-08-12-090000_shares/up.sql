@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- Layouts shared by short codes, which are kept after the shared board is
+-- deleted or expires
+CREATE TABLE shares (
+    code        VARCHAR(16) PRIMARY KEY,
+    notation    TEXT NOT NULL,
+    name        VARCHAR(100),
+    created_at  INT8 NOT NULL
+)
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- The times of moves made before move times were recorded are not known
+ALTER TABLE boards ADD COLUMN move_times TEXT NOT NULL DEFAULT '[]';
+UPDATE boards SET move_times = (
+    SELECT COALESCE(json_agg(NULL::INT8), '[]')::TEXT
+    FROM generate_series(1, json_array_length(moves::json))
+)
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Keys reserved before fingerprints were recorded match no request
+ALTER TABLE idempotency_keys ADD COLUMN fingerprint VARCHAR(64) NOT NULL DEFAULT ''
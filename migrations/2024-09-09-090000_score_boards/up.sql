@@ -0,0 +1,3 @@
+-- Your SQL goes here
+-- Scores submitted before scores were verified from boards have no board
+ALTER TABLE scores ADD COLUMN board_id INT4 UNIQUE
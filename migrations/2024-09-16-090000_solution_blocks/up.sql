@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Solutions cached before their blocks were recorded cannot be reindexed onto
+-- other boards, since the order of the blocks their moves index into is lost
+DELETE FROM solutions;
+
+ALTER TABLE solutions ADD COLUMN blocks TEXT NOT NULL
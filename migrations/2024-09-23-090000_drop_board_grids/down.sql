@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE boards ADD COLUMN grid BIGINT NOT NULL DEFAULT 0
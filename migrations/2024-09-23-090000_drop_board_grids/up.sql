@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Grids are rebuilt from the blocks when boards are loaded, and the starting
+-- hashes of boards are kept in their own column, so packed grids are never read
+ALTER TABLE boards DROP COLUMN grid
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+-- Hashed keys cannot be recovered, so keys must be issued again
+ALTER TABLE api_keys RENAME COLUMN key_hash TO key
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- Keys are stored as the hex encoded SHA-256 hashes of the keys, so that the
+-- keys themselves cannot be read from the table
+ALTER TABLE api_keys RENAME COLUMN key TO key_hash;
+
+UPDATE api_keys SET key_hash = encode(sha256(convert_to(key_hash, 'UTF8')), 'hex')
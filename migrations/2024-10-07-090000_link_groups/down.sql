@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+UPDATE boards SET start_hash = NULL WHERE blocks LIKE '%"link_group":%';
+
+UPDATE boards SET
+    blocks = regexp_replace(blocks, '"link_group":\d+', '"linked":true', 'g'),
+    edits = regexp_replace(edits, '"link_group":\d+', '"linked":true', 'g')
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- Boards had a single group of linked blocks, which becomes their first link
+-- group, and since hashes now count link groups, the starting hashes of boards
+-- with linked blocks are filled in again at startup
+UPDATE boards SET start_hash = NULL WHERE blocks LIKE '%"linked":true%';
+
+UPDATE boards SET
+    blocks = replace(blocks, '"linked":true', '"link_group":0'),
+    edits = replace(edits, '"linked":true', '"link_group":0')
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+UPDATE boards SET start_hash = NULL WHERE blocks LIKE '%"link_group":%'
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Hashes of boards with linked blocks are now mixed from exact keys that tell
+-- apart separate link groups, so their starting hashes are filled in again at
+-- startup
+UPDATE boards SET start_hash = NULL WHERE blocks LIKE '%"link_group":%'
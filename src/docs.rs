@@ -1,43 +1,278 @@
 use utoipa::OpenApi;
 
+use crate::errors::http::{Body as ErrorResponse, Kind as ErrorKind};
 use crate::handlers;
 use crate::models::api::request::{
-    AddBlock, AlterBlock, AlterBoard, ChangeBlock, ChangeState, MoveBlock,
+    AddBlock, AddBlocks, AlterBlock, AlterBoard, ApplyMoves, ChangeBlock, ChangeState,
+    CompareAlgorithms, DeleteBoards, FindHardest, Format, ImportBoard, LinkBlocks, Metadata,
+    MoveBlock, MoveBlockSteps, NewBoard, SessionCommand, SessionMoveBlock, SetEmptyCells,
+    SetLocked, SetTargets, SolveParams, SubmitScore, TransformBoard, ValidateBoard, Wall,
 };
-use crate::models::api::response::{Board, Solution, Solved};
-use crate::models::game::blocks::{Block, Positioned};
-use crate::models::game::board::State;
-use crate::models::game::moves::{FlatBoardMove, FlatMove};
+use crate::models::api::response::{
+    AlgorithmComparison, AlgorithmRun, Board, BoardActivity, BoardDeletion, BoardDeletions,
+    BoardDiff, BoardMetadata, BoardSummaries, BoardSummary, Boards, CachedSolution,
+    CachedSolutions, DailyPuzzle, DeletionStatus, Estimate, ExportedBoard, Graph, HardestBoards,
+    Hint, HintedMoves, History, Job, Layout, Layouts, Leaderboard, Moves, NextMove, NextMoves,
+    Puzzle, Puzzles, Replay, ReplayMove, Score, SessionEvent, Share, Snapshot as SolutionSnapshot,
+    Solution, Solvable, Solved, Validation,
+};
+use crate::models::game::blocks::{Axis, Block, Corner, Positioned};
+use crate::models::game::board::{Goal, NotReadyReason, State, Target, Variant};
+use crate::models::game::history::{Edit, Event as EditEvent};
+use crate::models::game::moves::{DetailedMove, FlatBoardMove, FlatMove, Step};
 use crate::models::game::utils::Position;
+use crate::models::game::validation::Issue as LayoutIssue;
+use crate::services::{
+    explorer::{Edge as GraphEdge, Node as GraphNode},
+    jobs::Status as JobStatus,
+    leaderboards::Ranking,
+    ordering::Strategy as MoveOrderingStrategy,
+    randomizer::Difficulty,
+    solver::{Algorithm, Objective, Progress},
+};
+
+// Error responses shared by many operations, which are registered once as
+// components and referred to by the operations' responses. Their types only
+// describe the responses, so are never constructed.
+mod responses {
+    #![allow(dead_code)]
+
+    use utoipa::ToResponse;
+
+    use crate::errors::http::Body as ErrorResponse;
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Invalid parameters",
+        examples(
+            ("Query" = (value = json!({"error": "bad_request", "message": "Invalid input: Invalid query parameters"}))),
+            ("Path" = (value = json!({"error": "bad_request", "message": "Invalid input: Invalid path parameters"}))),
+            ("Body" = (value = json!({"error": "bad_request", "message": "Invalid input: Invalid JSON payload"}))),
+        ),
+    )]
+    pub struct InvalidParameters(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Missing API key",
+        example = json!({"error": "unauthorized", "message": "Unauthorized: X-Api-Key header is required"}),
+    )]
+    pub struct MissingApiKey(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Action not allowed",
+        example = json!({"error": "forbidden", "message": "Forbidden: Board state is invalid for operation"}),
+    )]
+    pub struct NotAllowed(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Board not found",
+        example = json!({"error": "not_found", "message": "Not found: No board with matching ID"}),
+    )]
+    pub struct BoardNotFound(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Board version is stale",
+        example = json!({"error": "conflict", "message": "Conflict: Board has been changed since the given version"}),
+    )]
+    pub struct StaleVersion(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Board has expired",
+        example = json!({"error": "gone", "message": "Gone: Board has expired"}),
+    )]
+    pub struct BoardExpired(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Board ETag does not match",
+        example = json!({"error": "precondition_failed", "message": "Precondition failed: Board ETag does not match"}),
+    )]
+    pub struct ETagMismatch(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Rate limit exceeded",
+        example = json!({"error": "too_many_requests", "message": "Too many requests: retry after 6 seconds"}),
+    )]
+    pub struct RateLimited(ErrorResponse);
+
+    #[derive(ToResponse)]
+    #[response(
+        description = "Unhandled exception",
+        example = json!({"error": "unhandled", "message": "Internal server error: Unhandled exception"}),
+    )]
+    pub struct Unhandled(ErrorResponse);
+}
+
+pub use responses::{
+    BoardExpired, BoardNotFound, ETagMismatch, InvalidParameters, MissingApiKey, NotAllowed,
+    RateLimited, StaleVersion, Unhandled,
+};
 
 #[derive(OpenApi)]
 #[openapi(
     info(title = "Klotski API", version = "0.1.0",),
     paths(
+        handlers::admin::find_hardest,
+        handlers::admin::compare_algorithms,
         handlers::block::add,
+        handlers::block::add_many,
         handlers::block::alter,
         handlers::block::remove,
         handlers::board::new,
+        handlers::board::validate,
+        handlers::board::import,
+        handlers::board::get,
+        handlers::board::keep_alive,
+        handlers::board::list,
+        handlers::board::search,
         handlers::board::alter,
+        handlers::board::apply_moves,
         handlers::board::delete,
+        handlers::board::delete_many,
         handlers::board::solve,
+        handlers::board::check_solvable,
+        handlers::board::estimate,
+        handlers::board::hint,
+        handlers::board::solve_async,
+        handlers::board::solve_stream,
+        handlers::board::graph,
+        handlers::board::snapshot,
+        handlers::board::history,
+        handlers::board::export,
+        handlers::board::moves,
+        handlers::board::replay,
+        handlers::board::next_moves,
+        handlers::board::diff,
+        handlers::board::clone,
+        handlers::board::transform,
+        handlers::job::get,
+        handlers::layout::list,
+        handlers::puzzle::list,
+        handlers::puzzle::daily,
+        handlers::puzzle::start,
+        handlers::puzzle::submit_score,
+        handlers::puzzle::leaderboard,
+        handlers::share::create,
+        handlers::share::open,
+        handlers::session::connect,
+        handlers::solution::get,
     ),
-    components(schemas(
-        AddBlock,
-        AlterBlock,
-        AlterBoard,
-        Block,
-        Board,
-        ChangeBlock,
-        ChangeState,
-        FlatBoardMove,
-        FlatMove,
-        MoveBlock,
-        Positioned,
-        Position,
-        Solution,
-        Solved,
-        State
-    ),)
+    components(
+        schemas(
+            AddBlock,
+            AddBlocks,
+            Algorithm,
+            AlgorithmComparison,
+            AlgorithmRun,
+            AlterBlock,
+            AlterBoard,
+            ApplyMoves,
+            Axis,
+            Block,
+            Board,
+            BoardActivity,
+            BoardDeletion,
+            BoardDeletions,
+            BoardDiff,
+            BoardMetadata,
+            BoardSummaries,
+            BoardSummary,
+            Boards,
+            CachedSolution,
+            CachedSolutions,
+            ChangeBlock,
+            ChangeState,
+            CompareAlgorithms,
+            Corner,
+            DailyPuzzle,
+            DeleteBoards,
+            DeletionStatus,
+            DetailedMove,
+            Difficulty,
+            Edit,
+            EditEvent,
+            ErrorKind,
+            ErrorResponse,
+            Estimate,
+            ExportedBoard,
+            FindHardest,
+            FlatBoardMove,
+            FlatMove,
+            Format,
+            Goal,
+            Graph,
+            GraphEdge,
+            GraphNode,
+            HardestBoards,
+            Hint,
+            HintedMoves,
+            History,
+            ImportBoard,
+            Job,
+            JobStatus,
+            Layout,
+            LayoutIssue,
+            Layouts,
+            Leaderboard,
+            LinkBlocks,
+            Metadata,
+            MoveBlock,
+            MoveBlockSteps,
+            MoveOrderingStrategy,
+            Moves,
+            NewBoard,
+            NextMove,
+            NextMoves,
+            NotReadyReason,
+            Objective,
+            Positioned,
+            Position,
+            Progress,
+            Puzzle,
+            Puzzles,
+            Ranking,
+            Replay,
+            ReplayMove,
+            Score,
+            SessionCommand,
+            SessionEvent,
+            SessionMoveBlock,
+            SetEmptyCells,
+            SetLocked,
+            SetTargets,
+            Share,
+            Solution,
+            SolutionSnapshot,
+            Solvable,
+            SolveParams,
+            Solved,
+            State,
+            Step,
+            SubmitScore,
+            Target,
+            TransformBoard,
+            ValidateBoard,
+            Validation,
+            Variant,
+            Wall
+        ),
+        responses(
+            BoardExpired,
+            BoardNotFound,
+            ETagMismatch,
+            InvalidParameters,
+            MissingApiKey,
+            NotAllowed,
+            RateLimited,
+            StaleVersion,
+            Unhandled
+        ),
+    )
 )]
 pub struct ApiDoc;
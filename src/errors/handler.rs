@@ -4,6 +4,9 @@ use std::fmt;
 #[derive(Debug)]
 pub enum Error {
     Body,
+    // name given in the include or exclude query params which is not that of
+    // an optional board field
+    Field(String),
     Path,
     Query,
 }
@@ -14,6 +17,7 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Error::Body => write!(f, "Invalid JSON payload"),
+            Error::Field(name) => write!(f, "Unknown board field: {name}"),
             Error::Path => write!(f, "Invalid path parameters"),
             Error::Query => write!(f, "Invalid query parameters"),
         }
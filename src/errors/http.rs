@@ -1,28 +1,75 @@
 use axum::{
-    http::StatusCode,
+    http::{header::RETRY_AFTER, StatusCode},
     response::{IntoResponse, Response},
+    Json,
 };
+use serde::Serialize;
 use std::{error, fmt};
+use utoipa::ToSchema;
 
 use crate::errors::{board::Error as BoardError, handler::Error as HandlerError};
 use crate::repositories::boards::Error as BoardsRepositoryError;
 
 #[derive(Debug)]
 pub enum Error {
+    Conflict(String),
     Forbidden(String),
+    Gone(String),
     NotFound(String),
     BadRequest(String),
+    PreconditionFailed(String),
+    Unauthorized(String),
+    Unprocessable(String),
+    // number of seconds after which the request may be retried
+    TooManyRequests(u64),
     Unhandled(String),
 }
 
+// Kind of failure, which is the same for every error response with the same
+// status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[schema(as = ErrorKind)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Conflict,
+    Forbidden,
+    Gone,
+    NotFound,
+    BadRequest,
+    PreconditionFailed,
+    Unauthorized,
+    Unprocessable,
+    TooManyRequests,
+    Unhandled,
+}
+
+// JSON body of every error response
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(
+    as = ErrorResponse,
+    example = json!({"error": "not_found", "message": "Not found: Board not found"}),
+)]
+pub struct Body {
+    error: Kind,
+    message: String,
+}
+
 impl error::Error for Error {}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Error::Conflict(ref msg) => write!(f, "Conflict: {msg}"),
             Error::Forbidden(ref msg) => write!(f, "Forbidden: {msg}"),
+            Error::Gone(ref msg) => write!(f, "Gone: {msg}"),
             Error::NotFound(ref msg) => write!(f, "Not found: {msg}"),
             Error::BadRequest(ref msg) => write!(f, "Invalid input: {msg}"),
+            Error::PreconditionFailed(ref msg) => write!(f, "Precondition failed: {msg}"),
+            Error::Unauthorized(ref msg) => write!(f, "Unauthorized: {msg}"),
+            Error::Unprocessable(ref msg) => write!(f, "Unprocessable: {msg}"),
+            Error::TooManyRequests(retry_after) => {
+                write!(f, "Too many requests: retry after {retry_after} seconds")
+            }
             Error::Unhandled(ref msg) => write!(f, "Internal server error: {msg}"),
         }
     }
@@ -33,11 +80,26 @@ impl From<BoardError> for Error {
         match err {
             BoardError::BlockIndexOutOfBounds
             | BoardError::BlockInvalid
-            | BoardError::BlockPlacementInvalid => Error::BadRequest(err.to_string()),
+            | BoardError::BlockPlacementInvalid
+            | BoardError::BoardsIncompatible
+            | BoardError::DurationInvalid
+            | BoardError::EmptyCellsInvalid
+            | BoardError::LinkInvalid
+            | BoardError::MetadataInvalid
+            | BoardError::MoveIndexOutOfBounds
+            | BoardError::NotationInvalid
+            | BoardError::PuzzleMismatch
+            | BoardError::SolutionInvalid
+            | BoardError::TargetsInvalid => Error::BadRequest(err.to_string()),
             BoardError::BoardStateInvalid | BoardError::NoMovesToUndo => {
                 Error::Forbidden(err.to_string())
             }
-            BoardError::BoardNotFound => Error::NotFound(err.to_string()),
+            BoardError::BoardExpired => Error::Gone(err.to_string()),
+            BoardError::BoardNotFound | BoardError::LayoutNotFound => {
+                Error::NotFound(err.to_string())
+            }
+            BoardError::DifficultyNotFound => Error::Unprocessable(err.to_string()),
+            BoardError::VersionConflict => Error::Conflict(err.to_string()),
         }
     }
 }
@@ -57,10 +119,24 @@ impl From<BoardsRepositoryError> for Error {
     }
 }
 
+impl From<diesel::result::Error> for Error {
+    fn from(err: diesel::result::Error) -> Self {
+        tracing::error!("DieselError: {}", err);
+
+        match err {
+            diesel::result::Error::NotFound => Error::NotFound(err.to_string()),
+            _ => Error::Unhandled(err.to_string()),
+        }
+    }
+}
+
 impl From<HandlerError> for Error {
     fn from(err: HandlerError) -> Self {
         match err {
-            HandlerError::Body | HandlerError::Path | HandlerError::Query => {
+            HandlerError::Body
+            | HandlerError::Field(_)
+            | HandlerError::Path
+            | HandlerError::Query => {
                 tracing::error!("HandlerError: {}", err);
                 Error::BadRequest(err.to_string())
             }
@@ -68,15 +144,50 @@ impl From<HandlerError> for Error {
     }
 }
 
+impl Error {
+    fn kind(&self) -> Kind {
+        match self {
+            Error::Conflict(_) => Kind::Conflict,
+            Error::Forbidden(_) => Kind::Forbidden,
+            Error::Gone(_) => Kind::Gone,
+            Error::NotFound(_) => Kind::NotFound,
+            Error::BadRequest(_) => Kind::BadRequest,
+            Error::PreconditionFailed(_) => Kind::PreconditionFailed,
+            Error::Unauthorized(_) => Kind::Unauthorized,
+            Error::Unprocessable(_) => Kind::Unprocessable,
+            Error::TooManyRequests(_) => Kind::TooManyRequests,
+            Error::Unhandled(_) => Kind::Unhandled,
+        }
+    }
+}
+
 impl IntoResponse for Error {
     fn into_response(self) -> Response {
+        let body = Json(Body {
+            error: self.kind(),
+            message: self.to_string(),
+        });
+
         let status = match self {
+            Error::Conflict(_) => StatusCode::CONFLICT,
             Error::Forbidden(_) => StatusCode::FORBIDDEN,
+            Error::Gone(_) => StatusCode::GONE,
             Error::NotFound(_) => StatusCode::NOT_FOUND,
             Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Error::TooManyRequests(retry_after) => {
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(RETRY_AFTER, retry_after.to_string())],
+                    body,
+                )
+                    .into_response();
+            }
             Error::Unhandled(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
-        (status, self.to_string()).into_response()
+        (status, body).into_response()
     }
 }
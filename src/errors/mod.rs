@@ -1,3 +1,4 @@
-pub mod board;
+pub use klotski_core::errors::{board, solver};
+
 pub mod handler;
 pub mod http;
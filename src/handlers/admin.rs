@@ -0,0 +1,161 @@
+use axum::{
+    debug_handler,
+    extract::{Json, Path},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use std::time::Instant;
+
+use crate::docs;
+use crate::errors::{
+    handler::Error as HandlerError, http::Error as HttpError, solver::Error as SolverError,
+};
+use crate::models::api::{request, response};
+use crate::models::game::board::Board;
+use crate::repositories::boards::{get as get_board, insert as insert_board};
+use crate::services::{db::Pool as DbPool, hardest, solver};
+
+#[utoipa::path(
+    post,
+    tag = "Admin Operations",
+    operation_id = "find_hardest_boards",
+    path = "/admin/hardest",
+    request_body(content = FindHardest),
+    responses(
+        (status = OK, description = "Success", body = HardestBoards),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = UNAUTHORIZED, response = docs::MissingApiKey),
+        (status = FORBIDDEN, description = "Action not allowed, or API key is not an admin key", body = ErrorResponse,
+            example = json!({"error": "forbidden", "message": "Forbidden: API key is not an admin key"})),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn find_hardest(
+    Extension(pool): Extension<DbPool>,
+    json_extraction: Option<Json<request::FindHardest>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to find hardest boards");
+
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    let search = tokio::task::spawn_blocking(move || {
+        hardest::search(
+            &body.blocks,
+            body.goal.unwrap_or_default(),
+            body.max_boards.unwrap_or(hardest::DEFAULT_MAX_BOARDS),
+            &body.cancellation(),
+        )
+    })
+    .await
+    .map_err(|err| HttpError::Unhandled(err.to_string()))?;
+
+    let hardest = match search {
+        Ok(hardest) => hardest,
+        Err(SolverError::BoardError(err)) => return Err(err.into()),
+        Err(err) => return Err(HttpError::Unhandled(err.to_string())),
+    };
+
+    tracing::info!(
+        "Found {} boards needing {} moves to be solved",
+        hardest.boards.len(),
+        hardest.distance
+    );
+
+    let mut boards = vec![];
+
+    for board in &hardest.boards {
+        let board = insert_board(board, &pool)?;
+
+        tracing::info!("Board {} successfully persisted", board);
+
+        boards.push(response::Board::new(board));
+    }
+
+    Ok(response::HardestBoards::new(hardest.distance, boards).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Admin Operations",
+    operation_id = "compare_algorithms",
+    path = "/admin/board/{board_id}/compare",
+    params(request::BoardParams),
+    request_body(content = CompareAlgorithms),
+    responses(
+        (status = OK, description = "Success", body = AlgorithmComparison),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = UNAUTHORIZED, response = docs::MissingApiKey),
+        (status = FORBIDDEN, description = "Action not allowed, or API key is not an admin key", body = ErrorResponse,
+            example = json!({"error": "forbidden", "message": "Forbidden: API key is not an admin key"})),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn compare_algorithms(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    json_extraction: Option<Json<request::CompareAlgorithms>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to compare solver algorithms");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    if body.algorithms().len() < 2 {
+        return Err(HandlerError::Body.into());
+    }
+
+    let board = get_board(params.board_id, &pool)?;
+
+    let runs = tokio::task::spawn_blocking(move || run_algorithms(&board, &body))
+        .await
+        .map_err(|err| HttpError::Unhandled(err.to_string()))??;
+
+    Ok(response::AlgorithmComparison::new(params.board_id, runs).into_response())
+}
+
+// Solve the board with each of the algorithms in turn, bypassing the solution
+// cache so that every algorithm does the full search. Runs which stop early,
+// e.g. because they time out, are reported with the error rather than failing
+// the whole comparison.
+fn run_algorithms(
+    board: &Board,
+    body: &request::CompareAlgorithms,
+) -> Result<Vec<response::AlgorithmRun>, HttpError> {
+    let mut runs = vec![];
+
+    for algorithm in body.algorithms() {
+        let start = Instant::now();
+
+        let result = solver::solve_with_stats(board, &body.options(algorithm));
+
+        let elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        tracing::info!("Algorithm {:?} finished in {}ms", algorithm, elapsed_ms);
+
+        runs.push(match result {
+            Ok(outcome) => response::AlgorithmRun::new(
+                algorithm,
+                elapsed_ms,
+                Some(outcome.nodes_expanded),
+                outcome.moves.map(|moves| moves.len()),
+                None,
+            ),
+            Err(SolverError::BoardError(err)) => return Err(err.into()),
+            Err(err) => response::AlgorithmRun::new(
+                algorithm,
+                elapsed_ms,
+                None,
+                None,
+                Some(err.to_string()),
+            ),
+        });
+    }
+
+    Ok(runs)
+}
@@ -0,0 +1,59 @@
+use axum::{extract::Request, middleware::Next, response::Response, Extension};
+
+use crate::errors::http::Error as HttpError;
+use crate::models::game::utils::unix_millis;
+use crate::repositories::api_keys::find as find_api_key;
+use crate::services::api_keys::{self, ApiKey, Policy, Usage};
+use crate::services::db::Pool as DbPool;
+
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+// Check the API key given in the request, if any, by its hash, counting the
+// request towards the key's usage and passing the key on to the handlers
+// through the request's extensions. Requests without a key are rejected only
+// when keys are required.
+pub async fn authenticate(
+    Extension(pool): Extension<DbPool>,
+    Extension(policy): Extension<Policy>,
+    Extension(usage): Extension<Usage>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, HttpError> {
+    let Some(header) = request.headers().get(API_KEY_HEADER) else {
+        if policy.required {
+            return Err(HttpError::Unauthorized(format!(
+                "{API_KEY_HEADER} header is required"
+            )));
+        }
+
+        return Ok(next.run(request).await);
+    };
+
+    let invalid = || HttpError::Unauthorized("API key is invalid".to_string());
+
+    let key = header.to_str().map_err(|_| invalid())?;
+
+    let api_key = find_api_key(&api_keys::hash(key), &pool)?.ok_or_else(invalid)?;
+
+    usage.record(api_key.id, unix_millis());
+
+    tracing::info!("Authenticated request with API key {}", api_key.id);
+
+    request.extensions_mut().insert(api_key);
+
+    Ok(next.run(request).await)
+}
+
+// Reject requests which were not authenticated with an admin API key, for the
+// routes of the admin operations
+pub async fn require_admin(request: Request, next: Next) -> Result<Response, HttpError> {
+    match request.extensions().get::<ApiKey>() {
+        Some(api_key) if api_key.admin => Ok(next.run(request).await),
+        Some(_) => Err(HttpError::Forbidden(
+            "API key is not an admin key".to_string(),
+        )),
+        None => Err(HttpError::Unauthorized(format!(
+            "{API_KEY_HEADER} header is required"
+        ))),
+    }
+}
@@ -1,44 +1,58 @@
 use axum::{
     debug_handler,
-    extract::{Json, Path},
+    extract::{Json, Path, Query},
+    http::HeaderMap,
     response::{IntoResponse, Response},
     Extension,
 };
 
+use crate::docs;
 use crate::errors::{
     board::Error as BoardError, handler::Error as HandlerError, http::Error as HttpError,
 };
+use crate::handlers::preconditions::Precondition;
 use crate::models::{
     api::{request, response},
-    game::blocks::Positioned as PositionedBlock,
+    game::{board::Board, history::Action as EditAction, utils::unix_millis},
 };
-use crate::repositories::boards::update as update_board;
+use crate::repositories::boards::{update as update_board, Error as BoardsRepositoryError};
 use crate::services::db::Pool as DbPool;
 
+// Update making the edit to the board's blocks, which the board records in
+// its edit history
+fn edit_blocks(action: EditAction) -> impl FnOnce(&mut Board) -> Result<(), BoardError> {
+    move |board| board.edit_blocks(action, unix_millis())
+}
+
 #[utoipa::path(
     post,
     tag = "Block Operations",
     operation_id = "add_block",
     path = "/board/{board_id}/block",
-    params(request::BoardParams),
+    params(request::BoardParams, request::VersionParams, request::BoardFieldsParams),
     request_body(content = AddBlock),
     responses(
         (status = OK, description = "Success", body = Board),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = FORBIDDEN, description = "Action not allowed"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
 pub async fn add(
     Extension(pool): Extension<DbPool>,
     path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    fields: response::BoardFields,
     json_extraction: Option<Json<request::AddBlock>>,
 ) -> Result<Response, HttpError> {
     tracing::info!("Handling request to add block to board");
 
     let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
     let body = json_extraction.ok_or(HandlerError::Body)?.0;
 
     tracing::info!(
@@ -47,10 +61,14 @@ pub async fn add(
         params.board_id
     );
 
-    let new_block = PositionedBlock::new(body.block, body.min_row, body.min_col)
-        .ok_or(BoardError::BlockInvalid)?;
+    let new_block = body.positioned_block().ok_or(BoardError::BlockInvalid)?;
 
-    let board = update_board(params.board_id, |board| board.add_block(new_block), &pool)?;
+    let board = update_board(
+        params.board_id,
+        query.version,
+        edit_blocks(EditAction::AddBlock(new_block)),
+        &pool,
+    )?;
 
     tracing::info!(
         "Successfully added {:?} block to board with id {}",
@@ -58,36 +76,84 @@ pub async fn add(
         params.board_id
     );
 
-    Ok(response::Board::new(board).into_response())
+    Ok(response::Board::with_fields(board, &fields).into_response())
 }
 
 #[utoipa::path(
-    put,
+    post,
     tag = "Block Operations",
-    operation_id = "alter_block",
-    path = "/board/{board_id}/block/{block_idx}",
-    params(request::BlockParams),
-    request_body(content = AlterBlock),
+    operation_id = "add_blocks",
+    path = "/board/{board_id}/blocks",
+    params(request::BoardParams, request::VersionParams, request::BoardFieldsParams),
+    request_body(content = AddBlocks),
     responses(
         (status = OK, description = "Success", body = Board),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = FORBIDDEN, description = "Action not allowed"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
-pub async fn alter(
+pub async fn add_many(
     Extension(pool): Extension<DbPool>,
-    path_extraction: Option<Path<request::BlockParams>>,
-    json_extraction: Option<Json<request::AlterBlock>>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    fields: response::BoardFields,
+    json_extraction: Option<Json<request::AddBlocks>>,
 ) -> Result<Response, HttpError> {
-    tracing::info!("Handling request to alter block in board");
+    tracing::info!("Handling request to add blocks to board");
 
     let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
     let body = json_extraction.ok_or(HandlerError::Body)?.0;
 
-    let board = match body {
+    tracing::info!(
+        "Attempting to add {} blocks to board with id {}",
+        body.blocks.len(),
+        params.board_id
+    );
+
+    let new_blocks: Vec<_> = body
+        .blocks
+        .iter()
+        .map(|block| block.positioned_block().ok_or(BoardError::BlockInvalid))
+        .collect::<Result<_, _>>()?;
+
+    let board = update_board(
+        params.board_id,
+        query.version,
+        |board| {
+            let at = unix_millis();
+
+            for new_block in new_blocks {
+                board.edit_blocks(EditAction::AddBlock(new_block), at)?;
+            }
+
+            Ok(())
+        },
+        &pool,
+    )?;
+
+    tracing::info!(
+        "Successfully added {} blocks to board with id {}",
+        body.blocks.len(),
+        params.board_id
+    );
+
+    Ok(response::Board::with_fields(board, &fields).into_response())
+}
+
+// Apply the alteration to the block, against the given version of the board
+fn alter_block(
+    params: &request::BlockParams,
+    version: Option<u32>,
+    alteration: request::AlterBlock,
+    pool: &DbPool,
+) -> Result<Board, BoardsRepositoryError> {
+    match alteration {
         request::AlterBlock::ChangeBlock(data) => {
             tracing::info!(
                 "Changing block at index {} in board with id {} to {:?}",
@@ -98,8 +164,12 @@ pub async fn alter(
 
             update_board(
                 params.board_id,
-                |board| board.change_block(params.block_idx, data.new_block),
-                &pool,
+                version,
+                edit_blocks(EditAction::ChangeBlock {
+                    block_idx: params.block_idx,
+                    block: data.new_block,
+                }),
+                pool,
             )
         }
         request::AlterBlock::MoveBlock(data) => {
@@ -113,18 +183,121 @@ pub async fn alter(
 
             update_board(
                 params.board_id,
+                version,
                 |board| board.move_block(params.block_idx, data.row_diff, data.col_diff),
-                &pool,
+                pool,
             )
         }
-    }?;
+        request::AlterBlock::MoveBlockSteps(data) => {
+            tracing::info!(
+                "Moving block at index {} in board with id {} by steps {:?}",
+                params.block_idx,
+                params.board_id,
+                data.steps
+            );
+
+            update_board(
+                params.board_id,
+                version,
+                |board| board.move_block_steps(params.block_idx, &data.steps),
+                pool,
+            )
+        }
+        request::AlterBlock::RotateBlock => {
+            tracing::info!(
+                "Rotating block at index {} in board with id {}",
+                params.block_idx,
+                params.board_id
+            );
+
+            update_board(
+                params.board_id,
+                version,
+                edit_blocks(EditAction::RotateBlock {
+                    block_idx: params.block_idx,
+                }),
+                pool,
+            )
+        }
+        request::AlterBlock::SetLocked(data) => {
+            tracing::info!(
+                "Setting block at index {} in board with id {} to locked: {}",
+                params.block_idx,
+                params.board_id,
+                data.locked
+            );
+
+            update_board(
+                params.board_id,
+                version,
+                edit_blocks(EditAction::SetLocked {
+                    block_idx: params.block_idx,
+                    locked: data.locked,
+                }),
+                pool,
+            )
+        }
+        request::AlterBlock::LinkBlocks(data) => {
+            tracing::info!(
+                "Linking block at index {} in board with id {} to blocks {:?}",
+                params.block_idx,
+                params.board_id,
+                data.block_idxs
+            );
+
+            update_board(
+                params.board_id,
+                version,
+                edit_blocks(data.action(params.block_idx)),
+                pool,
+            )
+        }
+    }
+}
+
+#[utoipa::path(
+    put,
+    tag = "Block Operations",
+    operation_id = "alter_block",
+    path = "/board/{board_id}/block/{block_idx}",
+    params(request::BlockParams, request::VersionParams, request::BoardFieldsParams),
+    request_body(content = AlterBlock),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = PRECONDITION_FAILED, response = docs::ETagMismatch),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn alter(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BlockParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    fields: response::BoardFields,
+    headers: HeaderMap,
+    json_extraction: Option<Json<request::AlterBlock>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to alter block in board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let precondition = Precondition::new(&headers, query.version)?;
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    let board = alter_block(&params, precondition.version(), body, &pool)
+        .map_err(|err| precondition.error(err))?;
 
     tracing::info!(
         "Successfully altered block in board with id {}",
         params.board_id
     );
 
-    Ok(response::Board::new(board).into_response())
+    Ok(response::Board::with_fields(board, &fields).into_response())
 }
 
 #[utoipa::path(
@@ -133,24 +306,34 @@ pub async fn alter(
     operation_id = "remove_block",
     path = "/board/{board_id}/block/{block_idx}",
     params(
-        ("value" = request::BlockParams, Query,)
+        ("value" = request::BlockParams, Query,),
+        request::VersionParams,
+        request::BoardFieldsParams,
     ),
     responses(
         (status = OK, description = "Success", body = Board),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = FORBIDDEN, description = "Action not allowed"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = PRECONDITION_FAILED, response = docs::ETagMismatch),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
 pub async fn remove(
     Extension(pool): Extension<DbPool>,
     path_extraction: Option<Path<request::BlockParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    fields: response::BoardFields,
+    headers: HeaderMap,
 ) -> Result<Response, HttpError> {
     tracing::info!("Handling request to remove block from board");
 
     let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let precondition = Precondition::new(&headers, query.version)?;
 
     tracing::info!(
         "Attempting to remove block at index {} from board with id {}",
@@ -160,9 +343,13 @@ pub async fn remove(
 
     let board = update_board(
         params.board_id,
-        |board| board.remove_block(params.block_idx),
+        precondition.version(),
+        edit_blocks(EditAction::RemoveBlock {
+            block_idx: params.block_idx,
+        }),
         &pool,
-    )?;
+    )
+    .map_err(|err| precondition.error(err))?;
 
     tracing::info!(
         "Successfully removed block at index {} from board with id {}",
@@ -170,5 +357,5 @@ pub async fn remove(
         params.board_id
     );
 
-    Ok(response::Board::new(board).into_response())
+    Ok(response::Board::with_fields(board, &fields).into_response())
 }
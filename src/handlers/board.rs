@@ -1,155 +1,577 @@
 use axum::{
+    body::Bytes,
     debug_handler,
     extract::{Json, Path, Query},
-    response::{IntoResponse, Response},
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Extension,
 };
+use futures_util::stream;
+use std::{convert::Infallible, sync::Arc};
+use tokio::sync::mpsc;
 
-use crate::errors::{handler::Error as HandlerError, http::Error as HttpError};
+use crate::docs;
+use crate::errors::{
+    board::Error as BoardError, handler::Error as HandlerError, http::Error as HttpError,
+    solver::Error as SolverError,
+};
+use crate::handlers::preconditions::Precondition;
 use crate::models::{
     api::{request, response},
-    game::{board::Board, moves::FlatBoardMove},
+    game::{board::Board, layouts, moves::FlatBoardMove, notation, validation},
 };
 use crate::repositories::boards::{
-    create as create_board, delete as delete_board, get as get_board, update as update_board,
+    delete as delete_board, delete_all as delete_boards, delete_matching as delete_matching_boards,
+    get as get_board, insert as insert_board, keep_alive as keep_board_alive, list as list_boards,
+    list_summaries, search as search_boards, search_matching as search_matching_boards,
+    update as update_board, Error as BoardsRepositoryError, Filters as BoardFilters,
 };
+use crate::repositories::jobs::create as create_job;
 use crate::repositories::solutions::{create as create_solution, get as get_solution};
-use crate::services::{db::Pool as DbPool, randomizer, solver};
+use crate::services::{
+    api_keys::ApiKey, db::Pool as DbPool, distances, expiry, explorer, jobs, randomizer, solver,
+};
+
+const DEFAULT_LIST_LIMIT: u32 = 50;
+const MAX_LIST_LIMIT: u32 = 100;
 
 #[utoipa::path(
     post,
     tag = "Board Operations",
     operation_id = "create_board",
     path = "/board",
-    params(request::RandomizeParams),
+    params(request::RandomizeParams, request::BoardFieldsParams),
+    request_body(content = NewBoard),
     responses(
         (status = OK, description = "Success", body = Board),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = FORBIDDEN, description = "Action not allowed"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, description = "Layout not found", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: No layout with matching name"})),
+        (status = GONE, response = docs::BoardExpired),
+        (status = UNPROCESSABLE_ENTITY, description = "No board of the requested difficulty found", body = ErrorResponse,
+            example = json!({"error": "unprocessable", "message": "Unprocessable: No random board of the requested difficulty was found"})),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
 pub async fn new(
     Extension(pool): Extension<DbPool>,
     query_extraction: Option<Query<request::RandomizeParams>>,
+    fields: response::BoardFields,
+    body: Bytes,
 ) -> Result<Response, HttpError> {
     tracing::info!("Handling request to create a new board");
 
     let params = query_extraction.ok_or(HandlerError::Query)?.0;
 
-    let mut board = create_board(&pool)?;
+    // the body is optional, so it is only parsed if given, and rejected if it
+    // is given but invalid
+    let body: request::NewBoard = if body.is_empty() {
+        request::NewBoard::default()
+    } else {
+        serde_json::from_slice(&body).map_err(|_| HandlerError::Body)?
+    };
+
+    let metadata = body.metadata;
 
-    tracing::info!("Empty board {} successfully created", board);
+    let mut new_board = match (
+        body.blocks,
+        body.notation,
+        body.name,
+        body.goal,
+        body.variant,
+    ) {
+        (Some(blocks), None, None, goal, variant) => {
+            let blocks = blocks
+                .iter()
+                .map(|block| block.positioned_block().ok_or(BoardError::BlockInvalid))
+                .collect::<Result<_, _>>()?;
 
-    if params.randomize.unwrap_or(false) {
-        let randomized_board = update_board(board.id, randomizer::randomize, &pool)?;
+            let mut new_board =
+                Board::try_from_variant_blocks(variant.unwrap_or_default(), blocks)?;
+            new_board.set_goal(goal.unwrap_or_default())?;
+
+            new_board
+        }
+        (None, Some(notation), None, None, None) => notation::parse(&notation)?,
+        (None, None, Some(name), None, None) => layouts::find(&name)
+            .ok_or(BoardError::LayoutNotFound)?
+            .board(),
+        (None, None, None, goal, variant) => {
+            let mut new_board = Board::from_variant(variant.unwrap_or_default());
+            new_board.set_goal(goal.unwrap_or_default())?;
+
+            new_board
+        }
+        _ => return Err(HandlerError::Body.into()),
+    };
 
-        tracing::info!("Board {} successfully randomized", board.id);
+    if let Some(metadata) = metadata {
+        new_board.set_metadata(metadata.name, metadata.description, metadata.tags)?;
+    }
 
-        board = randomized_board;
+    if params.randomize.unwrap_or(false) {
+        randomize_board(&mut new_board, &params).await?;
     }
 
-    Ok(response::Board::new(board).into_response())
+    let board = insert_board(&new_board, &pool)?;
+
+    tracing::info!("Board {} successfully created", board);
+
+    Ok(response::Board::with_fields(board, &fields).into_response())
+}
+
+// Randomize the new board as requested before it is stored, on tokio's
+// blocking thread pool since placing blocks, shuffling them, or solving
+// candidates to find boards of a difficulty can all take a while
+async fn randomize_board(
+    board: &mut Board,
+    params: &request::RandomizeParams,
+) -> Result<(), HttpError> {
+    let seed = params.seed.unwrap_or_else(randomizer::random_seed);
+    let shuffle_moves = params.shuffle_moves()?;
+
+    let randomize = move |board: &mut Board, seed| match shuffle_moves {
+        Some(num_moves) => randomizer::shuffle(board, num_moves, seed),
+        None => randomizer::randomize(board, seed),
+    };
+
+    let optimal_moves = params.optimal_moves()?;
+    let mut candidate = board.clone();
+
+    *board = tokio::task::spawn_blocking(move || {
+        match optimal_moves {
+            Some(optimal_moves) => {
+                randomizer::randomize_within(&mut candidate, &optimal_moves, seed, randomize)
+            }
+            None => randomize(&mut candidate, seed),
+        }
+        .map(|()| candidate)
+    })
+    .await
+    .map_err(|err| HttpError::Unhandled(err.to_string()))??;
+
+    tracing::info!(
+        "New board successfully randomized with seed {}",
+        board.seed.unwrap_or(seed)
+    );
+
+    Ok(())
 }
 
 #[utoipa::path(
-    put,
+    post,
     tag = "Board Operations",
-    operation_id = "alter_board",
-    path = "/board/{board_id}",
-    params(request::BoardParams),
-    request_body(content = AlterBoard),
+    operation_id = "import_board",
+    path = "/board/import",
+    params(request::BoardFieldsParams),
+    request_body(content = ImportBoard),
     responses(
         (status = OK, description = "Success", body = Board),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = FORBIDDEN, description = "Action not allowed"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
-pub async fn alter(
+pub async fn import(
     Extension(pool): Extension<DbPool>,
-    path_extraction: Option<Path<request::BoardParams>>,
-    json_extraction: Option<Json<request::AlterBoard>>,
+    fields: response::BoardFields,
+    json_extraction: Option<Json<request::ImportBoard>>,
 ) -> Result<Response, HttpError> {
-    tracing::info!("Handling request to alter board");
+    tracing::info!("Handling request to import board");
+
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    let board = insert_board(&body.board()?, &pool)?;
+
+    tracing::info!("Board {} successfully imported", board);
+
+    Ok(response::Board::with_fields(board, &fields).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "validate_board",
+    path = "/board/validate",
+    request_body(content = ValidateBoard),
+    responses(
+        (status = OK, description = "Success", body = Validation),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn validate(
+    json_extraction: Option<Json<request::ValidateBoard>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to validate board");
 
-    let params = path_extraction.ok_or(HandlerError::Path)?.0;
     let body = json_extraction.ok_or(HandlerError::Body)?.0;
 
-    let board = match body {
+    let blocks: Vec<_> = body
+        .blocks
+        .iter()
+        .map(request::AddBlock::positioned_block)
+        .collect();
+
+    let report = validation::validate(&blocks, body.goal.unwrap_or_default());
+
+    tracing::info!("Found {} issues with board", report.issues.len());
+
+    Ok(response::Validation::new(report).into_response())
+}
+
+// Apply the alteration to the board, against the given version of the board
+fn alter_board(
+    board_id: i32,
+    version: Option<u32>,
+    alteration: request::AlterBoard,
+    pool: &DbPool,
+) -> Result<Board, BoardsRepositoryError> {
+    match alteration {
+        request::AlterBoard::AddWall(data) => {
+            tracing::info!(
+                "Adding wall at ({},{}) to board {}",
+                data.row,
+                data.col,
+                board_id
+            );
+
+            update_board(
+                board_id,
+                version,
+                |board| board.add_wall(data.row, data.col),
+                pool,
+            )
+        }
+        request::AlterBoard::Archive => {
+            tracing::info!("Archiving board with id {}", board_id);
+
+            update_board(board_id, version, Board::archive, pool)
+        }
         request::AlterBoard::ChangeState(data) => {
             tracing::info!(
                 "Changing state of board {} to {:?}",
-                params.board_id,
+                board_id,
                 data.new_state
             );
 
             update_board(
-                params.board_id,
+                board_id,
+                version,
                 |board| board.change_state(data.new_state),
-                &pool,
+                pool,
+            )
+        }
+        request::AlterBoard::RemoveWall(data) => {
+            tracing::info!(
+                "Removing wall at ({},{}) from board {}",
+                data.row,
+                data.col,
+                board_id
+            );
+
+            update_board(
+                board_id,
+                version,
+                |board| board.remove_wall(data.row, data.col),
+                pool,
+            )
+        }
+        request::AlterBoard::SetEmptyCells(data) => {
+            tracing::info!(
+                "Setting number of empty cells of board {} to {}",
+                board_id,
+                data.empty_cells
+            );
+
+            update_board(
+                board_id,
+                version,
+                |board| board.set_empty_cells(data.empty_cells),
+                pool,
+            )
+        }
+        request::AlterBoard::SetMetadata(data) => {
+            tracing::info!("Setting metadata of board {}", board_id);
+
+            update_board(
+                board_id,
+                version,
+                |board| board.set_metadata(data.name, data.description, data.tags),
+                pool,
+            )
+        }
+        request::AlterBoard::SetTargets(data) => {
+            tracing::info!(
+                "Setting {} targets of board {}",
+                data.targets.len(),
+                board_id
+            );
+
+            update_board(
+                board_id,
+                version,
+                |board| board.set_targets(data.targets),
+                pool,
             )
         }
         request::AlterBoard::UndoMove => {
-            tracing::info!("Undoing last move for board with id {}", params.board_id);
+            tracing::info!("Undoing last move for board with id {}", board_id);
 
-            update_board(params.board_id, Board::undo_move, &pool)
+            update_board(board_id, version, Board::undo_move, pool)
         }
         request::AlterBoard::Reset => {
-            tracing::info!("Resetting board with id {}", params.board_id);
+            tracing::info!("Resetting board with id {}", board_id);
 
-            update_board(params.board_id, Board::reset, &pool)
+            update_board(board_id, version, Board::reset, pool)
         }
-    }?;
+    }
+}
+
+#[utoipa::path(
+    put,
+    tag = "Board Operations",
+    operation_id = "alter_board",
+    path = "/board/{board_id}",
+    params(request::BoardParams, request::VersionParams, request::BoardFieldsParams),
+    request_body(content = AlterBoard),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = PRECONDITION_FAILED, response = docs::ETagMismatch),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn alter(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    fields: response::BoardFields,
+    headers: HeaderMap,
+    json_extraction: Option<Json<request::AlterBoard>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to alter board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let precondition = Precondition::new(&headers, query.version)?;
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    let board = alter_board(params.board_id, precondition.version(), body, &pool)
+        .map_err(|err| precondition.error(err))?;
 
     tracing::info!("Successfully altered board with id {}", params.board_id);
 
-    Ok(response::Board::new(board).into_response())
+    Ok(response::Board::with_fields(board, &fields).into_response())
 }
 
 #[utoipa::path(
-    post,
+    put,
     tag = "Board Operations",
-    operation_id = "solve_board",
-    path = "/board/{board_id}/solve",
-    params(request::BoardParams),
+    operation_id = "apply_moves",
+    path = "/board/{board_id}/moves",
+    params(request::BoardParams, request::VersionParams, request::BoardFieldsParams),
+    request_body(content = ApplyMoves),
     responses(
-        (status = OK, description = "Success", body = Solve),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = FORBIDDEN, description = "Action not allowed"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = PRECONDITION_FAILED, response = docs::ETagMismatch),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
-pub async fn solve(
+pub async fn apply_moves(
     Extension(pool): Extension<DbPool>,
     path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    fields: response::BoardFields,
+    headers: HeaderMap,
+    json_extraction: Option<Json<request::ApplyMoves>>,
 ) -> Result<Response, HttpError> {
-    tracing::info!("Handling request to solve board");
+    tracing::info!("Handling request to apply moves to board");
 
     let params = path_extraction.ok_or(HandlerError::Path)?.0;
-    let board = get_board(params.board_id, &pool)?;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let precondition = Precondition::new(&headers, query.version)?;
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    let board = update_board(
+        params.board_id,
+        precondition.version(),
+        |board| board.apply_moves(&body.moves),
+        &pool,
+    )
+    .map_err(|err| precondition.error(err))?;
+
+    tracing::info!(
+        "Successfully applied {} moves to board with id {}",
+        body.moves.len(),
+        params.board_id
+    );
+
+    Ok(response::Board::with_fields(board, &fields).into_response())
+}
+
+// Find a solution for a board which is one move away from a board with a
+// cached solution, by searching for a way back onto the cached solution. Since
+// the cached solution is optimal and moves can be undone, the board needs at
+// least one move fewer than the cached solution, so the solution found is only
+// returned when it reaches that bound and is known to be optimal.
+fn find_rejoined_solution(
+    board: &Board,
+    options: &solver::Options,
+    pool: &DbPool,
+) -> Option<Vec<FlatBoardMove>> {
+    if board.moves.is_empty() {
+        return None;
+    }
 
+    let mut previous_board = board.clone();
+    previous_board.undo_move_unchecked();
+
+    let solution = get_solution(&previous_board, pool).ok()??;
+
+    let moves = solver::rejoin(
+        board,
+        &previous_board,
+        &solution,
+        solver::MAX_REJOIN_DEPTH,
+        &options.cancellation,
+    )
+    .ok()??;
+
+    (moves.len() + 1 == solution.len()).then_some(moves)
+}
+
+// Count the distinct optimal solutions of the board, or None if counting them
+// stops early, e.g. because it timed out
+fn count_solutions(board: &Board, options: &solver::Options) -> Result<Option<u64>, HttpError> {
+    match solver::count_solutions(board, options) {
+        Ok(count) => Ok(Some(count)),
+        Err(
+            SolverError::BudgetExceeded
+            | SolverError::Cancelled
+            | SolverError::DepthExceeded
+            | SolverError::TimedOut,
+        ) => {
+            tracing::info!("Timed out while counting solutions for board {}", board);
+
+            Ok(None)
+        }
+        Err(SolverError::BoardError(err)) => Err(err.into()),
+    }
+}
+
+// Find the solution for the board, using the cached solution or the distance
+// table if either has the board, or rejoining the cached solution of the board
+// before the last move if allowed and the rejoined solution is optimal, and
+// caching the solution otherwise. Only solutions minimizing the number of
+// moves are cached or looked up, and solutions which are not guaranteed to be
+// optimal are never cached, nor are solutions of boards with constrained
+// blocks or targets. The first optimal solution found from the board's
+// starting position is used to record its difficulty.
+pub(super) fn find_solution(
+    board: &Board,
+    options: &solver::Options,
+    allow_rejoin: bool,
+    pool: &DbPool,
+) -> Result<response::Solution, HttpError> {
     let maybe_moves: Option<Vec<FlatBoardMove>>;
+    let mut optimal = true;
+    let mut truncated = false;
 
-    if let Ok(cached_solution) = get_solution(board.hash(), &pool) {
+    let use_cache = options.objective == solver::Objective::Moves
+        && options.max_depth.is_none()
+        && !board.has_constrained_blocks()
+        && board.targets.is_empty();
+
+    if let Some(cached_solution) = use_cache.then(|| get_solution(board, pool).ok()).flatten() {
         tracing::info!("Returning cached solution for board {}", board);
 
         maybe_moves = cached_solution;
-    } else {
+    } else if let Some(moves) = use_cache.then(|| distances::solve(board, pool)).flatten() {
+        tracing::info!("Returning solution from distance table for board {}", board);
+
+        maybe_moves = Some(moves);
+    } else if let Some(moves) = (use_cache && allow_rejoin)
+        .then(|| find_rejoined_solution(board, options, pool))
+        .flatten()
+    {
         tracing::info!(
-            "No cached solution found for board {}. Attempting to find solution",
+            "Returning solution rejoining cached solution for board {}",
             board
         );
 
-        maybe_moves = solver::solve(&board)?;
+        let _solution_cached = create_solution(board, Some(moves.clone()), pool).is_ok();
+
+        maybe_moves = Some(moves);
+    } else {
+        tracing::info!(
+            "No cached solution found for board {}. Attempting to find solution using {:?}",
+            board,
+            options.algorithm
+        );
+
+        let outcome = match solver::solve_with_stats(board, options) {
+            Ok(outcome) => outcome,
+            Err(SolverError::BudgetExceeded) => {
+                tracing::info!(
+                    "Node budget of {} exceeded while solving board {}",
+                    options.node_budget,
+                    board
+                );
+
+                return Ok(response::Solution::BudgetExceeded);
+            }
+            Err(SolverError::TimedOut) => {
+                tracing::info!("Timed out while solving board {}", board);
+
+                return Ok(response::Solution::TimedOut);
+            }
+            Err(SolverError::BoardError(err)) => return Err(err.into()),
+            // Once the maximum depth is exceeded, the moves to the closest board
+            // are returned as truncated rather than the DepthExceeded error, so
+            // the search can only have been cancelled
+            Err(err) => {
+                tracing::info!("Solving of board {} was cancelled", board);
+
+                return Err(HttpError::Unhandled(err.to_string()));
+            }
+        };
+
+        if outcome.truncated {
+            tracing::info!(
+                "Maximum depth of {:?} exceeded while solving board {}",
+                options.max_depth,
+                board
+            );
+        }
+
+        maybe_moves = outcome.moves;
+        optimal = options.is_optimal() && !outcome.truncated;
+        truncated = outcome.truncated;
 
-        let _solution_cached = create_solution(board.hash(), maybe_moves.clone(), &pool).is_ok();
+        if use_cache && optimal {
+            let _solution_cached = create_solution(board, maybe_moves.clone(), pool).is_ok();
+        }
     }
 
     let result = if let Some(moves) = maybe_moves {
@@ -159,41 +581,1015 @@ pub async fn solve(
             board
         );
 
-        response::Solution::Solved(response::Solved::new(moves))
+        jobs::record_difficulty(board, options, optimal, &moves, pool);
+
+        let count = if options.count_solutions && !truncated {
+            count_solutions(board, options)?
+        } else {
+            None
+        };
+
+        let num_raw_moves = moves.len();
+
+        response::Solution::Solved(response::Solved::new(
+            solver::compact(board, &moves),
+            num_raw_moves,
+            count,
+            optimal,
+            truncated,
+        ))
     } else {
         tracing::info!("There is no valid solution for board {}", board);
 
         response::Solution::UnableToSolve
     };
 
+    Ok(result)
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "solve_board",
+    path = "/board/{board_id}/solve",
+    params(
+        request::BoardParams,
+        request::SolveParams,
+        request::SolutionPageParams,
+        request::SolutionSnapshotsParams,
+    ),
+    responses(
+        (status = OK, description = "Success", body = Solve),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn solve(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::SolveParams>>,
+    page_query_extraction: Option<Query<request::SolutionPageParams>>,
+    snapshots_query_extraction: Option<Query<request::SolutionSnapshotsParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to solve board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let page_query = page_query_extraction.ok_or(HandlerError::Query)?.0;
+    let snapshots_query = snapshots_query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+    let start_board = snapshots_query
+        .snapshots
+        .unwrap_or(false)
+        .then(|| board.clone());
+
+    let options = query.options();
+
+    // Dropped along with the handler's future if the client disconnects before
+    // a solution is found, which stops the solver
+    let _cancel_on_drop = solver::CancelOnDrop(options.cancellation.clone());
+
+    let mut result =
+        tokio::task::spawn_blocking(move || find_solution(&board, &options, true, &pool))
+            .await
+            .map_err(|err| HttpError::Unhandled(err.to_string()))??;
+
+    if let Some(start_board) = start_board {
+        result = result.with_snapshots(&start_board, snapshots_query.snapshot_every.unwrap_or(1));
+    }
+
+    if page_query.offset.is_some() || page_query.limit.is_some() {
+        result = result.page(page_query.offset.unwrap_or(0), page_query.limit);
+    }
+
     Ok(result.into_response())
 }
 
 #[utoipa::path(
-    delete,
+    post,
     tag = "Board Operations",
-    operation_id = "delete_board",
-    path = "/board/{board_id}",
+    operation_id = "hint_board",
+    path = "/board/{board_id}/hint",
+    params(request::BoardParams, request::SolveParams, request::HintParams),
+    responses(
+        (status = OK, description = "Success", body = Hint),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn hint(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::SolveParams>>,
+    hint_query_extraction: Option<Query<request::HintParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request for board hint");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let hint_query = hint_query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let options = query.options();
+
+    // Dropped along with the handler's future if the client disconnects before
+    // a solution is found, which stops the solver
+    let _cancel_on_drop = solver::CancelOnDrop(options.cancellation.clone());
+
+    // Hints never rejoin the cached solution of the board before the last move,
+    // so that the next move suggested always comes from a full solution
+    let result = tokio::task::spawn_blocking(move || find_solution(&board, &options, false, &pool))
+        .await
+        .map_err(|err| HttpError::Unhandled(err.to_string()))??;
+
+    Ok(response::Hint::new(result, hint_query.count.unwrap_or(1)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "solve_board_stream",
+    path = "/board/{board_id}/solve/stream",
+    params(request::BoardParams, request::SolveParams),
+    responses(
+        (status = OK, description = "Stream of progress events followed by a solution event", content_type = "text/event-stream", body = SolveProgress),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn solve_stream(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::SolveParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to stream solving of board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let (sender, receiver) = mpsc::unbounded_channel::<Event>();
+
+    let progress_sender = sender.clone();
+
+    let options = solver::Options {
+        progress: Some(Arc::new(move |progress: solver::Progress| {
+            if let Ok(event) = Event::default().event("progress").json_data(progress) {
+                let _event_sent = progress_sender.send(event).is_ok();
+            }
+        })),
+        ..query.options()
+    };
+
+    // Kept alive by the stream, which is dropped along with the response body
+    // if the client disconnects before a solution is found, which stops the
+    // solver
+    let cancel_on_drop = solver::CancelOnDrop(options.cancellation.clone());
+
+    tokio::task::spawn_blocking(move || {
+        let event = match find_solution(&board, &options, true, &pool) {
+            Ok(result) => Event::default().event("solution").json_data(result),
+            Err(err) => Ok(Event::default().event("error").data(err.to_string())),
+        };
+
+        if let Ok(event) = event {
+            let _event_sent = sender.send(event).is_ok();
+        }
+    });
+
+    // The stream ends once the solver has finished and every sender is dropped
+    let stream = stream::unfold(
+        (receiver, cancel_on_drop),
+        |(mut receiver, cancel_on_drop)| async move {
+            let event = receiver.recv().await?;
+
+            Some((Ok::<_, Infallible>(event), (receiver, cancel_on_drop)))
+        },
+    );
+
+    Ok(Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "estimate_board",
+    path = "/board/{board_id}/estimate",
     params(request::BoardParams),
     responses(
-        (status = OK, description = "Success", body = Board),
-        (status = BAD_REQUEST, description = "Invalid parameters"),
-        (status = NOT_FOUND, description = "Board not found"),
-        (status = INTERNAL_SERVER_ERROR, description = "Unhandled exception"),
+        (status = OK, description = "Success", body = Estimate),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
     ),
 )]
 #[debug_handler]
-pub async fn delete(
+pub async fn estimate(
     Extension(pool): Extension<DbPool>,
     path_extraction: Option<Path<request::BoardParams>>,
 ) -> Result<Response, HttpError> {
-    tracing::info!("Handling request to delete board");
+    tracing::info!("Handling request to estimate board solution length");
 
     let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let board = get_board(params.board_id, &pool)?;
 
-    delete_board(params.board_id, &pool)?;
+    let min_moves = solver::lower_bound(&board);
 
-    tracing::info!("Successfully deleted board with id {}", params.board_id);
+    tracing::info!("Board {} needs at least {} moves", board, min_moves);
 
-    Ok(().into_response())
+    Ok(response::Estimate::new(min_moves).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "check_board_solvable",
+    path = "/board/{board_id}/check-solvable",
+    params(request::BoardParams),
+    responses(
+        (status = OK, description = "Success", body = Solvable),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn check_solvable(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to check whether board is solvable");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let cancellation = solver::Cancellation::with_timeout(solver::DEFAULT_TIMEOUT);
+
+    // Dropped along with the handler's future if the client disconnects before
+    // the check is done, which stops the search
+    let _cancel_on_drop = solver::CancelOnDrop(cancellation.clone());
+
+    let checked_board = board.clone();
+
+    let solvable = tokio::task::spawn_blocking(move || {
+        solver::is_solvable(&checked_board, solver::DEFAULT_NODE_BUDGET, &cancellation)
+    })
+    .await
+    .map_err(|err| HttpError::Unhandled(err.to_string()))?;
+
+    tracing::info!("Board {} is solvable: {:?}", board, solvable);
+
+    Ok(response::Solvable::new(solvable).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "solve_board_async",
+    path = "/board/{board_id}/solve/async",
+    params(request::BoardParams, request::SolveParams),
+    responses(
+        (status = OK, description = "Success", body = Job),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn solve_async(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::SolveParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to solve board asynchronously");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let job = create_job(&jobs::Job::new(board.id, query), &pool)?;
+
+    tracing::info!("Solve job {} created for board {}", job.id, board);
+
+    jobs::spawn(job.clone(), &pool);
+
+    Ok(response::Job::new(job).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "board_graph",
+    path = "/board/{board_id}/graph",
+    params(request::BoardParams, request::GraphParams),
+    responses(
+        (status = OK, description = "Success", body = Graph),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, response = docs::NotAllowed),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn graph(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::GraphParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to export state graph of board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let max_nodes = query.max_nodes()?;
+    let cancellation = query.cancellation();
+    let board = get_board(params.board_id, &pool)?;
+
+    // Dropped along with the handler's future if the client disconnects before
+    // the graph is explored, which stops the search
+    let _cancel_on_drop = solver::CancelOnDrop(cancellation.clone());
+
+    let explored_board = board.clone();
+
+    let graph = match tokio::task::spawn_blocking(move || {
+        explorer::explore(&explored_board, max_nodes, &cancellation)
+    })
+    .await
+    .map_err(|err| HttpError::Unhandled(err.to_string()))?
+    {
+        Ok(graph) => graph,
+        Err(SolverError::BoardError(err)) => return Err(err.into()),
+        Err(err) => return Err(HttpError::Unhandled(err.to_string())),
+    };
+
+    tracing::info!(
+        "Explored {} boards and {} moves reachable from board {}",
+        graph.nodes.len(),
+        graph.edges.len(),
+        board
+    );
+
+    Ok(response::Graph::new(graph).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "board_snapshot",
+    path = "/board/{board_id}/state",
+    params(request::BoardParams, request::SnapshotParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", content(
+            ("application/json" = Board),
+            ("text/plain" = String),
+        )),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn snapshot(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::SnapshotParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to read past state of board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let num_moves = query.at_move.unwrap_or(board.moves.len());
+    let snapshot = board.at_move(num_moves)?;
+
+    tracing::info!("Read board {} as of move {}", board, num_moves);
+
+    match query.format.unwrap_or_default() {
+        request::Format::Json => {
+            Ok(response::Board::with_fields(snapshot, &fields).into_response())
+        }
+        request::Format::Text => Ok(snapshot.render_text().into_response()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "export_board",
+    path = "/board/{board_id}/export",
+    params(request::BoardParams),
+    responses(
+        (status = OK, description = "Success", body = ExportedBoard),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn export(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to export board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    // solutions are only cached for boards without constrained blocks or
+    // targets, as when solving the board
+    let solution = (!board.has_constrained_blocks() && board.targets.is_empty())
+        .then(|| get_solution(&board, &pool).ok().flatten())
+        .flatten();
+
+    tracing::info!("Exported board {}", board);
+
+    Ok(response::ExportedBoard::new(board, solution).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "board_history",
+    path = "/board/{board_id}/history",
+    params(request::BoardParams),
+    responses(
+        (status = OK, description = "Success", body = History),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn history(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to read edit history of board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    tracing::info!("Read {} edits of board {}", board.edits.len(), board);
+
+    Ok(response::History::new(board.edits).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "board_moves",
+    path = "/board/{board_id}/moves",
+    params(request::BoardParams),
+    responses(
+        (status = OK, description = "Success", body = Moves),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn moves(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to read moves of board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    tracing::info!("Read {} moves of board {}", board.moves.len(), board);
+
+    Ok(response::Moves::new(board.moves).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "replay_board",
+    path = "/board/{board_id}/replay",
+    params(request::BoardParams, request::ReplayParams),
+    responses(
+        (status = OK, description = "Success", body = Replay),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn replay(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::ReplayParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to replay board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+
+    let board = get_board(params.board_id, &pool)?;
+    let start = board.at_move(0)?;
+
+    tracing::info!("Replaying {} moves of board {}", board.moves.len(), board);
+
+    Ok(response::Replay::new(&board, start, query.snapshots.unwrap_or(false)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "board_next_moves",
+    path = "/board/{board_id}/next-moves",
+    params(request::BoardParams, request::NextMovesParams),
+    responses(
+        (status = OK, description = "Success", body = NextMoves),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn next_moves(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::NextMovesParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to read next moves of board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    if query
+        .block_idx
+        .is_some_and(|block_idx| block_idx >= board.blocks.len())
+    {
+        return Err(BoardError::BlockIndexOutOfBounds.into());
+    }
+
+    tracing::info!("Reading next moves of board {}", board);
+
+    Ok(response::NextMoves::new(
+        board,
+        query.block_idx,
+        query.detailed_moves.unwrap_or(false),
+    )
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "board_diff",
+    path = "/board/{board_id}/diff",
+    params(request::BoardParams, request::DiffParams),
+    responses(
+        (status = OK, description = "Success", body = BoardDiff),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn diff(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::DiffParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to diff board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let from_board = match (query.from_board, query.from_move) {
+        (Some(_), Some(_)) => return Err(HandlerError::Query.into()),
+        (Some(other_id), None) => get_board(other_id, &pool)?,
+        (None, from_move) => board.at_move(from_move.unwrap_or(0))?,
+    };
+
+    let moves = from_board.diff(&board)?;
+
+    tracing::info!("{} blocks of board {} changed position", moves.len(), board);
+
+    Ok(response::BoardDiff::new(moves).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "clone_board",
+    path = "/board/{board_id}/clone",
+    params(request::BoardParams, request::CloneParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn clone(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::CloneParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to clone board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let new_board = insert_board(&board.fork(query.with_moves.unwrap_or(false)), &pool)?;
+
+    tracing::info!("Board {} successfully cloned as {}", board.id, new_board);
+
+    Ok(response::Board::with_fields(new_board, &fields).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "transform_board",
+    path = "/board/{board_id}/transform",
+    params(request::BoardParams, request::BoardFieldsParams),
+    request_body(content = TransformBoard),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn transform(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    fields: response::BoardFields,
+    json_extraction: Option<Json<request::TransformBoard>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to transform board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    let transformed_board = match body {
+        request::TransformBoard::MirrorHorizontal => board.mirrored_horizontal(),
+        request::TransformBoard::Rotate => board.rotated(),
+    };
+
+    let new_board = insert_board(&transformed_board, &pool)?;
+
+    tracing::info!(
+        "Board {} successfully transformed as {}",
+        board.id,
+        new_board
+    );
+
+    Ok(response::Board::with_fields(new_board, &fields).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "get_board",
+    path = "/board/{board_id}",
+    params(request::BoardParams, request::FormatParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", content(
+            ("application/json" = Board),
+            ("text/plain" = String),
+        )),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn get(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::FormatParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to get board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    tracing::info!("Successfully got board {}", board);
+
+    match query.format.unwrap_or_default() {
+        request::Format::Json => Ok(response::Board::with_fields(board, &fields).into_response()),
+        request::Format::Text => Ok(board.render_text().into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "Board Operations",
+    operation_id = "keep_board_alive",
+    path = "/board/{board_id}/keepalive",
+    params(request::BoardParams),
+    responses(
+        (status = OK, description = "Success", body = BoardActivity),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn keep_alive(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to keep board alive");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let last_active_at = keep_board_alive(params.board_id, &pool)?;
+
+    tracing::info!("Board {} successfully kept alive", params.board_id);
+
+    Ok(response::BoardActivity::new(
+        last_active_at,
+        expiry::expires_at(last_active_at, expiry::ttl()),
+    )
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "list_boards",
+    path = "/board",
+    params(request::ListBoardsParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", body = Boards),
+        (status = OK, description = "Success, if summary is set", body = BoardSummaries),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn list(
+    Extension(pool): Extension<DbPool>,
+    query_extraction: Option<Query<request::ListBoardsParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to list boards");
+
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+
+    let limit = query
+        .per_page
+        .or(query.limit)
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .min(MAX_LIST_LIMIT);
+    let offset = match query.page {
+        Some(page) => i64::from(page.saturating_sub(1)) * i64::from(limit),
+        None => i64::from(query.offset.unwrap_or(0)),
+    };
+
+    let filters = BoardFilters {
+        name: query.name.as_deref(),
+        tag: query.tag.as_deref(),
+        state: query.state,
+        difficulty: query.difficulty,
+        min_difficulty: query.min_difficulty,
+        max_difficulty: query.max_difficulty,
+    };
+
+    if query.summary.unwrap_or(false) {
+        let summaries = list_summaries(&filters, i64::from(limit), offset, &pool)?;
+
+        tracing::info!("Successfully listed {} board summaries", summaries.len());
+
+        return Ok(response::BoardSummaries::new(
+            summaries
+                .into_iter()
+                .map(response::BoardSummary::new)
+                .collect(),
+        )
+        .into_response());
+    }
+
+    let boards = list_boards(&filters, i64::from(limit), offset, &pool)?;
+
+    tracing::info!("Successfully listed {} boards", boards.len());
+
+    Ok(response::Boards::new(
+        boards
+            .into_iter()
+            .map(|board| response::Board::with_fields(board, &fields))
+            .collect(),
+    )
+    .into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Board Operations",
+    operation_id = "search_boards",
+    path = "/board/search",
+    params(request::SearchBoardsParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", body = Boards),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn search(
+    Extension(pool): Extension<DbPool>,
+    query_extraction: Option<Query<request::SearchBoardsParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to search boards");
+
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .min(MAX_LIST_LIMIT);
+
+    let offset = query.offset.unwrap_or(0);
+
+    let boards = match (query.hash, query.notation) {
+        (Some(hash), None) => {
+            search_boards(hash, None, i64::from(limit), i64::from(offset), &pool)?
+        }
+        (None, Some(notation)) => {
+            let start = notation::parse(&notation)?;
+            let notation = notation::serialize(&start);
+
+            // the constraints and targets of the boards' starting positions are
+            // not stored in their own columns, so are compared once read
+            search_matching_boards(
+                start.hash(),
+                Some(&start.goal),
+                |board| {
+                    board
+                        .at_move(0)
+                        .is_ok_and(|board| notation::serialize(&board) == notation)
+                },
+                usize::try_from(limit).unwrap(),
+                usize::try_from(offset).unwrap(),
+                &pool,
+            )?
+        }
+        _ => return Err(HandlerError::Query.into()),
+    };
+
+    let boards: Vec<response::Board> = boards
+        .into_iter()
+        .map(|board| response::Board::with_fields(board, &fields))
+        .collect();
+
+    tracing::info!("Successfully found {} boards", boards.len());
+
+    Ok(response::Boards::new(boards).into_response())
+}
+
+#[utoipa::path(
+    delete,
+    tag = "Board Operations",
+    operation_id = "delete_board",
+    path = "/board/{board_id}",
+    params(request::BoardParams, request::VersionParams),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, response = docs::StaleVersion),
+        (status = PRECONDITION_FAILED, response = docs::ETagMismatch),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn delete(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    query_extraction: Option<Query<request::VersionParams>>,
+    headers: HeaderMap,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to delete board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+    let precondition = Precondition::new(&headers, query.version)?;
+
+    delete_board(params.board_id, precondition.version(), &pool)
+        .map_err(|err| precondition.error(err))?;
+
+    tracing::info!("Successfully deleted board with id {}", params.board_id);
+
+    Ok(().into_response())
+}
+
+#[utoipa::path(
+    delete,
+    tag = "Board Operations",
+    operation_id = "delete_boards",
+    path = "/board",
+    request_body(content = DeleteBoards),
+    responses(
+        (status = OK, description = "Success", body = BoardDeletions),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = FORBIDDEN, description = "Deleting by conditions without an admin API key", body = ErrorResponse,
+            example = json!({"error": "forbidden", "message": "Forbidden: Deleting boards by conditions requires an admin API key"})),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn delete_many(
+    Extension(pool): Extension<DbPool>,
+    api_key: Option<Extension<ApiKey>>,
+    json_extraction: Option<Json<request::DeleteBoards>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to delete boards");
+
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    let (board_ids, deleted_ids) = match body {
+        request::DeleteBoards {
+            ids: Some(ids),
+            name: None,
+            tag: None,
+            state: None,
+        } => {
+            let deleted_ids = delete_boards(&ids, &pool)?;
+
+            (ids, deleted_ids)
+        }
+        request::DeleteBoards {
+            ids: None,
+            name,
+            tag,
+            state,
+        } if name.is_some() || tag.is_some() || state.is_some() => {
+            if !api_key.is_some_and(|api_key| api_key.admin) {
+                return Err(HttpError::Forbidden(
+                    "Deleting boards by conditions requires an admin API key".to_string(),
+                ));
+            }
+
+            let filters = BoardFilters {
+                name: name.as_deref(),
+                tag: tag.as_deref(),
+                state,
+                ..BoardFilters::default()
+            };
+
+            let deleted_ids = delete_matching_boards(&filters, &pool)?;
+
+            (deleted_ids.clone(), deleted_ids)
+        }
+        _ => return Err(HandlerError::Body.into()),
+    };
+
+    tracing::info!("Successfully deleted {} boards", deleted_ids.len());
+
+    Ok(response::BoardDeletions::new(&board_ids, &deleted_ids).into_response())
 }
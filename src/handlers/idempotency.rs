@@ -0,0 +1,164 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header::CONTENT_LENGTH, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use std::time::Duration;
+
+use crate::errors::http::Error as HttpError;
+use crate::models::game::utils::unix_millis;
+use crate::repositories::idempotency_keys::{complete, forget_expired, release, reserve};
+use crate::services::api_keys::ApiKey;
+use crate::services::db::Pool as DbPool;
+use crate::services::idempotency::{fingerprint, lease, window, Outcome, StoredResponse};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+// Header marking responses which are replayed rather than handled again
+const REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+const MAX_KEY_LEN: usize = 255;
+
+// Most bytes read from the body of a request made with an idempotency key, as
+// for the bodies extracted by the handlers
+const MAX_BODY_LEN: usize = 2 * 1024 * 1024;
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn replay(response: StoredResponse) -> Response {
+    let mut builder = Response::builder()
+        .status(StatusCode::from_u16(response.status).unwrap_or(StatusCode::OK))
+        .header(REPLAYED_HEADER, "true");
+
+    for (name, value) in &response.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::try_from(name.as_str()),
+            HeaderValue::try_from(value.as_str()),
+        ) {
+            builder = builder.header(name, value);
+        }
+    }
+
+    builder.body(Body::from(response.body)).unwrap()
+}
+
+// Persist the response to POST and PUT requests made with an Idempotency-Key
+// header, and replay it to retries of the request made with the same key, so
+// that retried requests are not handled twice. Keys are scoped to the API key
+// the request was made with, if any, and are forgotten once the window set by
+// the IDEMPOTENCY_WINDOW environment variable has passed. Retries are matched
+// to the request by their method, path, query string and body. Server errors
+// are not persisted, so that the request can be retried, and keys whose request
+// is never completed are freed once the lease set by the IDEMPOTENCY_LEASE
+// environment variable has passed.
+#[allow(clippy::cast_possible_wrap)]
+pub async fn replay_retries(
+    Extension(pool): Extension<DbPool>,
+    request: Request,
+    next: Next,
+) -> Result<Response, HttpError> {
+    if request.method() != Method::POST && request.method() != Method::PUT {
+        return Ok(next.run(request).await);
+    }
+
+    let Some(header) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+
+    let idempotency_key = header
+        .to_str()
+        .ok()
+        .filter(|value| !value.is_empty() && value.len() <= MAX_KEY_LEN)
+        .ok_or_else(|| HttpError::BadRequest(format!("{IDEMPOTENCY_KEY_HEADER} is invalid")))?
+        .to_string();
+
+    let api_key_id = request
+        .extensions()
+        .get::<ApiKey>()
+        .map_or(0, |api_key| api_key.id);
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().unwrap_or_default().to_string();
+
+    let (parts, body) = request.into_parts();
+
+    let body = to_bytes(body, MAX_BODY_LEN)
+        .await
+        .map_err(|_| HttpError::BadRequest("Request body is too large".to_string()))?;
+
+    let fingerprint = fingerprint(&query, &body);
+
+    let request = Request::from_parts(parts, Body::from(body));
+
+    let now = unix_millis();
+
+    forget_expired(
+        now.saturating_sub(millis(window())) as i64,
+        now.saturating_sub(millis(lease())) as i64,
+        &pool,
+    )?;
+
+    let record = reserve(
+        &idempotency_key,
+        api_key_id,
+        &method,
+        &path,
+        &fingerprint,
+        now,
+        &pool,
+    )?;
+
+    if let Some(record) = record {
+        return match record.outcome(&method, &path, &fingerprint) {
+            Outcome::Replay(response) => {
+                tracing::info!("Replaying response for idempotency key {}", idempotency_key);
+
+                Ok(replay(response))
+            }
+            Outcome::InProgress => Err(HttpError::Conflict(
+                "Request with the same idempotency key is in progress".to_string(),
+            )),
+            Outcome::Mismatch => Err(HttpError::BadRequest(
+                "Idempotency key was used for another request".to_string(),
+            )),
+        };
+    }
+
+    let response = next.run(request).await;
+
+    if response.status().is_server_error() {
+        release(&idempotency_key, api_key_id, &pool)?;
+
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        release(&idempotency_key, api_key_id, &pool)?;
+
+        return Ok(
+            HttpError::Unhandled("Failed to read response body".to_string()).into_response(),
+        );
+    };
+
+    let stored_response = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter(|(name, _)| *name != CONTENT_LENGTH)
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect(),
+        body: bytes.to_vec(),
+    };
+
+    complete(&idempotency_key, api_key_id, &stored_response, &pool)?;
+
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
@@ -0,0 +1,47 @@
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::{IntoResponse, Response},
+    Extension,
+};
+
+use crate::docs;
+use crate::errors::{handler::Error as HandlerError, http::Error as HttpError};
+use crate::models::api::{request, response};
+use crate::repositories::jobs::get as get_job;
+use crate::services::db::Pool as DbPool;
+
+#[utoipa::path(
+    get,
+    tag = "Job Operations",
+    operation_id = "get_job",
+    path = "/jobs/{job_id}",
+    params(request::JobParams),
+    responses(
+        (status = OK, description = "Success", body = Job),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, description = "Job not found", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: Record not found"})),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn get(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::JobParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to get job");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+
+    let job = get_job(params.job_id, &pool)?;
+
+    tracing::info!(
+        "Job {} for board {} has status {:?}",
+        job.id,
+        job.board_id,
+        job.status
+    );
+
+    Ok(response::Job::new(job).into_response())
+}
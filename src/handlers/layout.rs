@@ -0,0 +1,25 @@
+use axum::{
+    debug_handler,
+    response::{IntoResponse, Response},
+};
+
+use crate::docs;
+use crate::errors::http::Error as HttpError;
+use crate::models::{api::response, game::layouts::LAYOUTS};
+
+#[utoipa::path(
+    get,
+    tag = "Layout Operations",
+    operation_id = "list_layouts",
+    path = "/layouts",
+    responses(
+        (status = OK, description = "Success", body = Layouts),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn list() -> Result<Response, HttpError> {
+    tracing::info!("Handling request to list layouts");
+
+    Ok(response::Layouts::new(&LAYOUTS).into_response())
+}
@@ -1,2 +1,14 @@
+pub mod admin;
+pub mod api_keys;
 pub mod block;
 pub mod board;
+pub mod idempotency;
+pub mod job;
+pub mod layout;
+pub mod preconditions;
+pub mod puzzle;
+pub mod rate_limits;
+pub mod session;
+pub mod share;
+pub mod solution;
+pub mod versioning;
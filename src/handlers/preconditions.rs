@@ -0,0 +1,62 @@
+use axum::http::{header::IF_MATCH, HeaderMap};
+
+use crate::errors::{board::Error as BoardError, http::Error as HttpError};
+use crate::repositories::boards::Error as BoardsRepositoryError;
+
+// Version of the board a change was made against, which is given either by
+// the If-Match header, holding the board's ETag, or by the version query
+// parameter. The If-Match header takes precedence over the query parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precondition {
+    None,
+    Version(u32),
+    IfMatch(u32),
+}
+
+impl Precondition {
+    pub fn new(headers: &HeaderMap, query_version: Option<u32>) -> Result<Self, HttpError> {
+        let Some(header) = headers.get(IF_MATCH) else {
+            return Ok(query_version.map_or(Self::None, Self::Version));
+        };
+
+        let invalid = || HttpError::BadRequest("If-Match header is invalid".to_string());
+
+        let value = header.to_str().map_err(|_| invalid())?.trim();
+
+        if value == "*" {
+            return Ok(Self::None);
+        }
+
+        // weak ETags never match, since If-Match compares ETags strongly
+        if value.starts_with("W/") {
+            return Err(HttpError::PreconditionFailed(
+                "If-Match header must hold a strong ETag".to_string(),
+            ));
+        }
+
+        value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .and_then(|version| version.parse().ok())
+            .map(Self::IfMatch)
+            .ok_or_else(invalid)
+    }
+
+    pub fn version(self) -> Option<u32> {
+        match self {
+            Self::None => None,
+            Self::Version(version) | Self::IfMatch(version) => Some(version),
+        }
+    }
+
+    // Error returned for a failed change, where changes made against a stale
+    // ETag fail with 412 Precondition Failed rather than 409 Conflict
+    pub fn error(self, err: BoardsRepositoryError) -> HttpError {
+        match (self, err) {
+            (Self::IfMatch(_), BoardsRepositoryError::BoardError(BoardError::VersionConflict)) => {
+                HttpError::PreconditionFailed("Board ETag does not match".to_string())
+            }
+            (_, err) => err.into(),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use axum::{
+    debug_handler,
+    extract::{Json, Path, Query},
+    response::{IntoResponse, Response},
+    Extension,
+};
+
+use crate::docs;
+use crate::errors::{
+    board::Error as BoardError, handler::Error as HandlerError, http::Error as HttpError,
+};
+use crate::models::{
+    api::{request, response},
+    game::{
+        layouts::{self, LAYOUTS},
+        utils::unix_millis,
+    },
+};
+use crate::repositories::boards::{get as get_board, insert as insert_board};
+use crate::repositories::scores::{create as create_score, leaderboard as get_leaderboard};
+use crate::services::{daily, db::Pool as DbPool, leaderboards};
+
+const DEFAULT_LEADERBOARD_LIMIT: u32 = 10;
+const MAX_LEADERBOARD_LIMIT: u32 = 100;
+
+#[utoipa::path(
+    get,
+    tag = "Puzzle Operations",
+    operation_id = "list_puzzles",
+    path = "/puzzles",
+    responses(
+        (status = OK, description = "Success", body = Puzzles),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn list() -> Result<Response, HttpError> {
+    tracing::info!("Handling request to list puzzles");
+
+    Ok(response::Puzzles::new(&LAYOUTS).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Puzzle Operations",
+    operation_id = "daily_puzzle",
+    path = "/puzzles/daily",
+    params(request::DailyPuzzleParams),
+    responses(
+        (status = OK, description = "Success", body = DailyPuzzle),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn daily(
+    Extension(pool): Extension<DbPool>,
+    query_extraction: Option<Query<request::DailyPuzzleParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to get daily puzzle");
+
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+
+    let days = match query.date {
+        Some(date) => daily::parse_date(&date).ok_or(HandlerError::Query)?,
+        None => daily::today(unix_millis()),
+    };
+
+    let date = daily::format_date(days);
+    let board = daily::board(days)?;
+
+    let (board, solution) = tokio::task::spawn_blocking(move || {
+        let solution = daily::solution(&board, &pool);
+
+        (board, solution)
+    })
+    .await
+    .map_err(|err| HttpError::Unhandled(err.to_string()))?;
+
+    let solution = solution
+        .map_err(|err| HttpError::Unhandled(err.to_string()))?
+        .ok_or_else(|| HttpError::Unhandled("Daily puzzle has no solution".to_string()))?;
+
+    tracing::info!(
+        "Daily puzzle of {} has a solution of length {}",
+        date,
+        solution.len()
+    );
+
+    Ok(response::DailyPuzzle::new(date, &board, &solution).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Puzzle Operations",
+    operation_id = "start_puzzle",
+    path = "/puzzles/{name}/start",
+    params(request::PuzzleParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, description = "Puzzle not found", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: No layout with matching name"})),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn start(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::PuzzleParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to start puzzle");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+
+    let layout = layouts::find(&params.name).ok_or(BoardError::LayoutNotFound)?;
+
+    let mut new_board = layout.board();
+    new_board.set_metadata(Some(layout.name.to_string()), None, vec![])?;
+
+    let board = insert_board(&new_board, &pool)?;
+
+    tracing::info!("Puzzle {} successfully started as {}", layout.name, board);
+
+    Ok(response::Board::with_fields(board, &fields).into_response())
+}
+
+#[utoipa::path(
+    post,
+    tag = "Puzzle Operations",
+    operation_id = "submit_score",
+    path = "/puzzles/{name}/scores",
+    params(request::PuzzleParams),
+    request_body(content = SubmitScore),
+    responses(
+        (status = OK, description = "Success", body = Score),
+        (status = BAD_REQUEST, description = "Invalid parameters, or board not solved from the puzzle", body = ErrorResponse,
+            example = json!({"error": "bad_request", "message": "Invalid input: Moves do not solve the board"})),
+        (status = NOT_FOUND, description = "Puzzle or board not found", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: No layout with matching name"})),
+        (status = GONE, response = docs::BoardExpired),
+        (status = CONFLICT, description = "Board's solve already submitted", body = ErrorResponse,
+            example = json!({"error": "conflict", "message": "Conflict: Board's solve was already submitted"})),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn submit_score(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::PuzzleParams>>,
+    json_extraction: Option<Json<request::SubmitScore>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to submit score");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let body = json_extraction.ok_or(HandlerError::Body)?.0;
+
+    if !leaderboards::is_valid_player(&body.player) {
+        return Err(HandlerError::Body.into());
+    }
+
+    let layout = layouts::find(&params.name).ok_or(BoardError::LayoutNotFound)?;
+
+    let board = get_board(body.board_id, &pool)?;
+
+    let solve = leaderboards::verify(layout, &board)?;
+
+    let score = create_score(
+        layout.name,
+        body.player.trim(),
+        solve,
+        board.id,
+        unix_millis(),
+        &pool,
+    )?
+    .ok_or_else(|| HttpError::Conflict("Board's solve was already submitted".to_string()))?;
+
+    tracing::info!(
+        "Score of {} moves successfully submitted for puzzle {}",
+        solve.num_moves,
+        layout.name
+    );
+
+    Ok(response::Score::new(score).into_response())
+}
+
+#[utoipa::path(
+    get,
+    tag = "Puzzle Operations",
+    operation_id = "get_leaderboard",
+    path = "/puzzles/{name}/leaderboard",
+    params(request::PuzzleParams, request::LeaderboardParams),
+    responses(
+        (status = OK, description = "Success", body = Leaderboard),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, description = "Puzzle not found", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: No layout with matching name"})),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn leaderboard(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::PuzzleParams>>,
+    query_extraction: Option<Query<request::LeaderboardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to get leaderboard");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let query = query_extraction.ok_or(HandlerError::Query)?.0;
+
+    let layout = layouts::find(&params.name).ok_or(BoardError::LayoutNotFound)?;
+
+    let ranking = query.ranking.unwrap_or_default();
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LEADERBOARD_LIMIT)
+        .min(MAX_LEADERBOARD_LIMIT);
+
+    let scores = get_leaderboard(layout.name, ranking, i64::from(limit), &pool)?;
+
+    tracing::info!(
+        "Successfully found {} scores for puzzle {}",
+        scores.len(),
+        layout.name
+    );
+
+    Ok(response::Leaderboard::new(layout.name.to_string(), ranking, scores).into_response())
+}
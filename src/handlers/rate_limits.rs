@@ -0,0 +1,83 @@
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+    Extension,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
+
+use crate::errors::http::Error as HttpError;
+use crate::services::api_keys::ApiKey;
+use crate::services::rate_limits::{Client, Limiter, Tier};
+
+// Address of the client, which is given by the proxy in front of the API in the
+// configured header when deployed behind one, and otherwise is the address of
+// the connection
+fn client_address(
+    headers: &HeaderMap,
+    client_ip_header: Option<&str>,
+    connection: SocketAddr,
+) -> IpAddr {
+    client_ip_header
+        .and_then(|header| headers.get(header))
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(connection.ip())
+}
+
+async fn limit(
+    limiter: &Limiter,
+    connection: SocketAddr,
+    tier: Tier,
+    request: Request,
+    next: Next,
+) -> Result<Response, HttpError> {
+    // requests made with an API key are counted against the key, using its
+    // own limits when set
+    let (client, limit) = match request.extensions().get::<ApiKey>() {
+        Some(api_key) => (
+            Client::ApiKey(api_key.id),
+            api_key.limit(tier).unwrap_or(limiter.limit(tier)),
+        ),
+        None => (
+            Client::Address(client_address(
+                request.headers(),
+                limiter.client_ip_header(),
+                connection,
+            )),
+            limiter.limit(tier),
+        ),
+    };
+
+    if let Err(retry_after) = limiter.acquire(client, tier, limit, Instant::now()) {
+        tracing::info!("Rate limited request from {:?}", client);
+
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+        return Err(HttpError::TooManyRequests(seconds));
+    }
+
+    Ok(next.run(request).await)
+}
+
+pub async fn limit_requests(
+    Extension(limiter): Extension<Limiter>,
+    ConnectInfo(connection): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Result<Response, HttpError> {
+    limit(&limiter, connection, Tier::Default, request, next).await
+}
+
+// Stricter limit for requests which are expensive to serve, such as solving,
+// searching and creating boards, which also count towards the default limit
+pub async fn limit_expensive_requests(
+    Extension(limiter): Extension<Limiter>,
+    ConnectInfo(connection): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Result<Response, HttpError> {
+    limit(&limiter, connection, Tier::Expensive, request, next).await
+}
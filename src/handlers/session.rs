@@ -0,0 +1,194 @@
+use axum::{
+    debug_handler,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path,
+    },
+    response::Response,
+    Extension,
+};
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::docs;
+use crate::errors::{handler::Error as HandlerError, http::Error as HttpError};
+use crate::handlers::board::find_solution;
+use crate::models::{
+    api::{request, response},
+    game::board::Board,
+};
+use crate::repositories::boards::{get as get_board, update as update_board};
+use crate::services::{db::Pool as DbPool, sessions::Manager as SessionManager, solver};
+
+#[utoipa::path(
+    get,
+    tag = "Session Operations",
+    operation_id = "connect_session",
+    path = "/ws/board/{board_id}",
+    params(request::BoardParams),
+    request_body(content = SessionCommand, description = "Commands sent as WebSocket text messages"),
+    responses(
+        (status = SWITCHING_PROTOCOLS, description = "Success, events are sent as WebSocket text messages", body = SessionEvent),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn connect(
+    Extension(pool): Extension<DbPool>,
+    Extension(manager): Extension<SessionManager>,
+    path_extraction: Option<Path<request::BoardParams>>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to connect to board session");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+    let board = get_board(params.board_id, &pool)?;
+
+    Ok(upgrade.on_upgrade(move |socket| run(socket, board, pool, manager)))
+}
+
+async fn run(mut socket: WebSocket, board: Board, pool: DbPool, manager: SessionManager) {
+    let board_id = board.id;
+    let mut receiver = manager.join(board_id);
+
+    tracing::info!(
+        "Connection joined session for board {} ({} connections)",
+        board_id,
+        manager.connections(board_id)
+    );
+
+    let event = response::SessionEvent::Board(Box::new(response::Board::new(board)));
+
+    if send(&mut socket, &event).await {
+        loop {
+            tokio::select! {
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Err(err) = handle_command(&text, board_id, &pool, &manager) {
+                            let event = response::SessionEvent::Error(err.to_string());
+
+                            if !send(&mut socket, &event).await {
+                                break;
+                            }
+                        }
+                    }
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+                event = receiver.recv() => match event {
+                    Ok(event) => {
+                        if socket.send(Message::Text(event)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::info!("Session for board {} skipped {} events", board_id, skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+            }
+        }
+    }
+
+    drop(receiver);
+    manager.leave(board_id);
+
+    tracing::info!("Connection left session for board {}", board_id);
+}
+
+// Send an event to this connection only, returning whether it was sent
+async fn send(socket: &mut WebSocket, event: &response::SessionEvent) -> bool {
+    match serde_json::to_string(event) {
+        Ok(message) => socket.send(Message::Text(message)).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+// Apply the command and publish the resulting events to every connection to
+// the board. Errors are only reported back to the connection which sent it.
+fn handle_command(
+    text: &str,
+    board_id: i32,
+    pool: &DbPool,
+    manager: &SessionManager,
+) -> Result<(), HttpError> {
+    let command: request::SessionCommand =
+        serde_json::from_str(text).map_err(|_| HandlerError::Body)?;
+
+    let board = match command {
+        request::SessionCommand::MoveBlock(data) => {
+            tracing::info!(
+                "Moving block at index {} in board with id {} by ({},{})",
+                data.block_idx,
+                board_id,
+                data.row_diff,
+                data.col_diff
+            );
+
+            update_board(
+                board_id,
+                None,
+                |board| board.move_block(data.block_idx, data.row_diff, data.col_diff),
+                pool,
+            )
+        }
+        request::SessionCommand::UndoMove => {
+            tracing::info!("Undoing last move for board with id {}", board_id);
+
+            update_board(board_id, None, Board::undo_move, pool)
+        }
+        request::SessionCommand::Reset => {
+            tracing::info!("Resetting board with id {}", board_id);
+
+            update_board(board_id, None, Board::reset, pool)
+        }
+        request::SessionCommand::Solve(query) => {
+            let board = get_board(board_id, pool)?;
+
+            spawn_solve(board, &query, pool, manager);
+
+            return Ok(());
+        }
+    }?;
+
+    manager.publish(
+        board_id,
+        &response::SessionEvent::Board(Box::new(response::Board::new(board))),
+    );
+
+    Ok(())
+}
+
+// Solve the board on tokio's blocking thread pool, publishing the solver's
+// progress and then the solution to every connection to the board
+fn spawn_solve(
+    board: Board,
+    query: &request::SolveParams,
+    pool: &DbPool,
+    manager: &SessionManager,
+) {
+    let board_id = board.id;
+    let progress_manager = manager.clone();
+
+    let options = solver::Options {
+        progress: Some(Arc::new(move |progress: solver::Progress| {
+            progress_manager.publish(board_id, &response::SessionEvent::Progress(progress));
+        })),
+        ..query.options()
+    };
+
+    let pool = pool.clone();
+    let manager = manager.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let event = match find_solution(&board, &options, true, &pool) {
+            Ok(result) => response::SessionEvent::Solution(result),
+            Err(err) => response::SessionEvent::Error(err.to_string()),
+        };
+
+        manager.publish(board_id, &event);
+    });
+}
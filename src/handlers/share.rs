@@ -0,0 +1,110 @@
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::{IntoResponse, Response},
+    Extension,
+};
+
+use crate::docs;
+use crate::errors::{handler::Error as HandlerError, http::Error as HttpError};
+use crate::models::{
+    api::{request, response},
+    game::{notation, utils::unix_millis},
+};
+use crate::repositories::boards::{get as get_board, insert as insert_board};
+use crate::repositories::shares::{create as create_share, get as get_share};
+use crate::services::{
+    db::Pool as DbPool,
+    shares::{self, MAX_CODE_ATTEMPTS},
+};
+
+#[utoipa::path(
+    post,
+    tag = "Share Operations",
+    operation_id = "share_board",
+    path = "/board/{board_id}/share",
+    params(request::BoardParams),
+    responses(
+        (status = OK, description = "Success", body = Share),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, response = docs::BoardNotFound),
+        (status = GONE, response = docs::BoardExpired),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn create(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::BoardParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to share board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+
+    let board = get_board(params.board_id, &pool)?;
+    let start = board.at_move(0)?;
+    let board_notation = notation::serialize(&start);
+
+    for _ in 0..MAX_CODE_ATTEMPTS {
+        let code = shares::random_code();
+
+        if let Some(share) = create_share(
+            &code,
+            &board_notation,
+            board.name.as_deref(),
+            unix_millis(),
+            &pool,
+        )? {
+            tracing::info!("Board {} successfully shared as {}", board, code);
+
+            return Ok(response::Share::new(share).into_response());
+        }
+    }
+
+    Err(HttpError::Unhandled(
+        "No free share code was found".to_string(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    tag = "Share Operations",
+    operation_id = "open_shared_board",
+    path = "/shared/{code}",
+    params(request::ShareCodeParams, request::BoardFieldsParams),
+    responses(
+        (status = OK, description = "Success", body = Board),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, description = "Share code not found", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: No shared board with matching code"})),
+        (status = TOO_MANY_REQUESTS, response = docs::RateLimited),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn open(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::ShareCodeParams>>,
+    fields: response::BoardFields,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to open shared board");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+
+    let share = get_share(&params.code, &pool)?
+        .ok_or_else(|| HttpError::NotFound("No shared board with matching code".to_string()))?;
+
+    let mut new_board = notation::parse(&share.notation)?;
+    new_board.set_metadata(share.name, None, vec![])?;
+
+    let board = insert_board(&new_board, &pool)?;
+
+    tracing::info!(
+        "Shared board {} successfully opened as {}",
+        share.code,
+        board
+    );
+
+    Ok(response::Board::with_fields(board, &fields).into_response())
+}
@@ -0,0 +1,52 @@
+use axum::{
+    debug_handler,
+    extract::Path,
+    response::{IntoResponse, Response},
+    Extension,
+};
+
+use crate::docs;
+use crate::errors::{handler::Error as HandlerError, http::Error as HttpError};
+use crate::models::api::{request, response};
+use crate::repositories::solutions::get_all as get_solutions;
+use crate::services::db::Pool as DbPool;
+
+#[utoipa::path(
+    get,
+    tag = "Solution Operations",
+    operation_id = "get_cached_solutions",
+    path = "/solutions/{hash}",
+    params(request::SolutionHashParams),
+    responses(
+        (status = OK, description = "Success", body = CachedSolutions),
+        (status = BAD_REQUEST, response = docs::InvalidParameters),
+        (status = NOT_FOUND, description = "No cached solution", body = ErrorResponse,
+            example = json!({"error": "not_found", "message": "Not found: No cached solution for the hash"})),
+        (status = INTERNAL_SERVER_ERROR, response = docs::Unhandled),
+    ),
+)]
+#[debug_handler]
+pub async fn get(
+    Extension(pool): Extension<DbPool>,
+    path_extraction: Option<Path<request::SolutionHashParams>>,
+) -> Result<Response, HttpError> {
+    tracing::info!("Handling request to get cached solutions");
+
+    let params = path_extraction.ok_or(HandlerError::Path)?.0;
+
+    let solutions = get_solutions(params.hash, &pool)?;
+
+    if solutions.is_empty() {
+        return Err(HttpError::NotFound(
+            "No cached solution for the hash".to_string(),
+        ));
+    }
+
+    tracing::info!(
+        "Found {} cached solutions for hash {}",
+        solutions.len(),
+        params.hash
+    );
+
+    Ok(response::CachedSolutions::new(params.hash, solutions).into_response())
+}
@@ -0,0 +1,52 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::header::{CONTENT_LENGTH, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+use crate::errors::http::Error as HttpError;
+
+// Version of the API served under /api/v1, whose responses are wrapped in an
+// envelope naming it
+pub const API_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+struct Envelope {
+    api_version: u32,
+    data: serde_json::Value,
+}
+
+// Wrap the JSON body of the response in an envelope naming the API version,
+// so that clients can tell which version of the API produced it. Error
+// responses, and responses which are not JSON, such as plain text, event
+// streams and WebSocket upgrades, are left as is.
+pub async fn envelope(response: Response) -> Response {
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == "application/json");
+
+    if !is_json || !response.status().is_success() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return HttpError::Unhandled("Failed to read response body".to_string()).into_response();
+    };
+
+    let Ok(data) = serde_json::from_slice(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let envelope = Envelope {
+        api_version: API_VERSION,
+        data,
+    };
+
+    parts.headers.remove(CONTENT_LENGTH);
+
+    Response::from_parts(parts, Body::from(serde_json::to_vec(&envelope).unwrap()))
+}
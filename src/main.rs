@@ -1,15 +1,22 @@
 #![warn(clippy::pedantic)]
 
 use axum::{
-    http::{HeaderValue, Method},
-    routing::{delete, post, put},
+    http::{header::ETAG, HeaderValue, Method},
+    middleware::{from_fn, map_response},
+    routing::{delete, get, post, put},
     Extension, Router,
 };
-use tower_http::cors::{Any, CorsLayer};
+use std::net::SocketAddr;
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{Any, CorsLayer},
+};
 use tracing_subscriber::{layer::SubscriberExt, Registry};
 use utoipa::OpenApi;
 use utoipa_rapidoc::RapiDoc;
 
+use handlers::rate_limits::limit_expensive_requests;
+
 mod docs;
 mod errors;
 mod handlers;
@@ -17,6 +24,129 @@ mod models;
 mod repositories;
 mod services;
 
+// Routes of the board operations, along with the block operations nested
+// under each board
+fn board_routes() -> Router {
+    let block_routes = Router::new()
+        .route("/", post(handlers::block::add))
+        .route("/:block_idx", put(handlers::block::alter))
+        .route("/:block_idx", delete(handlers::block::remove));
+
+    Router::new()
+        .route(
+            "/",
+            post(handlers::board::new).layer(from_fn(limit_expensive_requests)),
+        )
+        .route("/", get(handlers::board::list))
+        .route("/", delete(handlers::board::delete_many))
+        .route("/search", get(handlers::board::search))
+        .route("/validate", post(handlers::board::validate))
+        .route(
+            "/import",
+            post(handlers::board::import).layer(from_fn(limit_expensive_requests)),
+        )
+        .route("/:board_id", get(handlers::board::get))
+        .route("/:board_id", put(handlers::board::alter))
+        .route("/:board_id", delete(handlers::board::delete))
+        .route("/:board_id/keepalive", post(handlers::board::keep_alive))
+        .route(
+            "/:board_id/share",
+            post(handlers::share::create).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:board_id/solve",
+            post(handlers::board::solve).layer(from_fn(limit_expensive_requests)),
+        )
+        .route("/:board_id/estimate", get(handlers::board::estimate))
+        .route(
+            "/:board_id/hint",
+            post(handlers::board::hint).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:board_id/check-solvable",
+            post(handlers::board::check_solvable).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:board_id/solve/async",
+            post(handlers::board::solve_async).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:board_id/solve/stream",
+            get(handlers::board::solve_stream).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:board_id/graph",
+            get(handlers::board::graph).layer(from_fn(limit_expensive_requests)),
+        )
+        .route("/:board_id/state", get(handlers::board::snapshot))
+        .route("/:board_id/history", get(handlers::board::history))
+        .route("/:board_id/export", get(handlers::board::export))
+        .route("/:board_id/diff", get(handlers::board::diff))
+        .route(
+            "/:board_id/clone",
+            post(handlers::board::clone).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:board_id/transform",
+            post(handlers::board::transform).layer(from_fn(limit_expensive_requests)),
+        )
+        .route("/:board_id/moves", get(handlers::board::moves))
+        .route("/:board_id/replay", get(handlers::board::replay))
+        .route("/:board_id/next-moves", get(handlers::board::next_moves))
+        .route("/:board_id/moves", put(handlers::board::apply_moves))
+        .route("/:board_id/blocks", post(handlers::block::add_many))
+        .nest("/:board_id/block", block_routes)
+}
+
+// Routes of the API, which are served under each of its prefixes
+fn api_routes() -> Router {
+    let admin_routes = Router::new()
+        .route("/hardest", post(handlers::admin::find_hardest))
+        .route(
+            "/board/:board_id/compare",
+            post(handlers::admin::compare_algorithms),
+        )
+        .route_layer(from_fn(limit_expensive_requests))
+        .route_layer(from_fn(handlers::api_keys::require_admin));
+
+    let job_routes = Router::new().route("/:job_id", get(handlers::job::get));
+
+    let puzzle_routes = Router::new()
+        .route("/", get(handlers::puzzle::list))
+        .route(
+            "/daily",
+            get(handlers::puzzle::daily).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:name/start",
+            post(handlers::puzzle::start).layer(from_fn(limit_expensive_requests)),
+        )
+        .route(
+            "/:name/scores",
+            post(handlers::puzzle::submit_score).layer(from_fn(limit_expensive_requests)),
+        )
+        .route("/:name/leaderboard", get(handlers::puzzle::leaderboard));
+
+    let share_routes = Router::new().route(
+        "/:code",
+        get(handlers::share::open).layer(from_fn(limit_expensive_requests)),
+    );
+
+    let solution_routes = Router::new().route("/:hash", get(handlers::solution::get));
+
+    let ws_routes = Router::new().route("/board/:board_id", get(handlers::session::connect));
+
+    Router::new()
+        .nest("/admin", admin_routes)
+        .nest("/board", board_routes())
+        .nest("/jobs", job_routes)
+        .route("/layouts", get(handlers::layout::list))
+        .nest("/puzzles", puzzle_routes)
+        .nest("/shared", share_routes)
+        .nest("/solutions", solution_routes)
+        .nest("/ws", ws_routes)
+}
+
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
@@ -49,34 +179,50 @@ async fn main() {
     let mut conn = db_pool.get().unwrap();
     services::db::run_migrations(&mut conn);
 
+    services::db::fill_start_hashes(&db_pool);
+
+    services::jobs::resume(&db_pool);
+
+    services::distances::populate(&db_pool);
+
+    services::classics::warm(&db_pool);
+
+    services::expiry::spawn_cleanup(&db_pool);
+
+    let api_key_usage = services::api_keys::Usage::default();
+
+    services::api_keys::spawn_flush(&api_key_usage, &db_pool);
+
     let origins: Vec<HeaderValue> = allowed_origins
         .split(',')
         .map(|origin| origin.parse().unwrap())
         .collect();
 
     let cors = CorsLayer::new()
-        .allow_methods([Method::DELETE, Method::POST, Method::PUT])
+        .allow_methods([Method::DELETE, Method::GET, Method::POST, Method::PUT])
         .allow_headers(Any)
+        .expose_headers([ETAG])
         .allow_origin(origins);
 
-    let block_routes = Router::new()
-        .route("/", post(handlers::block::add))
-        .route("/:block_idx", put(handlers::block::alter))
-        .route("/:block_idx", delete(handlers::block::remove));
-
-    let board_routes = Router::new()
-        .route("/", post(handlers::board::new))
-        .route("/:board_id", put(handlers::board::alter))
-        .route("/:board_id", delete(handlers::board::delete))
-        .route("/:board_id/solve", post(handlers::board::solve))
-        .nest("/:board_id/block", block_routes);
-
-    let api_routes = Router::new().nest("/board", board_routes);
-
+    // Routes are served under /api/v1, where JSON responses are wrapped in an
+    // envelope naming the version, and under /api without the envelope, for
+    // clients which predate versioning
     let app = Router::new()
-        .nest("/api", api_routes)
+        .nest(
+            "/api/v1",
+            api_routes().layer(map_response(handlers::versioning::envelope)),
+        )
+        .nest("/api", api_routes())
+        .layer(from_fn(handlers::idempotency::replay_retries))
+        .layer(from_fn(handlers::rate_limits::limit_requests))
+        .layer(from_fn(handlers::api_keys::authenticate))
+        .layer(Extension(services::api_keys::Policy::from_env()))
+        .layer(Extension(api_key_usage))
+        .layer(Extension(services::rate_limits::Limiter::from_env()))
         .layer(Extension(db_pool))
+        .layer(Extension(services::sessions::Manager::default()))
         .layer(cors)
+        .layer(CompressionLayer::new())
         .merge(
             RapiDoc::with_openapi("/api-docs/openapi.json", docs::ApiDoc::openapi())
                 .path("/rapidoc"),
@@ -88,5 +234,10 @@ async fn main() {
 
     tracing::info!("Listening on {bind_url}:{bind_port}");
 
-    axum::serve(listener, app).await.unwrap();
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
@@ -1,17 +1,526 @@
-use serde::Deserialize;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
+use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
-use crate::models::game::{blocks::Block, board::State as BoardState};
+use crate::errors::{
+    board::Error as BoardError, handler::Error as HandlerError, http::Error as HttpError,
+};
+use crate::models::api::response::{BoardField, BoardFields};
+use crate::models::game::{
+    blocks::{Axis, Block, Positioned as PositionedBlock},
+    board::{Board, Goal, State as BoardState, Target, Variant},
+    history::Action as EditAction,
+    moves::{FlatBoardMove, Step},
+    utils::Position,
+};
+use crate::services::{
+    explorer,
+    leaderboards::Ranking,
+    ordering,
+    randomizer::{Difficulty, MAX_SHUFFLE_MOVES},
+    solver::{self, Algorithm, Objective},
+};
 
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct BoardParams {
     pub board_id: i32,
 }
 
+// Version of the board the change is made against, if the change should be
+// rejected once the board has been changed by someone else
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VersionParams {
+    pub version: Option<u32>,
+}
+
 #[derive(Debug, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct RandomizeParams {
     pub randomize: Option<bool>,
+    // number of random moves made from a solved board when randomizing, which
+    // guarantees the board is solvable, instead of placing blocks at random, up
+    // to MAX_SHUFFLE_MOVES
+    pub shuffle_moves: Option<usize>,
+    // seed from which the board is randomized, so that the same board is
+    // created again for the same seed, which is random if left out
+    pub seed: Option<u64>,
+    // difficulty of the randomized board, given by the length of its optimal
+    // solution, which cannot be combined with the minimum and maximum
+    pub difficulty: Option<Difficulty>,
+    // minimum length of the optimal solution of the randomized board
+    pub min_optimal_moves: Option<usize>,
+    // maximum length of the optimal solution of the randomized board
+    pub max_optimal_moves: Option<usize>,
+}
+
+impl RandomizeParams {
+    // Number of random moves made from a solved board when randomizing, which
+    // is rejected if more than MAX_SHUFFLE_MOVES
+    pub fn shuffle_moves(&self) -> Result<Option<usize>, HandlerError> {
+        match self.shuffle_moves {
+            Some(num_moves) if num_moves > MAX_SHUFFLE_MOVES => Err(HandlerError::Query),
+            shuffle_moves => Ok(shuffle_moves),
+        }
+    }
+
+    // Range of lengths the optimal solution of the randomized board must fall
+    // in, if a difficulty or a minimum or maximum length is given
+    pub fn optimal_moves(&self) -> Result<Option<RangeInclusive<usize>>, HandlerError> {
+        let optimal_moves = match (
+            self.difficulty,
+            self.min_optimal_moves,
+            self.max_optimal_moves,
+        ) {
+            (None, None, None) => return Ok(None),
+            (Some(difficulty), None, None) => difficulty.optimal_moves(),
+            (None, min, max) => min.unwrap_or(0)..=max.unwrap_or(usize::MAX),
+            (Some(_), _, _) => return Err(HandlerError::Query),
+        };
+
+        if optimal_moves.is_empty() {
+            return Err(HandlerError::Query);
+        }
+
+        Ok(Some(optimal_moves))
+    }
+}
+
+// The boards are deleted by their ids, or by the conditions they match, at
+// least one of which must be given, as for listing boards, where ids cannot be
+// combined with conditions
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeleteBoards {
+    pub ids: Option<Vec<i32>>,
+    // text contained in the board's name, ignoring case
+    pub name: Option<String>,
+    pub tag: Option<String>,
+    pub state: Option<BoardState>,
+}
+
+// The board is created from its blocks, from its notation, from the named
+// layout, or empty, depending on which of these fields is given, where at most
+// one of them may be given, and the goal and variant may only be given with
+// the blocks or for an empty board
+#[derive(Debug, Default, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NewBoard {
+    pub blocks: Option<Vec<AddBlock>>,
+    pub notation: Option<String>,
+    pub name: Option<String>,
+    pub goal: Option<Goal>,
+    pub variant: Option<Variant>,
+    pub metadata: Option<Metadata>,
+}
+
+// Board exported from this or another deployment, given by its starting
+// position along with the moves made since
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ImportBoard {
+    pub blocks: Vec<PositionedBlock>,
+    #[serde(default)]
+    pub walls: Vec<Position>,
+    pub variant: Option<Variant>,
+    pub goal: Option<Goal>,
+    #[serde(default)]
+    pub targets: Vec<Target>,
+    pub empty_cells: Option<u8>,
+    #[serde(default)]
+    pub moves: Vec<FlatBoardMove>,
+    pub state: Option<BoardState>,
+    pub metadata: Option<Metadata>,
+}
+
+impl ImportBoard {
+    // Recreate the board at its starting position, as if each part of it was
+    // set in turn while building it, then replay its moves and change it to
+    // the given state, if any. The cells of each block are found from its
+    // shape and position rather than taken from the document.
+    pub fn board(self) -> Result<Board, BoardError> {
+        let mut board = Board::from_variant(self.variant.unwrap_or_default());
+
+        board.set_goal(self.goal.unwrap_or_default())?;
+
+        if !self.targets.is_empty() {
+            board.set_targets(self.targets)?;
+        }
+
+        if let Some(empty_cells) = self.empty_cells {
+            board.set_empty_cells(empty_cells)?;
+        }
+
+        // the walls of the variant are listed along with the other walls
+        for wall in self.walls {
+            if !board.is_wall(wall.row, wall.col) {
+                board.add_wall(wall.row, wall.col)?;
+            }
+        }
+
+        for positioned_block in self.blocks {
+            let new_block = PositionedBlock::new(
+                positioned_block.block,
+                positioned_block.min_position.row,
+                positioned_block.min_position.col,
+            )
+            .ok_or(BoardError::BlockInvalid)?
+            .with_axis(positioned_block.axis)
+            .with_locked(positioned_block.locked)
+            .with_link_group(positioned_block.link_group)
+            .with_display(positioned_block.label, positioned_block.color);
+
+            board.add_block(new_block)?;
+        }
+
+        board.apply_moves(&self.moves)?;
+
+        if let Some(metadata) = self.metadata {
+            board.set_metadata(metadata.name, metadata.description, metadata.tags)?;
+        }
+
+        if let Some(state) = self.state {
+            board.change_state(state)?;
+        }
+
+        Ok(board)
+    }
+}
+
+// Name, description and tags given to the board, where fields left out are
+// cleared
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListBoardsParams {
+    // text contained in the board's name, ignoring case
+    pub name: Option<String>,
+    pub tag: Option<String>,
+    pub state: Option<BoardState>,
+    pub difficulty: Option<u32>,
+    pub min_difficulty: Option<u32>,
+    pub max_difficulty: Option<u32>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    // pages of per_page boards, counted from 1, which take the place of the
+    // limit and offset if given
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    // whether to list only the summary of each board
+    pub summary: Option<bool>,
+}
+
+// Boards are searched for by the hash of their starting position, or by the
+// notation of their starting position, in which case only boards whose
+// starting position has exactly the same notation are found
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchBoardsParams {
+    pub hash: Option<u64>,
+    pub notation: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct SolveParams {
+    pub algorithm: Option<Algorithm>,
+    pub objective: Option<Objective>,
+    pub node_budget: Option<usize>,
+    pub timeout_ms: Option<u64>,
+    pub count_solutions: Option<bool>,
+    pub deterministic: Option<bool>,
+    pub move_ordering: Option<ordering::Strategy>,
+    pub max_depth: Option<usize>,
+}
+
+impl SolveParams {
+    pub fn options(&self) -> solver::Options {
+        solver::Options {
+            algorithm: self.algorithm.unwrap_or_default(),
+            objective: self.objective.unwrap_or_default(),
+            node_budget: self.node_budget.unwrap_or(solver::DEFAULT_NODE_BUDGET),
+            cancellation: solver::Cancellation::with_timeout(
+                self.timeout_ms
+                    .map_or(solver::DEFAULT_TIMEOUT, Duration::from_millis),
+            ),
+            progress: None,
+            count_solutions: self.count_solutions.unwrap_or(false),
+            deterministic: self
+                .deterministic
+                .unwrap_or_else(solver::deterministic_by_default),
+            move_ordering: self.move_ordering.unwrap_or_default().ordering(),
+            max_depth: self.max_depth,
+        }
+    }
+}
+
+// Optional fields of the board response to include or exclude, which are
+// grid, next_moves, notation, not_ready_reasons and repetitions
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BoardFieldsParams {
+    // comma separated fields to include, default: every field
+    pub include: Option<String>,
+    // comma separated fields to exclude, default: none
+    pub exclude: Option<String>,
+    // whether to include the steps taken by each next move, default: false
+    pub detailed_moves: Option<bool>,
+}
+
+// Fields of the board to return, extracted from the include and exclude query
+// params, where an unknown field name is rejected with the Field error
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for BoardFields {
+    type Rejection = HttpError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<BoardFieldsParams>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| HandlerError::Query)?;
+
+        let fields = BoardFields::parse(params.include.as_deref(), params.exclude.as_deref())
+            .map_err(HandlerError::Field)?;
+
+        if params.detailed_moves.unwrap_or(false) {
+            return Ok(fields.with(BoardField::DetailedMoves));
+        }
+
+        Ok(fields)
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SolutionPageParams {
+    // index of the first move of the solution returned, default: 0
+    pub offset: Option<usize>,
+    // number of moves of the solution returned, default: every move
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SolutionSnapshotsParams {
+    // whether to include the grid of the board after moves of the solution
+    pub snapshots: Option<bool>,
+    // number of moves between each snapshot, default: 1
+    pub snapshot_every: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HintParams {
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    #[default]
+    Json,
+    Text,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FormatParams {
+    pub format: Option<Format>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SnapshotParams {
+    pub at_move: Option<usize>,
+    pub format: Option<Format>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NextMovesParams {
+    // block whose next moves are returned, default: every block
+    pub block_idx: Option<usize>,
+    // whether to include the steps taken by each next move, default: false
+    pub detailed_moves: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReplayParams {
+    // whether the notation of the board after each move is returned, default:
+    // false
+    pub snapshots: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CloneParams {
+    pub with_moves: Option<bool>,
+}
+
+// Layout of blocks validated without creating a board from it
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ValidateBoard {
+    pub blocks: Vec<AddBlock>,
+    pub goal: Option<Goal>,
+}
+
+// Transform of the board's layout from which a new board is created
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformBoard {
+    MirrorHorizontal,
+    Rotate,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DiffParams {
+    pub from_move: Option<usize>,
+    pub from_board: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GraphParams {
+    // maximum number of nodes in the graph, up to MAX_MAX_NODES
+    pub max_nodes: Option<usize>,
+    pub timeout_ms: Option<u64>,
+}
+
+impl GraphParams {
+    // Maximum number of nodes in the graph, which is rejected if more than
+    // MAX_MAX_NODES
+    pub fn max_nodes(&self) -> Result<usize, HandlerError> {
+        match self.max_nodes {
+            Some(max_nodes) if max_nodes > explorer::MAX_MAX_NODES => Err(HandlerError::Query),
+            max_nodes => Ok(max_nodes.unwrap_or(explorer::DEFAULT_MAX_NODES)),
+        }
+    }
+
+    pub fn cancellation(&self) -> solver::Cancellation {
+        solver::Cancellation::with_timeout(
+            self.timeout_ms
+                .map_or(solver::DEFAULT_TIMEOUT, Duration::from_millis),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FindHardest {
+    pub blocks: Vec<Block>,
+    pub goal: Option<Goal>,
+    pub max_boards: Option<usize>,
+    pub timeout_ms: Option<u64>,
+}
+
+impl FindHardest {
+    pub fn cancellation(&self) -> solver::Cancellation {
+        solver::Cancellation::with_timeout(
+            self.timeout_ms
+                .map_or(solver::DEFAULT_TIMEOUT, Duration::from_millis),
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CompareAlgorithms {
+    pub algorithms: Option<Vec<Algorithm>>,
+    pub node_budget: Option<usize>,
+    pub timeout_ms: Option<u64>,
+    pub deterministic: Option<bool>,
+}
+
+impl CompareAlgorithms {
+    // Every algorithm is compared unless the request lists the algorithms
+    pub fn algorithms(&self) -> Vec<Algorithm> {
+        self.algorithms.clone().unwrap_or_else(|| {
+            vec![
+                Algorithm::AStar,
+                Algorithm::Bfs,
+                Algorithm::Bidirectional,
+                Algorithm::Greedy,
+                Algorithm::IdaStar,
+            ]
+        })
+    }
+
+    // Each algorithm gets its own cancellation, so that the timeout applies to
+    // each run separately
+    pub fn options(&self, algorithm: Algorithm) -> solver::Options {
+        solver::Options {
+            algorithm,
+            node_budget: self.node_budget.unwrap_or(solver::DEFAULT_NODE_BUDGET),
+            cancellation: solver::Cancellation::with_timeout(
+                self.timeout_ms
+                    .map_or(solver::DEFAULT_TIMEOUT, Duration::from_millis),
+            ),
+            deterministic: self
+                .deterministic
+                .unwrap_or_else(solver::deterministic_by_default),
+            ..solver::Options::default()
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct JobParams {
+    pub job_id: i32,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PuzzleParams {
+    // name of the puzzle, ignoring case
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DailyPuzzleParams {
+    // date of the puzzle as "YYYY-MM-DD", in UTC, default: today
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LeaderboardParams {
+    // default: "fewest_moves"
+    pub ranking: Option<Ranking>,
+    // default: 10, max: 100
+    pub limit: Option<u32>,
+}
+
+// Solve of a puzzle on a board started from the puzzle, whose moves and
+// duration are taken from the board
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubmitScore {
+    pub player: String,
+    pub board_id: i32,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ShareCodeParams {
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SolutionHashParams {
+    // hash of the board's position, as used to search boards
+    pub hash: u64,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -19,11 +528,33 @@ pub struct ChangeState {
     pub new_state: BoardState,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct Wall {
+    pub row: u8,
+    pub col: u8,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetEmptyCells {
+    pub empty_cells: u8,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetTargets {
+    pub targets: Vec<Target>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AlterBoard {
+    AddWall(Wall),
+    Archive,
     ChangeState(ChangeState),
+    RemoveWall(Wall),
     Reset,
+    SetEmptyCells(SetEmptyCells),
+    SetMetadata(Metadata),
+    SetTargets(SetTargets),
     UndoMove,
 }
 
@@ -38,6 +569,25 @@ pub struct AddBlock {
     pub block: Block,
     pub min_row: u8,
     pub min_col: u8,
+    pub axis: Option<Axis>,
+    pub label: Option<String>,
+    pub color: Option<String>,
+}
+
+impl AddBlock {
+    pub fn positioned_block(&self) -> Option<PositionedBlock> {
+        PositionedBlock::new(self.block, self.min_row, self.min_col).map(|positioned_block| {
+            positioned_block
+                .with_axis(self.axis)
+                .with_display(self.label.clone(), self.color.clone())
+        })
+    }
+}
+
+// Blocks added in order, of which either all or none are added
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AddBlocks {
+    pub blocks: Vec<AddBlock>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -51,10 +601,69 @@ pub struct MoveBlock {
     pub col_diff: i8,
 }
 
+// Moves made in order, of which either all or none are made
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApplyMoves {
+    pub moves: Vec<FlatBoardMove>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MoveBlockSteps {
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetLocked {
+    pub locked: bool,
+}
+
+// Other blocks to link to the block in a new link group, where no blocks
+// unlinks the block from its group
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LinkBlocks {
+    pub block_idxs: Vec<usize>,
+}
+
+impl LinkBlocks {
+    // Edit linking every block, including the given block, or unlinking the
+    // given block if no other blocks are given
+    pub fn action(&self, block_idx: usize) -> EditAction {
+        if self.block_idxs.is_empty() {
+            return EditAction::UnlinkBlock { block_idx };
+        }
+
+        EditAction::LinkBlocks {
+            block_idxs: std::iter::once(block_idx)
+                .chain(self.block_idxs.iter().copied())
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 // #[schema(as = AlterBlock)]
 pub enum AlterBlock {
     ChangeBlock(ChangeBlock),
     MoveBlock(MoveBlock),
+    MoveBlockSteps(MoveBlockSteps),
+    RotateBlock,
+    SetLocked(SetLocked),
+    LinkBlocks(LinkBlocks),
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SessionMoveBlock {
+    pub block_idx: usize,
+    pub row_diff: i8,
+    pub col_diff: i8,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SessionCommand {
+    MoveBlock(SessionMoveBlock),
+    UndoMove,
+    Reset,
+    Solve(SolveParams),
 }
@@ -1,5 +1,7 @@
+use std::collections::HashSet;
+
 use axum::{
-    http::StatusCode,
+    http::{header::ETAG, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -7,56 +9,999 @@ use serde::Serialize;
 use utoipa::{ToResponse, ToSchema};
 
 use crate::models::game::{
-    blocks::{Block, Positioned as PositionedBlock},
-    board::{Board as Board_, State as BoardState},
-    moves::{FlatBoardMove, FlatMove},
+    blocks::Positioned as PositionedBlock,
+    board::{Board as Board_, Goal, NotReadyReason, State as BoardState, Target, Variant},
+    history::Edit,
+    layouts::Layout as Layout_,
+    moves::{DetailedMove, FlatBoardMove, FlatMove, Step},
+    notation,
+    utils::{unix_millis, Position},
+    validation::{Issue as LayoutIssue, Report},
+};
+use crate::repositories::{boards::Summary as BoardSummary_, solutions::Cached as CachedSolution_};
+use crate::services::{
+    analysis,
+    explorer::{Edge as GraphEdge, Graph as Graph_, Node as GraphNode},
+    jobs::{Job as Job_, Status as JobStatus},
+    leaderboards::{Ranking, Score as Score_},
+    shares::Share as Share_,
+    solver::{Algorithm, Progress},
 };
 
 #[derive(Debug, Serialize, ToResponse, ToSchema)]
 pub struct Board {
     id: i32,
+    name: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
     state: BoardState,
     blocks: Vec<PositionedBlock>,
-    grid: [Option<Block>; (Board_::COLS * Board_::ROWS) as usize],
-    next_moves: Vec<Vec<FlatMove>>,
+    // index in blocks of the block covering each cell, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grid: Option<[Option<usize>; (Board_::COLS * Board_::ROWS) as usize]>,
+    walls: Vec<Position>,
+    variant: Variant,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_moves: Option<Vec<Vec<FlatMove>>>,
+    // next moves of each block along with the steps taken by each move, if
+    // requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detailed_next_moves: Option<Vec<Vec<DetailedMove>>>,
+    goal: Goal,
+    targets: Vec<Target>,
+    empty_cells: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not_ready_reasons: Option<Vec<NotReadyReason>>,
+    move_count: usize,
+    // number of moves which took the board back to a position it had already
+    // been in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repetition_count: Option<usize>,
+    // whether the last move took the board back to a position it had already
+    // been in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repeated_position: Option<bool>,
+    started_solving_at: Option<u64>,
+    finished_at: Option<u64>,
+    // milliseconds spent solving the board so far
+    elapsed: Option<u64>,
+    // difficulty of solving the board from its starting position, known once
+    // it has been solved optimally
+    difficulty: Option<u32>,
+    // seed from which the board was randomly generated, if it was
+    seed: Option<u64>,
+    // number of times the board has been changed
+    version: u32,
+}
+
+// Optional field of a board response, which is only computed when included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BoardField {
+    Grid,
+    NextMoves,
+    Notation,
+    NotReadyReasons,
+    // both repetition_count and repeated_position
+    Repetitions,
+    // detailed_next_moves, which is only included if the detailed_moves query
+    // param is set
+    DetailedMoves,
+}
+
+impl BoardField {
+    const ALL: [Self; 5] = [
+        Self::Grid,
+        Self::NextMoves,
+        Self::Notation,
+        Self::NotReadyReasons,
+        Self::Repetitions,
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "grid" => Some(Self::Grid),
+            "next_moves" => Some(Self::NextMoves),
+            "notation" => Some(Self::Notation),
+            "not_ready_reasons" => Some(Self::NotReadyReasons),
+            "repetitions" => Some(Self::Repetitions),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardFields(HashSet<BoardField>);
+
+impl Default for BoardFields {
+    fn default() -> Self {
+        Self(BoardField::ALL.into_iter().collect())
+    }
+}
+
+impl BoardFields {
+    // Fields named in the comma separated include list, or every field if
+    // there is none, less the fields named in the exclude list, or the first
+    // name which is not that of an optional field
+    pub fn parse(include: Option<&str>, exclude: Option<&str>) -> Result<Self, String> {
+        fn fields(list: &str) -> Result<HashSet<BoardField>, String> {
+            list.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| BoardField::from_name(name).ok_or_else(|| name.to_string()))
+                .collect()
+        }
+
+        let mut included = match include {
+            Some(include) => fields(include)?,
+            None => Self::default().0,
+        };
+
+        if let Some(exclude) = exclude {
+            let excluded = fields(exclude)?;
+
+            included.retain(|field| !excluded.contains(field));
+        }
+
+        Ok(Self(included))
+    }
+
+    // Same fields, along with the given field
+    pub fn with(mut self, field: BoardField) -> Self {
+        self.0.insert(field);
+        self
+    }
+
+    pub fn includes(&self, field: BoardField) -> bool {
+        self.0.contains(&field)
+    }
 }
 
 impl Board {
-    pub fn new(mut board: Board_) -> Self {
-        let next_moves = board.get_next_moves();
+    pub fn new(board: Board_) -> Self {
+        Self::with_fields(board, &BoardFields::default())
+    }
+
+    pub fn with_fields(mut board: Board_, fields: &BoardFields) -> Self {
+        let next_moves = fields
+            .includes(BoardField::NextMoves)
+            .then(|| board.get_next_moves());
+        let detailed_next_moves = fields
+            .includes(BoardField::DetailedMoves)
+            .then(|| board.get_next_detailed_moves());
+        let notation = fields
+            .includes(BoardField::Notation)
+            .then(|| notation::serialize(&board));
+        let not_ready_reasons = fields
+            .includes(BoardField::NotReadyReasons)
+            .then(|| board.readiness_report());
+        let elapsed = board.elapsed(unix_millis());
+        let (repetition_count, repeated_position) = fields
+            .includes(BoardField::Repetitions)
+            .then(|| board.repetitions())
+            .unzip();
 
         Self {
             id: board.id,
+            name: board.name,
+            description: board.description,
+            tags: board.tags,
             state: board.state,
             blocks: board.blocks,
-            grid: board.grid,
+            grid: fields.includes(BoardField::Grid).then_some(board.grid),
+            walls: board.walls,
+            variant: board.variant,
             next_moves,
+            detailed_next_moves,
+            goal: board.goal,
+            targets: board.targets,
+            empty_cells: board.empty_cells,
+            notation,
+            not_ready_reasons,
+            move_count: board.moves.len(),
+            repetition_count,
+            repeated_position,
+            started_solving_at: board.started_solving_at,
+            finished_at: board.finished_at,
+            elapsed,
+            difficulty: board.difficulty,
+            seed: board.seed,
+            version: board.version,
         }
     }
 }
 
+// The board's ETag is its version, so that changes can be made conditional on
+// the board being unchanged using the If-Match header
 impl IntoResponse for Board {
+    fn into_response(self) -> Response {
+        let etag = format!("\"{}\"", self.version);
+
+        (StatusCode::OK, [(ETAG, etag)], Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Boards {
+    boards: Vec<Board>,
+}
+
+impl Boards {
+    pub fn new(boards: Vec<Board>) -> Self {
+        Self { boards }
+    }
+}
+
+impl IntoResponse for Boards {
     fn into_response(self) -> Response {
         (StatusCode::OK, Json(self)).into_response()
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionStatus {
+    Deleted,
+    NotFound,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardDeletion {
+    board_id: i32,
+    status: DeletionStatus,
+}
+
 #[derive(Debug, Serialize, ToResponse, ToSchema)]
-pub struct Solved {
+pub struct BoardDeletions {
+    num_deleted: usize,
+    // result for each board, in the order the ids were given
+    results: Vec<BoardDeletion>,
+}
+
+impl BoardDeletions {
+    pub fn new(board_ids: &[i32], deleted_ids: &[i32]) -> Self {
+        Self {
+            num_deleted: deleted_ids.len(),
+            results: board_ids
+                .iter()
+                .map(|&board_id| BoardDeletion {
+                    board_id,
+                    status: if deleted_ids.contains(&board_id) {
+                        DeletionStatus::Deleted
+                    } else {
+                        DeletionStatus::NotFound
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl IntoResponse for BoardDeletions {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BoardMetadata {
+    name: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
+}
+
+// Self-contained document of the board, which is accepted as is when
+// importing the board
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct ExportedBoard {
+    // blocks of the board's starting position
+    blocks: Vec<PositionedBlock>,
+    walls: Vec<Position>,
+    variant: Variant,
+    goal: Goal,
+    targets: Vec<Target>,
+    empty_cells: u8,
+    // moves made from the starting position, oldest first
     moves: Vec<FlatBoardMove>,
+    state: BoardState,
+    metadata: BoardMetadata,
+    // cached optimal solution from the board's current position, if any,
+    // which is ignored when importing the board
+    solution: Option<Vec<FlatBoardMove>>,
 }
 
-impl Solved {
+impl ExportedBoard {
+    pub fn new(board: Board_, solution: Option<Vec<FlatBoardMove>>) -> Self {
+        let start = board.at_move(0).unwrap();
+
+        Self {
+            blocks: start.blocks,
+            walls: board.walls,
+            variant: board.variant,
+            goal: board.goal,
+            targets: board.targets,
+            empty_cells: board.empty_cells,
+            moves: board.moves,
+            state: board.state,
+            metadata: BoardMetadata {
+                name: board.name,
+                description: board.description,
+                tags: board.tags,
+            },
+            solution,
+        }
+    }
+}
+
+impl IntoResponse for ExportedBoard {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct BoardSummary {
+    id: i32,
+    state: BoardState,
+    num_blocks: usize,
+    // time, in milliseconds since the Unix epoch, at which the board was
+    // created, unknown for boards created before it was recorded
+    created_at: Option<u64>,
+}
+
+impl BoardSummary {
+    pub fn new(summary: BoardSummary_) -> Self {
+        Self {
+            id: summary.id,
+            state: summary.state,
+            num_blocks: summary.num_blocks,
+            created_at: summary.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct BoardSummaries {
+    boards: Vec<BoardSummary>,
+}
+
+impl BoardSummaries {
+    pub fn new(boards: Vec<BoardSummary>) -> Self {
+        Self { boards }
+    }
+}
+
+impl IntoResponse for BoardSummaries {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct History {
+    // changes made to the board's blocks while building it, oldest first
+    edits: Vec<Edit>,
+}
+
+impl History {
+    pub fn new(edits: Vec<Edit>) -> Self {
+        Self { edits }
+    }
+}
+
+impl IntoResponse for History {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct BoardActivity {
+    // time, in milliseconds since the Unix epoch, at which the board was last
+    // active
+    last_active_at: u64,
+    // time, in milliseconds since the Unix epoch, at which the board expires
+    // unless it is active again, if boards expire
+    expires_at: Option<u64>,
+}
+
+impl BoardActivity {
+    pub fn new(last_active_at: u64, expires_at: Option<u64>) -> Self {
+        Self {
+            last_active_at,
+            expires_at,
+        }
+    }
+}
+
+impl IntoResponse for BoardActivity {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Moves {
+    // moves made on the board, oldest first
+    moves: Vec<FlatBoardMove>,
+}
+
+impl Moves {
     pub fn new(moves: Vec<FlatBoardMove>) -> Self {
         Self { moves }
     }
 }
 
+impl IntoResponse for Moves {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// Move made on the board, along with when it was made
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReplayMove {
+    #[serde(rename = "move")]
+    move_: FlatBoardMove,
+    // time, in milliseconds since the Unix epoch, at which the move was made,
+    // or null if it is not known
+    at: Option<u64>,
+    // milliseconds since the board started being solved when the move was made
+    elapsed_ms: Option<u64>,
+    // notation of the board after the move, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Replay {
+    board_id: i32,
+    // notation of the board's starting position
+    start: String,
+    started_solving_at: Option<u64>,
+    finished_at: Option<u64>,
+    // moves made on the board, oldest first
+    moves: Vec<ReplayMove>,
+}
+
+impl Replay {
+    pub fn new(board: &Board_, start: Board_, snapshots: bool) -> Self {
+        let mut current = start;
+        let start = notation::serialize(&current);
+
+        let moves = board
+            .moves
+            .iter()
+            .enumerate()
+            .map(|(move_idx, move_)| {
+                let at = board.move_times.get(move_idx).copied().flatten();
+
+                let snapshot = snapshots.then(|| {
+                    current.move_block_unchecked(move_.block_idx, move_.row_diff, move_.col_diff);
+                    notation::serialize(&current)
+                });
+
+                ReplayMove {
+                    move_: move_.clone(),
+                    at,
+                    elapsed_ms: at
+                        .zip(board.started_solving_at)
+                        .map(|(at, started)| at.saturating_sub(started)),
+                    snapshot,
+                }
+            })
+            .collect();
+
+        Self {
+            board_id: board.id,
+            start,
+            started_solving_at: board.started_solving_at,
+            finished_at: board.finished_at,
+            moves,
+        }
+    }
+}
+
+impl IntoResponse for Replay {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// Next move of a block, along with the steps taken by the move if requested
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NextMove {
+    block_idx: usize,
+    row_diff: i8,
+    col_diff: i8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steps: Option<Vec<Step>>,
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct NextMoves {
+    next_moves: Vec<NextMove>,
+}
+
+impl NextMoves {
+    // Next moves of the board, or only of the given block if any, along with
+    // the steps taken by each move if detailed
+    pub fn new(mut board: Board_, block_idx: Option<usize>, detailed: bool) -> Self {
+        // plain moves are given without steps
+        let moves = if detailed {
+            board.get_next_detailed_moves()
+        } else {
+            board
+                .get_next_moves()
+                .into_iter()
+                .map(|moves| {
+                    moves
+                        .into_iter()
+                        .map(|move_| DetailedMove {
+                            row_diff: move_.row_diff,
+                            col_diff: move_.col_diff,
+                            steps: vec![],
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+
+        let next_moves = moves
+            .into_iter()
+            .enumerate()
+            .filter(|&(other_idx, _)| block_idx.is_none() || block_idx == Some(other_idx))
+            .flat_map(|(other_idx, moves)| {
+                moves.into_iter().map(move |move_| NextMove {
+                    block_idx: other_idx,
+                    row_diff: move_.row_diff,
+                    col_diff: move_.col_diff,
+                    steps: detailed.then_some(move_.steps),
+                })
+            })
+            .collect();
+
+        Self { next_moves }
+    }
+}
+
+impl IntoResponse for NextMoves {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct HardestBoards {
+    // length of the optimal solution of each board
+    distance: usize,
+    boards: Vec<Board>,
+}
+
+impl HardestBoards {
+    pub fn new(distance: usize, boards: Vec<Board>) -> Self {
+        Self { distance, boards }
+    }
+}
+
+impl IntoResponse for HardestBoards {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Layout {
+    name: String,
+    // length of the optimal solution of the layout
+    optimal_moves: usize,
+    notation: String,
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Layouts {
+    layouts: Vec<Layout>,
+}
+
+impl Layouts {
+    pub fn new(layouts: &[Layout_]) -> Self {
+        Self {
+            layouts: layouts
+                .iter()
+                .map(|layout| Layout {
+                    name: layout.name.to_string(),
+                    optimal_moves: layout.optimal_moves,
+                    notation: layout.notation.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl IntoResponse for Layouts {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// Puzzle of the catalog, which can be started as a new board
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Puzzle {
+    name: String,
+    notation: String,
+    // length of the optimal solution of the puzzle
+    optimal_moves: usize,
+    // difficulty of the optimal solution, as recorded for solved boards
+    difficulty: u32,
+}
+
+impl Puzzle {
+    pub fn new(layout: &Layout_) -> Self {
+        Self {
+            name: layout.name.to_string(),
+            notation: layout.notation.to_string(),
+            optimal_moves: layout.optimal_moves,
+            difficulty: layout.difficulty,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Puzzles {
+    puzzles: Vec<Puzzle>,
+}
+
+impl Puzzles {
+    pub fn new(layouts: &[Layout_]) -> Self {
+        Self {
+            puzzles: layouts.iter().map(Puzzle::new).collect(),
+        }
+    }
+}
+
+impl IntoResponse for Puzzles {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// Puzzle of the day, which is the same for every user
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct DailyPuzzle {
+    // date of the puzzle as "YYYY-MM-DD", in UTC
+    date: String,
+    seed: u64,
+    notation: String,
+    // length of the optimal solution of the puzzle
+    optimal_moves: usize,
+    // difficulty of the optimal solution, as recorded for solved boards
+    difficulty: u32,
+}
+
+impl DailyPuzzle {
+    pub fn new(date: String, board: &Board_, solution: &[FlatBoardMove]) -> Self {
+        Self {
+            date,
+            seed: board.seed.unwrap_or_default(),
+            notation: notation::serialize(board),
+            optimal_moves: solution.len(),
+            difficulty: analysis::difficulty(board, solution),
+        }
+    }
+}
+
+impl IntoResponse for DailyPuzzle {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Score {
+    id: i32,
+    puzzle: String,
+    player: String,
+    num_moves: usize,
+    duration_ms: u64,
+    // time, in milliseconds since the Unix epoch, the score was submitted
+    created_at: u64,
+}
+
+impl Score {
+    pub fn new(score: Score_) -> Self {
+        Self {
+            id: score.id,
+            puzzle: score.puzzle,
+            player: score.player,
+            num_moves: score.num_moves,
+            duration_ms: score.duration_ms,
+            created_at: score.created_at,
+        }
+    }
+}
+
+impl IntoResponse for Score {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Leaderboard {
+    puzzle: String,
+    ranking: Ranking,
+    // best scores, in the order of the ranking
+    scores: Vec<Score>,
+}
+
+impl Leaderboard {
+    pub fn new(puzzle: String, ranking: Ranking, scores: Vec<Score_>) -> Self {
+        Self {
+            puzzle,
+            ranking,
+            scores: scores.into_iter().map(Score::new).collect(),
+        }
+    }
+}
+
+impl IntoResponse for Leaderboard {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// Starting layout of a board shared by its code
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Share {
+    code: String,
+    notation: String,
+    name: Option<String>,
+    // time, in milliseconds since the Unix epoch, the board was shared
+    created_at: u64,
+}
+
+impl Share {
+    pub fn new(share: Share_) -> Self {
+        Self {
+            code: share.code,
+            notation: share.notation,
+            name: share.name,
+            created_at: share.created_at,
+        }
+    }
+}
+
+impl IntoResponse for Share {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+// Optimal solution cached for a board with the given goal, as found when the
+// board was solved
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CachedSolution {
+    goal: Goal,
+    // blocks of the board the solution was cached for, in the order the
+    // moves index into
+    blocks: Vec<PositionedBlock>,
+    // moves of the solution, or null if the board has no solution
+    moves: Option<Vec<FlatBoardMove>>,
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct CachedSolutions {
+    hash: u64,
+    solutions: Vec<CachedSolution>,
+}
+
+impl CachedSolutions {
+    pub fn new(hash: u64, solutions: Vec<CachedSolution_>) -> Self {
+        Self {
+            hash,
+            solutions: solutions
+                .into_iter()
+                .map(|solution| CachedSolution {
+                    goal: solution.goal,
+                    blocks: solution.blocks,
+                    moves: solution.moves,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl IntoResponse for CachedSolutions {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AlgorithmRun {
+    algorithm: Algorithm,
+    // wall time of the run in milliseconds
+    elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nodes_expanded: Option<usize>,
+    // number of flattened moves in the solution found, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_moves: Option<usize>,
+    // reason the run stopped without finishing, e.g. a timeout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl AlgorithmRun {
+    pub fn new(
+        algorithm: Algorithm,
+        elapsed_ms: u64,
+        nodes_expanded: Option<usize>,
+        num_moves: Option<usize>,
+        error: Option<String>,
+    ) -> Self {
+        Self {
+            algorithm,
+            elapsed_ms,
+            nodes_expanded,
+            num_moves,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct AlgorithmComparison {
+    board_id: i32,
+    runs: Vec<AlgorithmRun>,
+}
+
+impl AlgorithmComparison {
+    pub fn new(board_id: i32, runs: Vec<AlgorithmRun>) -> Self {
+        Self { board_id, runs }
+    }
+}
+
+impl IntoResponse for AlgorithmComparison {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Solved {
+    moves: Vec<FlatBoardMove>,
+    // number of flattened moves in the solution
+    num_moves: usize,
+    // number of flattened moves in the solution before consecutive moves of
+    // the same block were merged
+    num_raw_moves: usize,
+    // number of unit steps in the solution, where an L-shaped move counts twice
+    num_steps: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<u64>,
+    // whether the solution is guaranteed to be optimal
+    optimal: bool,
+    // whether the maximum depth was exceeded, in which case the moves only
+    // lead to the board closest to the goal
+    truncated: bool,
+    // index in the solution of the first of the moves, if only a page of the
+    // solution's moves was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+    // grid of the board after moves of the solution, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshots: Option<Vec<Snapshot>>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = SolutionSnapshot)]
+pub struct Snapshot {
+    // number of moves of the solution made before the snapshot was taken
+    after_move: usize,
+    // index in the board's blocks of the block covering each cell, if any
+    grid: [Option<usize>; (Board_::COLS * Board_::ROWS) as usize],
+}
+
+impl Solved {
+    pub fn new(
+        moves: Vec<FlatBoardMove>,
+        num_raw_moves: usize,
+        count: Option<u64>,
+        optimal: bool,
+        truncated: bool,
+    ) -> Self {
+        Self {
+            num_moves: moves.len(),
+            num_raw_moves,
+            num_steps: moves.iter().map(FlatBoardMove::steps).sum(),
+            moves,
+            count,
+            optimal,
+            truncated,
+            offset: None,
+            snapshots: None,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToResponse, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Solution {
     Solved(Solved),
     UnableToSolve,
+    BudgetExceeded,
+    TimedOut,
+}
+
+impl Solution {
+    // Keep only the moves of the solution from the offset on, up to the limit
+    // if one is given, while the rest of the solution still describes every
+    // move, so that long solutions can be fetched lazily. Only the snapshots
+    // taken after the kept moves are kept.
+    pub fn page(self, offset: usize, limit: Option<usize>) -> Self {
+        match self {
+            Self::Solved(solved) => {
+                let moves: Vec<FlatBoardMove> = solved
+                    .moves
+                    .into_iter()
+                    .skip(offset)
+                    .take(limit.unwrap_or(usize::MAX))
+                    .collect();
+
+                let end = offset + moves.len();
+
+                let snapshots = solved.snapshots.map(|snapshots| {
+                    snapshots
+                        .into_iter()
+                        .filter(|snapshot| {
+                            snapshot.after_move > offset && snapshot.after_move <= end
+                        })
+                        .collect()
+                });
+
+                Self::Solved(Solved {
+                    moves,
+                    offset: Some(offset),
+                    snapshots,
+                    ..solved
+                })
+            }
+            _ => self,
+        }
+    }
+
+    // Record the grid of the board after every given number of moves of the
+    // solution, made from the given board, and after the last move, so that
+    // the solution can be animated without making its moves
+    pub fn with_snapshots(self, board: &Board_, every: usize) -> Self {
+        match self {
+            Self::Solved(solved) => {
+                let mut board = board.clone();
+                let mut snapshots = vec![];
+
+                for (move_idx, move_) in solved.moves.iter().enumerate() {
+                    board.move_block_unchecked(move_.block_idx, move_.row_diff, move_.col_diff);
+
+                    let after_move = move_idx + 1;
+
+                    if after_move % every.max(1) == 0 || after_move == solved.moves.len() {
+                        snapshots.push(Snapshot {
+                            after_move,
+                            grid: board.grid,
+                        });
+                    }
+                }
+
+                Self::Solved(Solved {
+                    snapshots: Some(snapshots),
+                    ..solved
+                })
+            }
+            _ => self,
+        }
+    }
 }
 
 impl IntoResponse for Solution {
@@ -64,3 +1009,191 @@ impl IntoResponse for Solution {
         (StatusCode::OK, Json(self)).into_response()
     }
 }
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct HintedMoves {
+    next_move: FlatBoardMove,
+    // the next moves of the optimal solution, starting with next_move
+    next_moves: Vec<FlatBoardMove>,
+    // number of moves remaining in the optimal solution, including next_move
+    distance: usize,
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Hint {
+    NextMove(HintedMoves),
+    AlreadySolved,
+    UnableToSolve,
+    BudgetExceeded,
+    TimedOut,
+}
+
+impl Hint {
+    pub fn new(solution: Solution, count: usize) -> Self {
+        match solution {
+            Solution::Solved(Solved { moves, .. }) => match moves.first() {
+                Some(next_move) => Self::NextMove(HintedMoves {
+                    next_move: next_move.clone(),
+                    next_moves: moves.iter().take(count.max(1)).cloned().collect(),
+                    distance: moves.len(),
+                }),
+                None => Self::AlreadySolved,
+            },
+            Solution::UnableToSolve => Self::UnableToSolve,
+            Solution::BudgetExceeded => Self::BudgetExceeded,
+            Solution::TimedOut => Self::TimedOut,
+        }
+    }
+}
+
+impl IntoResponse for Hint {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Estimate {
+    min_moves: usize,
+}
+
+impl Estimate {
+    pub fn new(min_moves: usize) -> Self {
+        Self { min_moves }
+    }
+}
+
+impl IntoResponse for Estimate {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct BoardDiff {
+    // move taking each block which changed position to its new position
+    moves: Vec<FlatBoardMove>,
+}
+
+impl BoardDiff {
+    pub fn new(moves: Vec<FlatBoardMove>) -> Self {
+        Self { moves }
+    }
+}
+
+impl IntoResponse for BoardDiff {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Solvable {
+    // unknown if the check ran out of time or node budget before finding out
+    solvable: Option<bool>,
+}
+
+impl Solvable {
+    pub fn new(solvable: Option<bool>) -> Self {
+        Self { solvable }
+    }
+}
+
+impl IntoResponse for Solvable {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Validation {
+    // whether a board can be created from the layout and solved as is
+    valid: bool,
+    issues: Vec<LayoutIssue>,
+    // number of blocks of the goal block's shape
+    goal_block_count: usize,
+    not_ready_reasons: Vec<NotReadyReason>,
+}
+
+impl Validation {
+    pub fn new(report: Report) -> Self {
+        Self {
+            valid: report.is_valid(),
+            issues: report.issues,
+            goal_block_count: report.goal_block_count,
+            not_ready_reasons: report.not_ready_reasons,
+        }
+    }
+}
+
+impl IntoResponse for Validation {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Job {
+    id: i32,
+    board_id: i32,
+    status: JobStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    moves: Option<Vec<FlatBoardMove>>,
+    // whether the solution is guaranteed to be optimal, if there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    optimal: Option<bool>,
+}
+
+impl Job {
+    pub fn new(job: Job_) -> Self {
+        Self {
+            id: job.id,
+            board_id: job.board_id,
+            status: job.status,
+            optimal: job
+                .moves
+                .as_ref()
+                .map(|_| job.params.options().is_optimal()),
+            moves: job.moves,
+        }
+    }
+}
+
+impl IntoResponse for Job {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToResponse, ToSchema)]
+pub struct Graph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+    truncated: bool,
+}
+
+impl Graph {
+    pub fn new(graph: Graph_) -> Self {
+        Self {
+            nodes: graph.nodes,
+            edges: graph.edges,
+            truncated: graph.truncated,
+        }
+    }
+}
+
+impl IntoResponse for Graph {
+    fn into_response(self) -> Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum SessionEvent {
+    Board(Box<Board>),
+    Progress(Progress),
+    Solution(Solution),
+    Error(String),
+}
@@ -1,13 +1,107 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
+        rate_limit -> Nullable<Int4>,
+        expensive_rate_limit -> Nullable<Int4>,
+        requests -> Int8,
+        last_used_at -> Nullable<Int8>,
+        revoked -> Bool,
+        admin -> Bool,
+    }
+}
+
 diesel::table! {
     boards (id) {
         id -> Int4,
         #[max_length = 20]
         state -> Varchar,
         blocks -> Text,
-        grid -> Text,
         moves -> Text,
+        goal -> Text,
+        walls -> Text,
+        empty_cells -> Int2,
+        started_solving_at -> Nullable<Int8>,
+        finished_at -> Nullable<Int8>,
+        version -> Int4,
+        targets -> Text,
+        #[max_length = 100]
+        name -> Nullable<Varchar>,
+        description -> Nullable<Text>,
+        tags -> Array<Text>,
+        difficulty -> Nullable<Int4>,
+        edits -> Text,
+        seed -> Nullable<Int8>,
+        start_hash -> Nullable<Int8>,
+        created_at -> Nullable<Int8>,
+        last_active_at -> Int8,
+        move_times -> Text,
+        variant -> Text,
+    }
+}
+
+diesel::table! {
+    distances (hash) {
+        hash -> Int8,
+        distance -> Int4,
+    }
+}
+
+diesel::table! {
+    idempotency_keys (key, api_key_id) {
+        #[max_length = 255]
+        key -> Varchar,
+        api_key_id -> Int4,
+        #[max_length = 10]
+        method -> Varchar,
+        path -> Text,
+        status -> Nullable<Int2>,
+        headers -> Nullable<Text>,
+        body -> Nullable<Bytea>,
+        created_at -> Int8,
+        #[max_length = 64]
+        fingerprint -> Varchar,
+    }
+}
+
+diesel::table! {
+    jobs (id) {
+        id -> Int4,
+        board_id -> Int4,
+        #[max_length = 20]
+        status -> Varchar,
+        params -> Text,
+        moves -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    scores (id) {
+        id -> Int4,
+        #[max_length = 100]
+        puzzle -> Varchar,
+        #[max_length = 50]
+        player -> Varchar,
+        num_moves -> Int4,
+        duration_ms -> Int8,
+        created_at -> Int8,
+        board_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    shares (code) {
+        #[max_length = 16]
+        code -> Varchar,
+        notation -> Text,
+        #[max_length = 100]
+        name -> Nullable<Varchar>,
+        created_at -> Int8,
     }
 }
 
@@ -16,7 +110,18 @@ diesel::table! {
         id -> Int4,
         hash -> Int8,
         moves -> Nullable<Text>,
+        goal -> Text,
+        blocks -> Text,
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(boards, solutions,);
+diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    boards,
+    distances,
+    idempotency_keys,
+    jobs,
+    scores,
+    shares,
+    solutions,
+);
@@ -1,23 +1,69 @@
 use diesel::prelude::*;
 
-use crate::models::game::{board::Board, moves::FlatBoardMove};
+use crate::models::game::{
+    blocks::Positioned as PositionedBlock,
+    board::{Board, Goal},
+    moves::FlatBoardMove,
+};
+use crate::services::api_keys::ApiKey;
+use crate::services::idempotency::{Record as IdempotencyRecord, StoredResponse};
+use crate::services::jobs::{Job, Status as JobStatus};
+use crate::services::leaderboards::Score;
+use crate::services::shares::Share;
 
 #[derive(Debug, Insertable, AsChangeset)]
 #[diesel(table_name = super::schema::boards)]
 pub struct InsertableBoard {
     pub state: String,
     pub blocks: String,
-    pub grid: String,
     pub moves: String,
+    pub goal: String,
+    pub walls: String,
+    pub empty_cells: i16,
+    pub started_solving_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub version: i32,
+    pub targets: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub difficulty: Option<i32>,
+    pub edits: String,
+    pub seed: Option<i64>,
+    pub start_hash: i64,
+    pub move_times: String,
+    pub variant: String,
 }
 
+#[allow(clippy::cast_possible_wrap)]
 impl InsertableBoard {
     pub fn from(board: &Board) -> Self {
         Self {
             state: serde_json::to_string(&board.state).unwrap(),
             blocks: serde_json::to_string(&board.blocks).unwrap(),
-            grid: serde_json::to_string(&board.grid).unwrap(),
             moves: serde_json::to_string(&board.moves).unwrap(),
+            goal: serde_json::to_string(&board.goal).unwrap(),
+            walls: serde_json::to_string(&board.walls).unwrap(),
+            empty_cells: i16::from(board.empty_cells),
+            started_solving_at: board
+                .started_solving_at
+                .map(|time| i64::try_from(time).unwrap()),
+            finished_at: board.finished_at.map(|time| i64::try_from(time).unwrap()),
+            version: i32::try_from(board.version).unwrap(),
+            targets: serde_json::to_string(&board.targets).unwrap(),
+            name: board.name.clone(),
+            description: board.description.clone(),
+            tags: board.tags.clone(),
+            difficulty: board
+                .difficulty
+                .map(|difficulty| i32::try_from(difficulty).unwrap()),
+            edits: serde_json::to_string(&board.edits).unwrap(),
+            // seeds above i64::MAX are stored as negative numbers with the
+            // same bits
+            seed: board.seed.map(|seed| seed as i64),
+            start_hash: board.start_hash() as i64,
+            move_times: serde_json::to_string(&board.move_times).unwrap(),
+            variant: serde_json::to_string(&board.variant).unwrap(),
         }
     }
 }
@@ -28,35 +74,101 @@ pub struct SelectableBoard {
     pub id: i32,
     pub state: String,
     pub blocks: String,
-    pub grid: String,
     pub moves: String,
+    pub goal: String,
+    pub walls: String,
+    pub empty_cells: i16,
+    pub started_solving_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub version: i32,
+    pub targets: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub difficulty: Option<i32>,
+    pub edits: String,
+    pub seed: Option<i64>,
+    pub move_times: String,
+    pub variant: String,
 }
 
 impl SelectableBoard {
+    #[allow(clippy::cast_sign_loss)]
     pub fn into_board(self) -> Board {
-        Board::new(
+        let blocks: Vec<PositionedBlock> = serde_json::from_str(self.blocks.as_str()).unwrap();
+        let grid = Board::grid_from_blocks(&blocks);
+
+        let board = Board::new(
             self.id,
             serde_json::from_str(self.state.as_str()).unwrap(),
-            serde_json::from_str(self.blocks.as_str()).unwrap(),
-            serde_json::from_str(self.grid.as_str()).unwrap(),
+            blocks,
+            grid,
+            serde_json::from_str(self.walls.as_str()).unwrap(),
             serde_json::from_str(self.moves.as_str()).unwrap(),
-        )
+            serde_json::from_str(self.goal.as_str()).unwrap(),
+        );
+
+        Board {
+            empty_cells: u8::try_from(self.empty_cells).unwrap(),
+            started_solving_at: self
+                .started_solving_at
+                .map(|time| u64::try_from(time).unwrap()),
+            finished_at: self.finished_at.map(|time| u64::try_from(time).unwrap()),
+            version: u32::try_from(self.version).unwrap(),
+            targets: serde_json::from_str(self.targets.as_str()).unwrap(),
+            name: self.name,
+            description: self.description,
+            tags: self.tags,
+            difficulty: self
+                .difficulty
+                .map(|difficulty| u32::try_from(difficulty).unwrap()),
+            edits: serde_json::from_str(self.edits.as_str()).unwrap(),
+            seed: self.seed.map(|seed| seed as u64),
+            move_times: serde_json::from_str(self.move_times.as_str()).unwrap(),
+            variant: serde_json::from_str(self.variant.as_str()).unwrap(),
+            ..board
+        }
     }
 }
 
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::boards)]
+pub struct SelectableBoardSummary {
+    pub id: i32,
+    pub state: String,
+    pub blocks: String,
+    pub created_at: Option<i64>,
+}
+
 #[derive(Debug, Insertable)]
 #[diesel(table_name = super::schema::solutions)]
 pub struct InsertableSolution {
     pub hash: i64,
     pub moves: Option<String>,
+    pub goal: String,
+    pub blocks: String,
 }
 
 #[allow(clippy::cast_possible_wrap)]
 impl InsertableSolution {
-    pub fn from(hash: u64, moves: Option<Vec<FlatBoardMove>>) -> Self {
+    // Solution of the board, along with its blocks, in the order the moves
+    // index into, without their labels and colors
+    pub fn from(board: &Board, moves: Option<Vec<FlatBoardMove>>) -> Self {
+        let blocks: Vec<PositionedBlock> = board
+            .blocks
+            .iter()
+            .map(|positioned_block| PositionedBlock {
+                label: None,
+                color: None,
+                ..positioned_block.clone()
+            })
+            .collect();
+
         Self {
-            hash: hash as i64,
+            hash: board.hash() as i64,
             moves: moves.map(|moves| serde_json::to_string(&moves).unwrap()),
+            goal: serde_json::to_string(&board.goal).unwrap(),
+            blocks: serde_json::to_string(&blocks).unwrap(),
         }
     }
 }
@@ -67,11 +179,235 @@ pub struct SelectableSolution {
     pub id: i32,
     pub hash: i64,
     pub moves: Option<String>,
+    pub goal: String,
+    pub blocks: String,
 }
 
 impl SelectableSolution {
+    pub fn get_goal(&self) -> Goal {
+        serde_json::from_str(self.goal.as_str()).unwrap()
+    }
+
+    pub fn get_blocks(&self) -> Vec<PositionedBlock> {
+        serde_json::from_str(self.blocks.as_str()).unwrap()
+    }
+
     pub fn get_moves(self) -> Option<Vec<FlatBoardMove>> {
         self.moves
             .map(|moves| serde_json::from_str(moves.as_str()).unwrap())
     }
 }
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = super::schema::distances)]
+pub struct InsertableDistance {
+    pub hash: i64,
+    pub distance: i32,
+}
+
+#[allow(clippy::cast_possible_wrap)]
+impl InsertableDistance {
+    pub fn from(hash: u64, distance: usize) -> Self {
+        Self {
+            hash: hash as i64,
+            distance: i32::try_from(distance).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::distances)]
+pub struct SelectableDistance {
+    pub hash: i64,
+    pub distance: i32,
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl SelectableDistance {
+    pub fn into_entry(self) -> (u64, usize) {
+        (self.hash as u64, usize::try_from(self.distance).unwrap())
+    }
+}
+
+#[derive(Debug, Insertable, AsChangeset)]
+#[diesel(table_name = super::schema::jobs)]
+pub struct InsertableJob {
+    pub board_id: i32,
+    pub status: String,
+    pub params: String,
+    pub moves: Option<String>,
+}
+
+impl InsertableJob {
+    pub fn from(job: &Job) -> Self {
+        Self {
+            board_id: job.board_id,
+            status: serde_json::to_string(&job.status).unwrap(),
+            params: serde_json::to_string(&job.params).unwrap(),
+            moves: job
+                .moves
+                .as_ref()
+                .map(|moves| serde_json::to_string(moves).unwrap()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::jobs)]
+pub struct SelectableJob {
+    pub id: i32,
+    pub board_id: i32,
+    pub status: String,
+    pub params: String,
+    pub moves: Option<String>,
+}
+
+impl SelectableJob {
+    pub fn into_job(self) -> Job {
+        Job {
+            id: self.id,
+            board_id: self.board_id,
+            status: serde_json::from_str::<JobStatus>(self.status.as_str()).unwrap(),
+            params: serde_json::from_str(self.params.as_str()).unwrap(),
+            moves: self
+                .moves
+                .map(|moves| serde_json::from_str(moves.as_str()).unwrap()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::api_keys)]
+pub struct SelectableApiKey {
+    pub id: i32,
+    pub name: String,
+    pub rate_limit: Option<i32>,
+    pub expensive_rate_limit: Option<i32>,
+    pub requests: i64,
+    pub last_used_at: Option<i64>,
+    pub admin: bool,
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl SelectableApiKey {
+    pub fn into_api_key(self) -> ApiKey {
+        ApiKey {
+            id: self.id,
+            name: self.name,
+            rate_limit: self.rate_limit.map(|limit| limit as u32),
+            expensive_rate_limit: self.expensive_rate_limit.map(|limit| limit as u32),
+            requests: self.requests as u64,
+            last_used_at: self.last_used_at.map(|at| at as u64),
+            admin: self.admin,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = super::schema::idempotency_keys)]
+pub struct InsertableIdempotencyKey<'a> {
+    pub key: &'a str,
+    pub api_key_id: i32,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub created_at: i64,
+    pub fingerprint: &'a str,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::idempotency_keys)]
+pub struct SelectableIdempotencyKey {
+    pub method: String,
+    pub path: String,
+    pub fingerprint: String,
+    pub status: Option<i16>,
+    pub headers: Option<String>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl SelectableIdempotencyKey {
+    pub fn into_record(self) -> IdempotencyRecord {
+        let response = match (self.status, self.headers, self.body) {
+            (Some(status), Some(headers), Some(body)) => Some(StoredResponse {
+                status: status as u16,
+                headers: serde_json::from_str(headers.as_str()).unwrap(),
+                body,
+            }),
+            _ => None,
+        };
+
+        IdempotencyRecord {
+            method: self.method,
+            path: self.path,
+            fingerprint: self.fingerprint,
+            response,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = super::schema::scores)]
+pub struct InsertableScore<'a> {
+    pub puzzle: &'a str,
+    pub player: &'a str,
+    pub num_moves: i32,
+    pub duration_ms: i64,
+    pub created_at: i64,
+    pub board_id: i32,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::scores)]
+pub struct SelectableScore {
+    pub id: i32,
+    pub puzzle: String,
+    pub player: String,
+    pub num_moves: i32,
+    pub duration_ms: i64,
+    pub created_at: i64,
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl SelectableScore {
+    pub fn into_score(self) -> Score {
+        Score {
+            id: self.id,
+            puzzle: self.puzzle,
+            player: self.player,
+            num_moves: usize::try_from(self.num_moves).unwrap(),
+            duration_ms: self.duration_ms as u64,
+            created_at: self.created_at as u64,
+        }
+    }
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = super::schema::shares)]
+pub struct InsertableShare<'a> {
+    pub code: &'a str,
+    pub notation: &'a str,
+    pub name: Option<&'a str>,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Selectable, Queryable)]
+#[diesel(table_name = super::schema::shares)]
+pub struct SelectableShare {
+    pub code: String,
+    pub notation: String,
+    pub name: Option<String>,
+    pub created_at: i64,
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl SelectableShare {
+    pub fn into_share(self) -> Share {
+        Share {
+            code: self.code,
+            notation: self.notation,
+            name: self.name,
+            created_at: self.created_at as u64,
+        }
+    }
+}
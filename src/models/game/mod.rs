@@ -1,4 +0,0 @@
-pub mod blocks;
-pub mod board;
-pub mod moves;
-pub mod utils;
@@ -1,3 +1,4 @@
+pub use klotski_core::game;
+
 pub mod api;
 pub mod db;
-pub mod game;
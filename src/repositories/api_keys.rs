@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::db::schema::api_keys::dsl::{
+    api_keys, id, key_hash, last_used_at, requests, revoked,
+};
+use crate::models::db::tables::SelectableApiKey;
+use crate::services::api_keys::{ApiKey, Uses};
+use crate::services::db::Pool as DbPool;
+
+// Key with the given hash, if it exists and has not been revoked
+pub fn find(search_hash: &str, pool: &DbPool) -> Result<Option<ApiKey>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let api_key = api_keys
+        .filter(key_hash.eq(search_hash))
+        .filter(revoked.eq(false))
+        .select(SelectableApiKey::as_select())
+        .first(&mut conn)
+        .optional()?
+        .map(SelectableApiKey::into_api_key);
+
+    Ok(api_key)
+}
+
+// Add the uses of each key to its number of requests and time of its latest
+// request, in a single transaction
+#[allow(clippy::cast_possible_wrap)]
+pub fn add_uses(uses: &HashMap<i32, Uses>, pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    conn.transaction(|conn| {
+        for (api_key_id, key_uses) in uses {
+            diesel::update(api_keys.filter(id.eq(api_key_id)))
+                .set((
+                    requests.eq(requests + key_uses.requests as i64),
+                    last_used_at.eq(key_uses.last_used_at as i64),
+                ))
+                .execute(conn)?;
+        }
+
+        Ok(())
+    })
+}
@@ -1,12 +1,22 @@
-use diesel::prelude::*;
+use diesel::{pg::Pg, prelude::*};
 
 use crate::errors::board::Error as BoardError;
-use crate::models::db::schema::boards::dsl::{boards, id};
+use crate::models::db::schema::boards::dsl::{
+    boards, difficulty, goal, id, last_active_at, name, start_hash, state, tags, version,
+};
+use crate::models::db::schema::boards::BoxedQuery as BoxedBoardsQuery;
 use crate::models::{
-    db::tables::{InsertableBoard, SelectableBoard},
-    game::board::Board,
+    db::tables::{InsertableBoard, SelectableBoard, SelectableBoardSummary},
+    game::{
+        board::{Board, Goal, State as BoardState},
+        utils::unix_millis,
+    },
 };
-use crate::services::db::Pool as DbPool;
+use crate::services::{db::Pool as DbPool, expiry};
+
+// Number of boards read at a time when searching boards by what cannot be
+// compared in SQL, or filling in their starting hashes
+const SEARCH_BATCH_LEN: i64 = 100;
 
 #[derive(Debug)]
 pub enum Error {
@@ -26,66 +36,457 @@ impl From<diesel::result::Error> for Error {
     }
 }
 
-pub fn create(pool: &DbPool) -> Result<Board, Error> {
+// Save the new board, where the times of any moves it was created with, e.g.
+// when importing it, are not known
+pub fn insert(board: &Board, pool: &DbPool) -> Result<Board, Error> {
     let mut conn = pool.get().unwrap();
 
-    let new_board_state = InsertableBoard::from(&Board::default());
+    let mut board = board.clone();
+    board.record_move_times(None);
 
     let result = diesel::insert_into(boards)
-        .values(&new_board_state)
-        .get_result::<SelectableBoard>(&mut conn)?
+        .values(&InsertableBoard::from(&board))
+        .returning(SelectableBoard::as_returning())
+        .get_result(&mut conn)?
         .into_board();
 
     Ok(result)
 }
 
+// Time, in milliseconds since the Unix epoch, before which boards must have
+// last been active to have expired by now, if boards expire
+fn expiry_cutoff() -> Option<i64> {
+    expiry::cutoff(unix_millis(), expiry::ttl())
+}
+
+fn check_expiry(board_last_active_at: i64) -> Result<(), BoardError> {
+    if expiry_cutoff().is_some_and(|cutoff| board_last_active_at < cutoff) {
+        return Err(BoardError::BoardExpired);
+    }
+
+    Ok(())
+}
+
 pub fn get(search_id: i32, pool: &DbPool) -> Result<Board, Error> {
     let mut conn = pool.get().unwrap();
 
-    let board = boards
+    let (board, board_last_active_at) = boards
         .filter(id.eq(search_id))
-        .first::<SelectableBoard>(&mut conn)?
-        .into_board();
+        .select((SelectableBoard::as_select(), last_active_at))
+        .first::<(SelectableBoard, i64)>(&mut conn)?;
 
-    Ok(board)
+    check_expiry(board_last_active_at)?;
+
+    Ok(board.into_board())
+}
+
+// Mark the board as active without changing it, so that it does not expire,
+// returning the time, in milliseconds since the Unix epoch, at which it was
+// marked
+#[allow(clippy::cast_possible_wrap)]
+pub fn keep_alive(search_id: i32, pool: &DbPool) -> Result<u64, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let board_last_active_at = boards
+        .filter(id.eq(search_id))
+        .select(last_active_at)
+        .first::<i64>(&mut conn)
+        .optional()?
+        .ok_or(BoardError::BoardNotFound)?;
+
+    check_expiry(board_last_active_at)?;
+
+    let now = unix_millis();
+
+    diesel::update(boards.filter(id.eq(search_id)))
+        .set(last_active_at.eq(now as i64))
+        .execute(&mut conn)?;
+
+    Ok(now)
+}
+
+// Remove the boards last active before the given time, returning the number of
+// boards removed
+pub fn delete_expired(cutoff: i64, pool: &DbPool) -> Result<usize, diesel::result::Error> {
+    let mut conn = pool.get().unwrap();
+
+    diesel::delete(boards.filter(last_active_at.lt(cutoff))).execute(&mut conn)
+}
+
+// Conditions on the boards to list, each of which only applies if given
+#[derive(Debug, Default)]
+pub struct Filters<'a> {
+    // text contained in the board's name, ignoring case
+    pub name: Option<&'a str>,
+    pub tag: Option<&'a str>,
+    pub state: Option<BoardState>,
+    pub difficulty: Option<u32>,
+    pub min_difficulty: Option<u32>,
+    pub max_difficulty: Option<u32>,
+}
+
+// Summary of a board, as shown in lists of boards
+#[derive(Debug, Clone, Copy)]
+pub struct Summary {
+    pub id: i32,
+    pub state: BoardState,
+    pub num_blocks: usize,
+    pub created_at: Option<u64>,
+}
+
+// Boards matching the filters, where boards whose difficulty is not yet known
+// are left out if any condition on the difficulty is given, and expired boards
+// are always left out
+fn filtered(filters: &Filters) -> BoxedBoardsQuery<'static, Pg> {
+    let mut query = boards.into_boxed();
+
+    if let Some(cutoff) = expiry_cutoff() {
+        query = query.filter(last_active_at.ge(cutoff));
+    }
+
+    if let Some(search_name) = filters.name {
+        let pattern = search_name
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+
+        query = query.filter(name.ilike(format!("%{pattern}%")));
+    }
+
+    if let Some(search_tag) = filters.tag {
+        query = query.filter(tags.contains(vec![search_tag.to_string()]));
+    }
+
+    if let Some(search_state) = filters.state {
+        query = query.filter(state.eq(serde_json::to_string(&search_state).unwrap()));
+    }
+
+    if let Some(search_difficulty) = filters.difficulty {
+        query = query.filter(difficulty.eq(i32::try_from(search_difficulty).unwrap_or(i32::MAX)));
+    }
+
+    if let Some(min_difficulty) = filters.min_difficulty {
+        query = query.filter(difficulty.ge(i32::try_from(min_difficulty).unwrap_or(i32::MAX)));
+    }
+
+    if let Some(max_difficulty) = filters.max_difficulty {
+        query = query.filter(difficulty.le(i32::try_from(max_difficulty).unwrap_or(i32::MAX)));
+    }
+
+    query
+}
+
+// Boards matching the filters, in the order they were created
+pub fn list(
+    filters: &Filters,
+    limit: i64,
+    offset: i64,
+    pool: &DbPool,
+) -> Result<Vec<Board>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let results = filtered(filters)
+        .select(SelectableBoard::as_select())
+        .order(id.asc())
+        .limit(limit)
+        .offset(offset)
+        .load(&mut conn)?
+        .into_iter()
+        .map(SelectableBoard::into_board)
+        .collect();
+
+    Ok(results)
+}
+
+// Summaries of the boards matching the filters, in the order they were
+// created, which are read without reading the rest of each board
+pub fn list_summaries(
+    filters: &Filters,
+    limit: i64,
+    offset: i64,
+    pool: &DbPool,
+) -> Result<Vec<Summary>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let results = filtered(filters)
+        .select(SelectableBoardSummary::as_select())
+        .order(id.asc())
+        .limit(limit)
+        .offset(offset)
+        .load(&mut conn)?
+        .into_iter()
+        .map(|summary| Summary {
+            id: summary.id,
+            state: serde_json::from_str(summary.state.as_str()).unwrap(),
+            num_blocks: serde_json::from_str::<Vec<serde_json::Value>>(summary.blocks.as_str())
+                .unwrap()
+                .len(),
+            created_at: summary.created_at.map(|time| u64::try_from(time).unwrap()),
+        })
+        .collect();
+
+    Ok(results)
 }
 
-fn get_count(pool: &DbPool) -> i64 {
+// Query of the boards whose starting position has the given hash and, if it is
+// given, the given goal, in the order they were created, leaving out expired
+// boards
+#[allow(clippy::cast_possible_wrap)]
+fn searched(search_hash: u64, search_goal: Option<&Goal>) -> BoxedBoardsQuery<'static, Pg> {
+    let mut query = boards
+        .filter(start_hash.eq(search_hash as i64))
+        .into_boxed();
+
+    if let Some(search_goal) = search_goal {
+        query = query.filter(goal.eq(serde_json::to_string(search_goal).unwrap()));
+    }
+
+    if let Some(cutoff) = expiry_cutoff() {
+        query = query.filter(last_active_at.ge(cutoff));
+    }
+
+    query
+}
+
+// Boards whose starting position has the given hash and, if it is given, the
+// given goal, in the order they were created, leaving out expired boards
+pub fn search(
+    search_hash: u64,
+    search_goal: Option<&Goal>,
+    limit: i64,
+    offset: i64,
+    pool: &DbPool,
+) -> Result<Vec<Board>, Error> {
     let mut conn = pool.get().unwrap();
 
-    boards.count().first::<i64>(&mut conn).unwrap()
+    let results = searched(search_hash, search_goal)
+        .select(SelectableBoard::as_select())
+        .order(id.asc())
+        .limit(limit)
+        .offset(offset)
+        .load(&mut conn)?
+        .into_iter()
+        .map(SelectableBoard::into_board)
+        .collect();
+
+    Ok(results)
+}
+
+// Boards found as by search which also match the predicate, for what cannot be
+// compared in SQL. Boards are read in batches of SEARCH_BATCH_LEN, until the
+// first offset matching boards have been skipped and limit more are found.
+pub fn search_matching<F>(
+    search_hash: u64,
+    search_goal: Option<&Goal>,
+    matches: F,
+    limit: usize,
+    offset: usize,
+    pool: &DbPool,
+) -> Result<Vec<Board>, Error>
+where
+    F: Fn(&Board) -> bool,
+{
+    let mut conn = pool.get().unwrap();
+
+    let mut results = vec![];
+    let mut num_skipped = 0;
+    let mut last_id = 0;
+
+    while results.len() < limit {
+        let batch: Vec<Board> = searched(search_hash, search_goal)
+            .filter(id.gt(last_id))
+            .select(SelectableBoard::as_select())
+            .order(id.asc())
+            .limit(SEARCH_BATCH_LEN)
+            .load(&mut conn)?
+            .into_iter()
+            .map(SelectableBoard::into_board)
+            .collect();
+
+        let Some(last_board) = batch.last() else {
+            break;
+        };
+
+        last_id = last_board.id;
+
+        for board in batch.into_iter().filter(|board| matches(board)) {
+            if num_skipped < offset {
+                num_skipped += 1;
+            } else if results.len() < limit {
+                results.push(board);
+            }
+        }
+    }
+
+    Ok(results)
 }
 
-pub fn delete(search_id: i32, pool: &DbPool) -> Result<(), Error> {
+// Fill in the starting hash of each board saved before starting hashes were
+// recorded, which the migration adding them could only fill in for boards
+// without moves, since the moves of the others must be undone. Boards are read
+// in batches of SEARCH_BATCH_LEN. Returns the number of boards filled in.
+#[allow(clippy::cast_possible_wrap)]
+pub fn fill_start_hashes(pool: &DbPool) -> Result<usize, Error> {
     let mut conn = pool.get().unwrap();
 
-    let old_count = get_count(pool);
+    let mut num_filled = 0;
+
+    loop {
+        let batch: Vec<Board> = boards
+            .filter(start_hash.is_null())
+            .select(SelectableBoard::as_select())
+            .order(id.asc())
+            .limit(SEARCH_BATCH_LEN)
+            .load(&mut conn)?
+            .into_iter()
+            .map(SelectableBoard::into_board)
+            .collect();
 
-    diesel::delete(boards.filter(id.eq(search_id))).execute(&mut conn)?;
+        if batch.is_empty() {
+            return Ok(num_filled);
+        }
 
-    if get_count(pool) == old_count {
-        return Err(Error::BoardError(BoardError::BoardNotFound));
+        for board in &batch {
+            diesel::update(boards.filter(id.eq(board.id)).filter(start_hash.is_null()))
+                .set(start_hash.eq(board.start_hash() as i64))
+                .execute(&mut conn)?;
+        }
+
+        num_filled += batch.len();
     }
+}
+
+// Record the difficulty of the board, unless the board has since been changed
+// or its difficulty is already known. The board's version is not bumped, since
+// its difficulty follows from the rest of the board.
+pub fn set_difficulty(board: &Board, new_difficulty: u32, pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    diesel::update(
+        boards
+            .filter(id.eq(board.id))
+            .filter(version.eq(i32::try_from(board.version).unwrap()))
+            .filter(difficulty.is_null()),
+    )
+    .set(difficulty.eq(i32::try_from(new_difficulty).unwrap()))
+    .execute(&mut conn)?;
 
     Ok(())
 }
 
-pub fn update<F>(search_id: i32, update_fn: F, pool: &DbPool) -> Result<Board, Error>
+// Delete the board. If the version the deletion was made against is given and
+// the board has since been changed, the VersionConflict error is returned.
+pub fn delete(search_id: i32, expected_version: Option<u32>, pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    let num_deleted = match expected_version {
+        Some(expected_version) => diesel::delete(
+            boards
+                .filter(id.eq(search_id))
+                .filter(version.eq(i32::try_from(expected_version).unwrap_or(i32::MAX))),
+        )
+        .execute(&mut conn)?,
+        None => diesel::delete(boards.filter(id.eq(search_id))).execute(&mut conn)?,
+    };
+
+    if num_deleted == 0 {
+        let exists = diesel::select(diesel::dsl::exists(boards.filter(id.eq(search_id))))
+            .get_result::<bool>(&mut conn)?;
+
+        return Err(Error::BoardError(if exists {
+            BoardError::VersionConflict
+        } else {
+            BoardError::BoardNotFound
+        }));
+    }
+
+    Ok(())
+}
+
+// Delete the boards with the given ids in a single statement, so that either
+// all or none of them are deleted, returning the ids of the boards deleted,
+// which leaves out the ids of boards which do not exist
+pub fn delete_all(search_ids: &[i32], pool: &DbPool) -> Result<Vec<i32>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let mut deleted_ids: Vec<i32> = diesel::delete(boards.filter(id.eq_any(search_ids)))
+        .returning(id)
+        .get_results(&mut conn)?;
+
+    deleted_ids.sort_unstable();
+
+    Ok(deleted_ids)
+}
+
+// Delete the boards matching the filters in a single statement, as by
+// delete_all, returning the ids of the boards deleted in the order they were
+// created
+pub fn delete_matching(filters: &Filters, pool: &DbPool) -> Result<Vec<i32>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let mut deleted_ids: Vec<i32> =
+        diesel::delete(boards.filter(id.eq_any(filtered(filters).select(id))))
+            .returning(id)
+            .get_results(&mut conn)?;
+
+    deleted_ids.sort_unstable();
+
+    Ok(deleted_ids)
+}
+
+// Apply the update to the board, bump its version and mark it as active. If
+// the version the update was made against is given and the board has since
+// been changed, or if the board is changed by another update while this one
+// is applied, the VersionConflict error is returned.
+#[allow(clippy::cast_possible_wrap)]
+pub fn update<F>(
+    search_id: i32,
+    expected_version: Option<u32>,
+    update_fn: F,
+    pool: &DbPool,
+) -> Result<Board, Error>
 where
     F: FnOnce(&mut Board) -> Result<(), BoardError>,
 {
     let mut conn = pool.get().unwrap();
 
-    let mut board = boards
+    let (board, board_last_active_at) = boards
         .filter(id.eq(search_id))
-        .first::<SelectableBoard>(&mut conn)?
-        .into_board();
+        .select((SelectableBoard::as_select(), last_active_at))
+        .first::<(SelectableBoard, i64)>(&mut conn)?;
+
+    check_expiry(board_last_active_at)?;
+
+    let mut board = board.into_board();
+
+    if expected_version.is_some_and(|expected_version| expected_version != board.version) {
+        return Err(Error::BoardError(BoardError::VersionConflict));
+    }
+
+    let old_version = i32::try_from(board.version).unwrap();
 
     update_fn(&mut board)?;
 
-    diesel::update(boards.filter(id.eq(search_id)))
-        .set(&InsertableBoard::from(&board.clone()))
-        .execute(&mut conn)?;
+    let now = unix_millis();
+
+    board.update_timer(now);
+    board.record_move_times(Some(now));
+    board.version += 1;
+
+    let num_updated = diesel::update(
+        boards
+            .filter(id.eq(search_id))
+            .filter(version.eq(old_version)),
+    )
+    .set((
+        &InsertableBoard::from(&board.clone()),
+        last_active_at.eq(now as i64),
+    ))
+    .execute(&mut conn)?;
+
+    if num_updated == 0 {
+        return Err(Error::BoardError(BoardError::VersionConflict));
+    }
 
     Ok(board)
 }
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::db::schema::distances::dsl::{distances, hash};
+use crate::models::db::tables::{InsertableDistance, SelectableDistance};
+use crate::services::db::Pool as DbPool;
+
+// Maximum number of rows inserted by a single statement, which keeps the
+// number of bind parameters below the Postgres limit
+const INSERT_CHUNK_SIZE: usize = 10_000;
+
+pub fn create_many(entries: &[(u64, usize)], pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    for chunk in entries.chunks(INSERT_CHUNK_SIZE) {
+        let new_distances: Vec<InsertableDistance> = chunk
+            .iter()
+            .map(|(new_hash, distance)| InsertableDistance::from(*new_hash, *distance))
+            .collect();
+
+        diesel::insert_into(distances)
+            .values(&new_distances)
+            .on_conflict_do_nothing()
+            .execute(&mut conn)?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::cast_possible_wrap)]
+pub fn get_many(search_hashes: &[u64], pool: &DbPool) -> Result<HashMap<u64, usize>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let entries = distances
+        .filter(hash.eq_any(search_hashes.iter().map(|search_hash| *search_hash as i64)))
+        .load::<SelectableDistance>(&mut conn)?
+        .into_iter()
+        .map(SelectableDistance::into_entry)
+        .collect();
+
+    Ok(entries)
+}
+
+pub fn count(pool: &DbPool) -> Result<i64, Error> {
+    let mut conn = pool.get().unwrap();
+
+    distances.count().first::<i64>(&mut conn)
+}
@@ -0,0 +1,106 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::db::schema::idempotency_keys::dsl::{
+    api_key_id, body, created_at, headers, idempotency_keys, key, status,
+};
+use crate::models::db::tables::{InsertableIdempotencyKey, SelectableIdempotencyKey};
+use crate::services::db::Pool as DbPool;
+use crate::services::idempotency::{Record, StoredResponse};
+
+// Forget the keys reserved before the cutoff, and the keys whose request has
+// not been handled which were reserved before the lease cutoff, both in
+// milliseconds since the Unix epoch
+pub fn forget_expired(cutoff: i64, lease_cutoff: i64, pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    diesel::delete(
+        idempotency_keys.filter(
+            created_at
+                .lt(cutoff)
+                .or(status.is_null().and(created_at.lt(lease_cutoff))),
+        ),
+    )
+    .execute(&mut conn)?;
+
+    Ok(())
+}
+
+// Reserve the key for the request, or return the request the key has already
+// been reserved for
+#[allow(clippy::cast_possible_wrap)]
+pub fn reserve(
+    search_key: &str,
+    search_api_key_id: i32,
+    method: &str,
+    path: &str,
+    fingerprint: &str,
+    now: u64,
+    pool: &DbPool,
+) -> Result<Option<Record>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let num_inserted = diesel::insert_into(idempotency_keys)
+        .values(&InsertableIdempotencyKey {
+            key: search_key,
+            api_key_id: search_api_key_id,
+            method,
+            path,
+            created_at: now as i64,
+            fingerprint,
+        })
+        .on_conflict_do_nothing()
+        .execute(&mut conn)?;
+
+    if num_inserted == 1 {
+        return Ok(None);
+    }
+
+    let record = idempotency_keys
+        .filter(key.eq(search_key))
+        .filter(api_key_id.eq(search_api_key_id))
+        .select(SelectableIdempotencyKey::as_select())
+        .first(&mut conn)?
+        .into_record();
+
+    Ok(Some(record))
+}
+
+// Persist the response to the request the key was reserved for
+#[allow(clippy::cast_possible_wrap)]
+pub fn complete(
+    search_key: &str,
+    search_api_key_id: i32,
+    response: &StoredResponse,
+    pool: &DbPool,
+) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    diesel::update(
+        idempotency_keys
+            .filter(key.eq(search_key))
+            .filter(api_key_id.eq(search_api_key_id)),
+    )
+    .set((
+        status.eq(response.status as i16),
+        headers.eq(serde_json::to_string(&response.headers).unwrap()),
+        body.eq(&response.body),
+    ))
+    .execute(&mut conn)?;
+
+    Ok(())
+}
+
+// Forget the key, so that the request can be retried
+pub fn release(search_key: &str, search_api_key_id: i32, pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    diesel::delete(
+        idempotency_keys
+            .filter(key.eq(search_key))
+            .filter(api_key_id.eq(search_api_key_id)),
+    )
+    .execute(&mut conn)?;
+
+    Ok(())
+}
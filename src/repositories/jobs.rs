@@ -0,0 +1,56 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::db::schema::jobs::dsl::{id, jobs, status};
+use crate::models::db::tables::{InsertableJob, SelectableJob};
+use crate::services::db::Pool as DbPool;
+use crate::services::jobs::{Job, Status};
+
+pub fn create(job: &Job, pool: &DbPool) -> Result<Job, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let result = diesel::insert_into(jobs)
+        .values(&InsertableJob::from(job))
+        .get_result::<SelectableJob>(&mut conn)?
+        .into_job();
+
+    Ok(result)
+}
+
+pub fn get(search_id: i32, pool: &DbPool) -> Result<Job, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let job = jobs
+        .filter(id.eq(search_id))
+        .first::<SelectableJob>(&mut conn)?
+        .into_job();
+
+    Ok(job)
+}
+
+pub fn get_unfinished(pool: &DbPool) -> Result<Vec<Job>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let unfinished_statuses = [Status::Pending, Status::Running]
+        .map(|unfinished_status| serde_json::to_string(&unfinished_status).unwrap());
+
+    let unfinished_jobs = jobs
+        .filter(status.eq_any(unfinished_statuses))
+        .order(id.asc())
+        .load::<SelectableJob>(&mut conn)?
+        .into_iter()
+        .map(SelectableJob::into_job)
+        .collect();
+
+    Ok(unfinished_jobs)
+}
+
+pub fn update(job: &Job, pool: &DbPool) -> Result<(), Error> {
+    let mut conn = pool.get().unwrap();
+
+    diesel::update(jobs.filter(id.eq(job.id)))
+        .set(&InsertableJob::from(job))
+        .execute(&mut conn)?;
+
+    Ok(())
+}
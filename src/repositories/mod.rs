@@ -1,2 +1,8 @@
+pub mod api_keys;
 pub mod boards;
+pub mod distances;
+pub mod idempotency_keys;
+pub mod jobs;
+pub mod scores;
+pub mod shares;
 pub mod solutions;
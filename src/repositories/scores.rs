@@ -0,0 +1,66 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::db::schema::scores::dsl::{duration_ms, id, num_moves, puzzle, scores};
+use crate::models::db::tables::{InsertableScore, SelectableScore};
+use crate::services::db::Pool as DbPool;
+use crate::services::leaderboards::{Ranking, Score, Solve};
+
+// Record the verified solve of the puzzle on the board, submitted at the given
+// time in milliseconds since the Unix epoch, returning None if a solve on the
+// board was already recorded
+#[allow(clippy::cast_possible_wrap)]
+pub fn create(
+    search_puzzle: &str,
+    player: &str,
+    solve: Solve,
+    search_board_id: i32,
+    now: u64,
+    pool: &DbPool,
+) -> Result<Option<Score>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let score = diesel::insert_into(scores)
+        .values(&InsertableScore {
+            puzzle: search_puzzle,
+            player,
+            num_moves: i32::try_from(solve.num_moves).unwrap_or(i32::MAX),
+            duration_ms: i64::try_from(solve.duration_ms).unwrap_or(i64::MAX),
+            created_at: now as i64,
+            board_id: search_board_id,
+        })
+        .on_conflict_do_nothing()
+        .returning(SelectableScore::as_returning())
+        .get_result(&mut conn)
+        .optional()?
+        .map(SelectableScore::into_score);
+
+    Ok(score)
+}
+
+// Best scores of the puzzle, in the order of the ranking
+pub fn leaderboard(
+    search_puzzle: &str,
+    ranking: Ranking,
+    limit: i64,
+    pool: &DbPool,
+) -> Result<Vec<Score>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let query = scores.filter(puzzle.eq(search_puzzle)).into_boxed();
+
+    let query = match ranking {
+        Ranking::FewestMoves => query.order((num_moves.asc(), duration_ms.asc(), id.asc())),
+        Ranking::Fastest => query.order((duration_ms.asc(), num_moves.asc(), id.asc())),
+    };
+
+    let results = query
+        .select(SelectableScore::as_select())
+        .limit(limit)
+        .load(&mut conn)?
+        .into_iter()
+        .map(SelectableScore::into_score)
+        .collect();
+
+    Ok(results)
+}
@@ -0,0 +1,48 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::db::schema::shares::dsl::{code, shares};
+use crate::models::db::tables::{InsertableShare, SelectableShare};
+use crate::services::db::Pool as DbPool;
+use crate::services::shares::Share;
+
+// Share the layout by the given code at the given time, in milliseconds since
+// the Unix epoch, returning the share, or None if the code is already taken
+#[allow(clippy::cast_possible_wrap)]
+pub fn create(
+    new_code: &str,
+    notation: &str,
+    name: Option<&str>,
+    now: u64,
+    pool: &DbPool,
+) -> Result<Option<Share>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let share = diesel::insert_into(shares)
+        .values(&InsertableShare {
+            code: new_code,
+            notation,
+            name,
+            created_at: now as i64,
+        })
+        .on_conflict_do_nothing()
+        .returning(SelectableShare::as_returning())
+        .get_result(&mut conn)
+        .optional()?
+        .map(SelectableShare::into_share);
+
+    Ok(share)
+}
+
+pub fn get(search_code: &str, pool: &DbPool) -> Result<Option<Share>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let share = shares
+        .filter(code.eq(search_code))
+        .select(SelectableShare::as_select())
+        .first(&mut conn)
+        .optional()?
+        .map(SelectableShare::into_share);
+
+    Ok(share)
+}
@@ -1,21 +1,25 @@
 use diesel::prelude::*;
 use diesel::result::Error;
 
-use crate::models::db::schema::solutions::dsl::{hash, solutions};
+use crate::models::db::schema::solutions::dsl::{goal, hash, id, solutions};
 use crate::models::{
     db::tables::{InsertableSolution, SelectableSolution},
-    game::moves::FlatBoardMove,
+    game::{
+        blocks::Positioned as PositionedBlock,
+        board::{Board, Goal},
+        moves::FlatBoardMove,
+    },
 };
 use crate::services::db::Pool as DbPool;
 
 pub fn create(
-    new_hash: u64,
+    board: &Board,
     moves: Option<Vec<FlatBoardMove>>,
     pool: &DbPool,
 ) -> Result<(), Error> {
     let mut conn = pool.get().unwrap();
 
-    let new_solution = InsertableSolution::from(new_hash, moves);
+    let new_solution = InsertableSolution::from(board, moves);
 
     diesel::insert_into(solutions)
         .values(&new_solution)
@@ -24,14 +28,91 @@ pub fn create(
     Ok(())
 }
 
+// Moves of the solution cached with the given blocks, with the index of each
+// moved block taken to the index of the board's block in the same position.
+// Since the board has the hash the solution was cached for, each cached block
+// has a block of the same shape in the same position on the board.
+fn reindex(
+    moves: Vec<FlatBoardMove>,
+    blocks: &[PositionedBlock],
+    board: &Board,
+) -> Option<Vec<FlatBoardMove>> {
+    let block_idxs = blocks
+        .iter()
+        .map(|cached_block| {
+            board.blocks.iter().position(|positioned_block| {
+                positioned_block.block == cached_block.block
+                    && positioned_block.min_position == cached_block.min_position
+            })
+        })
+        .collect::<Option<Vec<usize>>>()?;
+
+    moves
+        .into_iter()
+        .map(|move_| {
+            Some(FlatBoardMove {
+                block_idx: *block_idxs.get(move_.block_idx)?,
+                ..move_
+            })
+        })
+        .collect()
+}
+
+// Cached solution of the board, with its moves indexing into the board's
+// blocks, where the moves are None if the board was found to have no solution
+#[allow(clippy::cast_possible_wrap)]
+pub fn get(board: &Board, pool: &DbPool) -> Result<Option<Vec<FlatBoardMove>>, Error> {
+    let mut conn = pool.get().unwrap();
+
+    let solution = solutions
+        .filter(hash.eq(board.hash() as i64))
+        .filter(goal.eq(serde_json::to_string(&board.goal).unwrap()))
+        .first::<SelectableSolution>(&mut conn)?;
+
+    let blocks = solution.get_blocks();
+
+    match solution.get_moves() {
+        Some(moves) => reindex(moves, &blocks, board)
+            .map(Some)
+            .ok_or(Error::NotFound),
+        None => Ok(None),
+    }
+}
+
+// Solution cached for a board with the given goal, where the moves index into
+// the blocks, and are None if the board was found to have no solution
+#[derive(Debug, Clone)]
+pub struct Cached {
+    pub goal: Goal,
+    pub blocks: Vec<PositionedBlock>,
+    pub moves: Option<Vec<FlatBoardMove>>,
+}
+
+// Cached solutions of boards with the given hash, one for each goal
 #[allow(clippy::cast_possible_wrap)]
-pub fn get(search_hash: u64, pool: &DbPool) -> Result<Option<Vec<FlatBoardMove>>, Error> {
+pub fn get_all(search_hash: u64, pool: &DbPool) -> Result<Vec<Cached>, Error> {
     let mut conn = pool.get().unwrap();
 
-    let moves = solutions
+    let mut cached_solutions: Vec<Cached> = vec![];
+
+    for solution in solutions
         .filter(hash.eq(search_hash as i64))
-        .first::<SelectableSolution>(&mut conn)?
-        .get_moves();
+        .order(id)
+        .load::<SelectableSolution>(&mut conn)?
+    {
+        let solution_goal = solution.get_goal();
+
+        if cached_solutions
+            .iter()
+            .all(|cached_solution| cached_solution.goal != solution_goal)
+        {
+            cached_solutions.push(Cached {
+                goal: solution_goal,
+                blocks: solution.get_blocks(),
+                moves: solution.get_moves(),
+            });
+        }
+    }
 
-    Ok(moves)
+    Ok(cached_solutions)
 }
@@ -0,0 +1,201 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::repositories::api_keys::add_uses;
+use crate::services::{db::Pool as DbPool, rate_limits::Tier};
+
+// Seconds between each time the uses of keys counted in memory are added to
+// the keys' records
+const USAGE_FLUSH_INTERVAL_SECS: u64 = 60;
+
+// Key identifying a client, given in the X-Api-Key header, whose rate limits,
+// when set, replace the default rate limits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub rate_limit: Option<u32>,
+    pub expensive_rate_limit: Option<u32>,
+    // number of requests made with the key, and time, in milliseconds since
+    // the Unix epoch, of the latest of them, as of the last time the uses of
+    // keys were recorded
+    pub requests: u64,
+    pub last_used_at: Option<u64>,
+    // whether the key may be used for the admin operations
+    pub admin: bool,
+}
+
+impl ApiKey {
+    pub fn limit(&self, tier: Tier) -> Option<u32> {
+        match tier {
+            Tier::Default => self.rate_limit,
+            Tier::Expensive => self.expensive_rate_limit,
+        }
+    }
+}
+
+// Hex encoded SHA-256 hash of the key, which is stored in place of the key
+pub fn hash(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+// Number of requests made with a key since its uses were last recorded, and
+// the time, in milliseconds since the Unix epoch, of the latest of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uses {
+    pub requests: u64,
+    pub last_used_at: u64,
+}
+
+// Uses of each key counted in memory, which are added to the keys' records
+// periodically rather than on every request, so that the record of a busy key
+// is not updated by each of its requests
+#[derive(Debug, Clone, Default)]
+pub struct Usage {
+    uses: Arc<Mutex<HashMap<i32, Uses>>>,
+}
+
+impl Usage {
+    // Count a request made with the key at the given time
+    pub fn record(&self, api_key_id: i32, now: u64) {
+        let mut uses = self.uses.lock().unwrap();
+
+        let key_uses = uses.entry(api_key_id).or_insert(Uses {
+            requests: 0,
+            last_used_at: now,
+        });
+
+        key_uses.requests += 1;
+        key_uses.last_used_at = key_uses.last_used_at.max(now);
+    }
+
+    // Uses counted since the last call, which are forgotten
+    pub fn take(&self) -> HashMap<i32, Uses> {
+        std::mem::take(&mut *self.uses.lock().unwrap())
+    }
+
+    // Count the uses again, e.g. after failing to record them
+    pub fn restore(&self, restored_uses: HashMap<i32, Uses>) {
+        let mut uses = self.uses.lock().unwrap();
+
+        for (api_key_id, restored_key_uses) in restored_uses {
+            let key_uses = uses.entry(api_key_id).or_insert(Uses {
+                requests: 0,
+                last_used_at: restored_key_uses.last_used_at,
+            });
+
+            key_uses.requests += restored_key_uses.requests;
+            key_uses.last_used_at = key_uses.last_used_at.max(restored_key_uses.last_used_at);
+        }
+    }
+}
+
+// Add the uses of keys counted in memory to the keys' records every
+// USAGE_FLUSH_INTERVAL_SECS seconds on tokio's blocking thread pool, where the
+// uses of a failed attempt are counted again for the next attempt
+pub fn spawn_flush(usage: &Usage, pool: &DbPool) {
+    let usage = usage.clone();
+    let pool = pool.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(USAGE_FLUSH_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+
+            let uses = usage.take();
+
+            if uses.is_empty() {
+                continue;
+            }
+
+            let pool = pool.clone();
+
+            let result = tokio::task::spawn_blocking(move || {
+                add_uses(&uses, &pool).map_err(|err| (err, uses))
+            })
+            .await;
+
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err((err, uses))) => {
+                    tracing::error!("DieselError: {}", err);
+                    usage.restore(uses);
+                }
+                Err(err) => tracing::error!("Failed to record uses of API keys: {}", err),
+            }
+        }
+    });
+}
+
+// Whether requests without an API key are rejected, set for each environment
+// using the REQUIRE_API_KEY environment variable, which defaults to false
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+    pub required: bool,
+}
+
+impl Policy {
+    pub fn from_env() -> Self {
+        Self {
+            required: dotenvy::var("REQUIRE_API_KEY")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_keys() {
+        assert_eq!(
+            hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash("abc"), hash("abd"));
+    }
+
+    #[test]
+    fn counts_uses() {
+        let usage = Usage::default();
+
+        usage.record(1, 2_000);
+        usage.record(1, 1_000);
+        usage.record(2, 3_000);
+
+        let uses = usage.take();
+
+        assert_eq!(
+            uses.get(&1),
+            Some(&Uses {
+                requests: 2,
+                last_used_at: 2_000
+            })
+        );
+        assert_eq!(uses.len(), 2);
+        assert!(usage.take().is_empty());
+
+        usage.record(1, 4_000);
+        usage.restore(uses);
+
+        assert_eq!(
+            usage.take().get(&1),
+            Some(&Uses {
+                requests: 3,
+                last_used_at: 4_000
+            })
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use crate::models::game::{board::Board, layouts::LAYOUTS};
+use crate::repositories::solutions::{create as create_solution, get as get_solution};
+use crate::services::{
+    db::Pool as DbPool,
+    distances,
+    solver::{self, Options},
+};
+
+// Build the board of each layout, ready to solve
+pub fn boards() -> Vec<(&'static str, Board)> {
+    LAYOUTS
+        .iter()
+        .map(|layout| (layout.name, layout.board()))
+        .collect()
+}
+
+// Cache the solution of each layout on tokio's blocking thread pool, unless
+// it has already been cached, so that the first request to solve a famous
+// layout does not wait for the solver. The distance table is used if it has
+// already been populated, and the solver otherwise.
+pub fn warm(pool: &DbPool) {
+    let pool = pool.clone();
+
+    tokio::task::spawn_blocking(move || {
+        for (name, board) in boards() {
+            if get_solution(&board, &pool).is_ok() {
+                continue;
+            }
+
+            let moves = match distances::solve(&board, &pool) {
+                Some(moves) => Some(moves),
+                None => match solver::solve(&board, &Options::default()) {
+                    Ok(moves) => moves,
+                    Err(err) => {
+                        tracing::error!("SolverError: {}", err);
+                        continue;
+                    }
+                },
+            };
+
+            match create_solution(&board, moves, &pool) {
+                Ok(()) => tracing::info!("Solution of classic layout {} cached", name),
+                Err(err) => tracing::error!("DieselError: {}", err),
+            }
+        }
+    });
+}
@@ -0,0 +1,140 @@
+use crate::errors::{board::Error as BoardError, solver::Error as SolverError};
+use crate::models::game::{board::Board, moves::FlatBoardMove};
+use crate::repositories::solutions::{create as create_solution, get as get_solution};
+use crate::services::{
+    db::Pool as DbPool,
+    distances, randomizer,
+    solver::{self, Options},
+};
+
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+// Number of random moves made from a solved board to derive each day's puzzle
+const SHUFFLE_MOVES: usize = 200;
+
+// Calendar date, in UTC, of the given time in milliseconds since the Unix
+// epoch, as the number of days since the epoch
+pub fn today(now: u64) -> u64 {
+    now / MILLIS_PER_DAY
+}
+
+// Number of days since the Unix epoch of the date given as "YYYY-MM-DD", or
+// None if it is not a valid date from 1970 onwards
+pub fn parse_date(date: &str) -> Option<u64> {
+    let mut parts = date.splitn(3, '-');
+
+    let year: u64 = parts.next().filter(|part| part.len() == 4)?.parse().ok()?;
+    let month: u64 = parts.next().filter(|part| part.len() == 2)?.parse().ok()?;
+    let day: u64 = parts.next().filter(|part| part.len() == 2)?.parse().ok()?;
+
+    if year < 1970 || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // days since 0000-03-01, counting years from March so that leap days fall
+    // at the end of each year
+    let year = if month <= 2 { year - 1 } else { year };
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let days = year * 365 + year / 4 - year / 100 + year / 400 + day_of_year;
+
+    let days = days - 719_468;
+
+    // days which overflow the month, e.g. "2024-02-30", are rejected
+    (format_date(days) == date).then_some(days)
+}
+
+// Date of the given number of days since the Unix epoch, as "YYYY-MM-DD"
+pub fn format_date(days: u64) -> String {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+// Seed of the puzzle of the given date, which spreads consecutive dates apart,
+// of at most MAX_RANDOM_SEED as with random seeds
+pub fn seed(days: u64) -> u64 {
+    let mut seed = days.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    (seed ^ (seed >> 31)) & randomizer::MAX_RANDOM_SEED
+}
+
+// Puzzle of the given date, which is the same for every user as it is
+// shuffled from a solved board using the date's seed, and so is solvable
+pub fn board(days: u64) -> Result<Board, BoardError> {
+    let mut board = Board::default();
+
+    randomizer::shuffle(&mut board, SHUFFLE_MOVES, seed(days))?;
+
+    Ok(board)
+}
+
+// Optimal solution of the puzzle, which is cached once found so that every
+// user is given the same solution and difficulty
+pub fn solution(board: &Board, pool: &DbPool) -> Result<Option<Vec<FlatBoardMove>>, SolverError> {
+    if let Ok(moves) = get_solution(board, pool) {
+        return Ok(moves);
+    }
+
+    let moves = match distances::solve(board, pool) {
+        Some(moves) => Some(moves),
+        None => solver::solve(
+            board,
+            &Options {
+                deterministic: true,
+                ..Options::default()
+            },
+        )?,
+    };
+
+    if let Err(err) = create_solution(board, moves.clone(), pool) {
+        tracing::error!("DieselError: {}", err);
+    }
+
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2024-02-29"), Some(19_782));
+        assert_eq!(format_date(19_782), "2024-02-29");
+        assert_eq!(today(19_782 * MILLIS_PER_DAY + 1), 19_782);
+
+        for days in [59, 365, 10_956, 11_016, 20_000] {
+            assert_eq!(parse_date(&format_date(days)), Some(days));
+        }
+
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("1969-12-31"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2024-1-01"), None);
+        assert_eq!(parse_date("today"), None);
+    }
+
+    #[test]
+    fn same_puzzle_each_day() {
+        let board = board(19_782).unwrap();
+
+        assert_eq!(board.blocks, super::board(19_782).unwrap().blocks);
+        assert_ne!(board.blocks, super::board(19_783).unwrap().blocks);
+        assert_ne!(seed(19_782), seed(19_783));
+    }
+}
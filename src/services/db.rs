@@ -3,6 +3,8 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool as R2D2Pool};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 
+use crate::repositories::boards::fill_start_hashes as fill_board_start_hashes;
+
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
 fn get_db_url() -> String {
@@ -33,3 +35,16 @@ pub fn run_migrations(conn: &mut impl MigrationHarness<Pg>) {
     conn.run_pending_migrations(MIGRATIONS)
         .expect("Diesel migrations failed");
 }
+
+// Fill in the starting hashes of boards saved before they were recorded on
+// tokio's blocking thread pool, which cannot be done in SQL by the migration
+// adding them, so that every board can be found by searching
+pub fn fill_start_hashes(pool: &Pool) {
+    let pool = pool.clone();
+
+    tokio::task::spawn_blocking(move || match fill_board_start_hashes(&pool) {
+        Ok(0) => {}
+        Ok(num_filled) => tracing::info!("Filled in starting hashes of {} boards", num_filled),
+        Err(err) => tracing::error!("Failed to fill in starting hashes of boards: {:?}", err),
+    });
+}
@@ -0,0 +1,139 @@
+use crate::models::game::{
+    blocks::Block,
+    board::{Board, Goal, State as BoardState},
+    moves::FlatBoardMove,
+};
+use crate::repositories::distances::{count, create_many, get_many};
+use crate::services::{
+    db::Pool as DbPool,
+    solver::{self, Cancellation},
+};
+
+// Blocks of the classic board, whose distances are stored in the distance table
+pub const STANDARD_BLOCKS: [Block; 10] = [
+    Block::TWO_BY_TWO,
+    Block::TWO_BY_ONE,
+    Block::TWO_BY_ONE,
+    Block::TWO_BY_ONE,
+    Block::TWO_BY_ONE,
+    Block::ONE_BY_TWO,
+    Block::ONE_BY_ONE,
+    Block::ONE_BY_ONE,
+    Block::ONE_BY_ONE,
+    Block::ONE_BY_ONE,
+];
+
+// Compute the length of the optimal solution of every board of the standard
+// blocks from which the default goal can be reached, keyed by the board's
+// canonical hash
+pub fn compute() -> Vec<(u64, usize)> {
+    let mut entries = vec![];
+
+    solver::retrograde_bfs(
+        &STANDARD_BLOCKS,
+        Goal::default(),
+        &Cancellation::default(),
+        |distance, level| {
+            entries.extend(level.iter().map(|board| (board.canonical_hash(), distance)));
+        },
+    )
+    .unwrap();
+
+    entries
+}
+
+// Compute and persist the distance table on tokio's blocking thread pool,
+// unless the table has already been populated
+pub fn populate(pool: &DbPool) {
+    let pool = pool.clone();
+
+    tokio::task::spawn_blocking(move || match count(&pool) {
+        Ok(0) => {
+            tracing::info!("Populating distance table");
+
+            let entries = compute();
+
+            match create_many(&entries, &pool) {
+                Ok(()) => tracing::info!("Distance table populated with {} boards", entries.len()),
+                Err(err) => tracing::error!("DieselError: {}", err),
+            }
+        }
+        Ok(_) => {}
+        Err(err) => tracing::error!("DieselError: {}", err),
+    });
+}
+
+// Find an optimal solution for the board using the distance table, by
+// repeatedly making a move to a board whose distance is one less than the
+// current board's distance. Returns None if the board does not have the
+// default goal, has targets, or is not in the table.
+pub fn solve(board: &Board, pool: &DbPool) -> Option<Vec<FlatBoardMove>> {
+    if board.goal != Goal::default() || !board.targets.is_empty() {
+        return None;
+    }
+
+    let mut current = board.clone();
+    current.moves.clear();
+
+    current.change_state(BoardState::Solving).ok()?;
+    let _board_is_already_solved = current.change_state(BoardState::Solved).is_ok();
+
+    let mut distance = *get_many(&[current.canonical_hash()], pool)
+        .ok()?
+        .get(&current.canonical_hash())?;
+
+    while distance > 0 {
+        let mut children = vec![];
+
+        for (block_idx, moves) in current.get_next_moves().into_iter().enumerate() {
+            for move_ in moves {
+                current.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                children.push((
+                    current.canonical_hash(),
+                    FlatBoardMove::new(block_idx, &move_),
+                ));
+
+                current.undo_move_unchecked();
+            }
+        }
+
+        let hashes: Vec<u64> = children.iter().map(|(hash, _)| *hash).collect();
+        let distances = get_many(&hashes, pool).ok()?;
+
+        let (_, next_move) = children
+            .into_iter()
+            .find(|(hash, _)| distances.get(hash) == Some(&(distance - 1)))?;
+
+        current.move_block_unchecked(next_move.block_idx, next_move.row_diff, next_move.col_diff);
+
+        distance -= 1;
+    }
+
+    Some(current.moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_compute() {
+        let entries: HashMap<u64, usize> = compute().into_iter().collect();
+
+        let goal_hashes: HashSet<u64> =
+            solver::get_goal_boards(&STANDARD_BLOCKS, Goal::default(), &[])
+                .iter()
+                .map(Board::canonical_hash)
+                .collect();
+
+        assert!(goal_hashes
+            .iter()
+            .all(|goal_hash| entries.get(goal_hash) == Some(&0)));
+        assert!(entries
+            .values()
+            .max()
+            .is_some_and(|distance| *distance >= 81));
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use crate::models::game::utils::unix_millis;
+use crate::repositories::boards::delete_expired;
+use crate::services::db::Pool as DbPool;
+
+// Seconds after which boards which have been neither changed nor kept alive
+// expire, by default, where 0 means that boards never expire, since expired
+// boards are deleted
+const DEFAULT_TTL_SECS: u64 = 0;
+
+// Seconds between each removal of expired boards
+const CLEANUP_INTERVAL_SECS: u64 = 60 * 60;
+
+// Time after which inactive boards expire, set in seconds using the BOARD_TTL
+// environment variable, where 0 means that boards never expire
+pub fn ttl() -> Option<Duration> {
+    let ttl = dotenvy::var("BOARD_TTL")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TTL_SECS);
+
+    (ttl > 0).then(|| Duration::from_secs(ttl))
+}
+
+// Time, in milliseconds since the Unix epoch, before which boards must have
+// last been active to have expired at the given time, if boards expire
+#[allow(clippy::cast_possible_wrap)]
+pub fn cutoff(now: u64, ttl: Option<Duration>) -> Option<i64> {
+    ttl.map(|ttl| {
+        let ttl = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+
+        now.saturating_sub(ttl) as i64
+    })
+}
+
+// Time, in milliseconds since the Unix epoch, at which a board last active at
+// the given time expires, if boards expire
+pub fn expires_at(last_active_at: u64, ttl: Option<Duration>) -> Option<u64> {
+    ttl.map(|ttl| last_active_at.saturating_add(u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX)))
+}
+
+// Remove expired boards periodically on tokio's blocking thread pool, so that
+// boards abandoned by their clients do not accumulate
+pub fn spawn_cleanup(pool: &DbPool) {
+    let pool = pool.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(CLEANUP_INTERVAL_SECS));
+
+        loop {
+            interval.tick().await;
+
+            let Some(cutoff) = cutoff(unix_millis(), ttl()) else {
+                continue;
+            };
+
+            let pool = pool.clone();
+
+            match tokio::task::spawn_blocking(move || delete_expired(cutoff, &pool)).await {
+                Ok(Ok(0)) => {}
+                Ok(Ok(num_deleted)) => tracing::info!("Removed {} expired boards", num_deleted),
+                Ok(Err(err)) => tracing::error!("DieselError: {}", err),
+                Err(err) => tracing::error!("Failed to remove expired boards: {}", err),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiry_times() {
+        let ttl = Some(Duration::from_secs(90));
+
+        assert_eq!(cutoff(100_000, ttl), Some(10_000));
+        assert_eq!(cutoff(100_000, None), None);
+        assert_eq!(cutoff(50_000, ttl), Some(0));
+
+        assert_eq!(expires_at(10_000, ttl), Some(100_000));
+        assert_eq!(expires_at(10_000, None), None);
+    }
+}
@@ -0,0 +1,202 @@
+use serde::Serialize;
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use utoipa::ToSchema;
+
+use crate::errors::solver::Error as SolverError;
+use crate::models::game::board::{Board, State as BoardState};
+use crate::services::solver::Cancellation;
+
+pub const DEFAULT_MAX_NODES: usize = 100_000;
+pub const MAX_MAX_NODES: usize = 1_000_000;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = GraphNode)]
+pub struct Node {
+    pub id: usize,
+    pub depth: usize,
+    pub solved: bool,
+    // index of the block covering each cell, if any
+    pub grid: [Option<usize>; (Board::ROWS * Board::COLS) as usize],
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = GraphEdge)]
+pub struct Edge {
+    pub source: usize,
+    pub target: usize,
+    pub block_idx: usize,
+    pub row_diff: i8,
+    pub col_diff: i8,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub truncated: bool,
+}
+
+impl Graph {
+    fn add_node(&mut self, board: &Board, depth: usize) -> usize {
+        let id = self.nodes.len();
+
+        self.nodes.push(Node {
+            id,
+            depth,
+            solved: board.state == BoardState::Solved,
+            grid: board.grid,
+        });
+
+        id
+    }
+}
+
+// Explore every board reachable from the given board using a breadth-first
+// search and return the state graph. Each node is a distinct board, with ids
+// assigned in the order the boards are discovered, so that the depth of a node
+// is the minimum number of moves needed to reach it. Since every move can be
+// undone, the edges are undirected and each is listed once, from the node with
+// the lower id. Unlike the solver, the search continues past solved boards. If
+// the number of nodes reaches max_nodes or the cancellation is triggered, the
+// search stops and the graph is marked as truncated.
+pub fn explore(
+    board: &Board,
+    max_nodes: usize,
+    cancellation: &Cancellation,
+) -> Result<Graph, SolverError> {
+    let mut root = board.clone();
+    root.moves.clear();
+
+    root.change_state(BoardState::Solving)?;
+    let _board_is_already_solved = root.change_state(BoardState::Solved).is_ok();
+
+    let mut graph = Graph::default();
+
+    let mut ids = HashMap::from([(root.key(), graph.add_node(&root, 0))]);
+
+    let mut queue = VecDeque::from([(root, 0)]);
+
+    while let Some((mut board, source)) = queue.pop_front() {
+        if cancellation.is_cancelled() {
+            graph.truncated = true;
+            break;
+        }
+
+        let depth = graph.nodes[source].depth;
+
+        // A solved board stays solved when one of its blocks is moved, so the
+        // state is reset before each move to detect which children are solved
+        board.state = BoardState::Solving;
+
+        let next_moves = board.get_next_moves();
+
+        for (block_idx, moves) in next_moves.into_iter().enumerate() {
+            for move_ in moves {
+                board.move_block_unchecked(block_idx, move_.row_diff, move_.col_diff);
+
+                let target = match ids.entry(board.key()) {
+                    Entry::Occupied(entry) => Some(*entry.get()),
+                    Entry::Vacant(entry) => {
+                        if graph.nodes.len() < max_nodes {
+                            let target = graph.add_node(&board, depth + 1);
+
+                            let mut next_board = board.clone();
+                            next_board.moves.clear();
+
+                            queue.push_back((next_board, target));
+
+                            Some(*entry.insert(target))
+                        } else {
+                            graph.truncated = true;
+
+                            None
+                        }
+                    }
+                };
+
+                if let Some(target) = target.filter(|target| source < *target) {
+                    graph.edges.push(Edge {
+                        source,
+                        target,
+                        block_idx,
+                        row_diff: move_.row_diff,
+                        col_diff: move_.col_diff,
+                    });
+                }
+
+                board.undo_move_unchecked();
+                board.state = BoardState::Solving;
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game::blocks::{Block, Positioned as PositionedBlock};
+
+    fn easy_board() -> Board {
+        let blocks = [
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 0).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_TWO, 0, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 0, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 1, 3).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 2, 2).unwrap(),
+            PositionedBlock::new(Block::TWO_BY_ONE, 2, 3).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 1).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 3, 2).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 0).unwrap(),
+            PositionedBlock::new(Block::ONE_BY_ONE, 4, 3).unwrap(),
+        ];
+
+        let mut board = Board::default();
+
+        for block in &blocks {
+            board.add_block(block.clone()).unwrap();
+        }
+
+        board
+    }
+
+    #[test]
+    fn test_explore() {
+        let graph = explore(&easy_board(), usize::MAX, &Cancellation::default()).unwrap();
+
+        assert!(!graph.truncated);
+        assert_eq!(graph.nodes[0].depth, 0);
+
+        let min_solved_depth = graph
+            .nodes
+            .iter()
+            .filter(|node| node.solved)
+            .map(|node| node.depth)
+            .min();
+
+        assert_eq!(min_solved_depth, Some(17));
+
+        for edge in &graph.edges {
+            assert!(edge.source < edge.target);
+            assert!(edge.target < graph.nodes.len());
+
+            let depth_diff = graph.nodes[edge.target]
+                .depth
+                .abs_diff(graph.nodes[edge.source].depth);
+
+            assert!(depth_diff <= 1);
+        }
+    }
+
+    #[test]
+    fn test_explore_truncated() {
+        let graph = explore(&easy_board(), 10, &Cancellation::default()).unwrap();
+
+        assert!(graph.truncated);
+        assert_eq!(graph.nodes.len(), 10);
+    }
+}
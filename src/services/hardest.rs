@@ -0,0 +1,88 @@
+use crate::errors::solver::Error as SolverError;
+use crate::models::game::{
+    blocks::Block,
+    board::{Board, Goal},
+};
+use crate::services::solver::{self, Cancellation};
+
+pub const DEFAULT_MAX_BOARDS: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct Hardest {
+    pub distance: usize,
+    pub boards: Vec<Board>,
+}
+
+// Search for the starting configurations of the given blocks which need the
+// most moves to be solved, which are the boards of the last level of a
+// retrograde breadth-first search from the goal boards of the blocks. At most
+// max_boards of the hardest boards are returned, each ready to solve.
+pub fn search(
+    blocks: &[Block],
+    goal: Goal,
+    max_boards: usize,
+    cancellation: &Cancellation,
+) -> Result<Hardest, SolverError> {
+    let (distance, level) = solver::retrograde_bfs(blocks, goal, cancellation, |_, _| {})?;
+
+    let mut boards = vec![];
+
+    for hardest_board in level.into_iter().take(max_boards) {
+        let mut board = Board {
+            goal,
+            ..Board::default()
+        };
+
+        for positioned_block in hardest_board.blocks {
+            board.add_block(positioned_block)?;
+        }
+
+        boards.push(board);
+    }
+
+    Ok(Hardest { distance, boards })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::board::Error as BoardError;
+    use crate::services::solver::{solve, Options};
+
+    #[test]
+    fn test_search() {
+        let blocks = [
+            Block::TWO_BY_TWO,
+            Block::TWO_BY_ONE,
+            Block::TWO_BY_ONE,
+            Block::TWO_BY_ONE,
+            Block::TWO_BY_ONE,
+            Block::ONE_BY_TWO,
+            Block::ONE_BY_ONE,
+            Block::ONE_BY_ONE,
+            Block::ONE_BY_ONE,
+            Block::ONE_BY_ONE,
+        ];
+
+        let hardest = search(&blocks, Goal::default(), 2, &Cancellation::default()).unwrap();
+
+        assert!(hardest.distance >= 81);
+        assert!(!hardest.boards.is_empty() && hardest.boards.len() <= 2);
+
+        for board in &hardest.boards {
+            let moves = solve(board, &Options::default()).unwrap().unwrap();
+
+            assert_eq!(moves.len(), hardest.distance);
+        }
+    }
+
+    #[test]
+    fn test_invalid_blocks() {
+        let blocks = [Block::TWO_BY_TWO, Block::ONE_BY_ONE];
+
+        assert_eq!(
+            search(&blocks, Goal::default(), 1, &Cancellation::default()).unwrap_err(),
+            SolverError::BoardError(BoardError::BoardStateInvalid)
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use sha2::{Digest, Sha256};
+use std::{fmt::Write, time::Duration};
+
+// Seconds for which the response to a request made with an idempotency key is
+// replayed to retries of the request, by default
+const DEFAULT_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+// Seconds after which a key whose request has not been handled is freed, by
+// default, in case the request was never completed, e.g. because the client
+// disconnected or the server restarted while handling it
+const DEFAULT_LEASE_SECS: u64 = 2 * 60;
+
+// Response persisted for an idempotency key, to be replayed to retries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+// Request made with an idempotency key, whose response is None until the
+// request has been handled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub method: String,
+    pub path: String,
+    // fingerprint of the request's query string and body, as by fingerprint
+    pub fingerprint: String,
+    pub response: Option<StoredResponse>,
+}
+
+// What to do with a retry of a request made with an idempotency key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Replay(StoredResponse),
+    // the first request made with the key is still being handled
+    InProgress,
+    // the key was first used for a request to another endpoint, or with
+    // another query string or body
+    Mismatch,
+}
+
+impl Record {
+    pub fn outcome(self, method: &str, path: &str, fingerprint: &str) -> Outcome {
+        if self.method != method || self.path != path || self.fingerprint != fingerprint {
+            return Outcome::Mismatch;
+        }
+
+        match self.response {
+            Some(response) => Outcome::Replay(response),
+            None => Outcome::InProgress,
+        }
+    }
+}
+
+// Time for which responses are replayed, set in seconds using the
+// IDEMPOTENCY_WINDOW environment variable
+pub fn window() -> Duration {
+    Duration::from_secs(
+        dotenvy::var("IDEMPOTENCY_WINDOW")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_WINDOW_SECS),
+    )
+}
+
+// Time after which a key whose request has not been handled is freed, set in
+// seconds using the IDEMPOTENCY_LEASE environment variable
+pub fn lease() -> Duration {
+    Duration::from_secs(
+        dotenvy::var("IDEMPOTENCY_LEASE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_LEASE_SECS),
+    )
+}
+
+// Hex-encoded SHA-256 hash of the request's query string and body, so that a
+// key reused for a request with another payload is not replayed. The length of
+// the query string is hashed first, so that the query string and body cannot
+// run into each other.
+pub fn fingerprint(query: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+
+    hasher.update((query.len() as u64).to_le_bytes());
+    hasher.update(query.as_bytes());
+    hasher.update(body);
+
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_outcomes() {
+        let response = StoredResponse {
+            status: 200,
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: b"{}".to_vec(),
+        };
+
+        let fingerprint = fingerprint("randomize=true", b"{}");
+
+        let record = Record {
+            method: "POST".to_string(),
+            path: "/api/board".to_string(),
+            fingerprint: fingerprint.clone(),
+            response: Some(response.clone()),
+        };
+
+        assert_eq!(
+            record.clone().outcome("POST", "/api/board", &fingerprint),
+            Outcome::Replay(response)
+        );
+        assert_eq!(
+            record.clone().outcome("PUT", "/api/board", &fingerprint),
+            Outcome::Mismatch
+        );
+        assert_eq!(
+            record
+                .clone()
+                .outcome("POST", "/api/board/1/solve", &fingerprint),
+            Outcome::Mismatch
+        );
+        assert_eq!(
+            record
+                .clone()
+                .outcome("POST", "/api/board", &super::fingerprint("", b"{}")),
+            Outcome::Mismatch
+        );
+
+        let record = Record {
+            response: None,
+            ..record
+        };
+
+        assert_eq!(
+            record.outcome("POST", "/api/board", &fingerprint),
+            Outcome::InProgress
+        );
+    }
+
+    #[test]
+    fn fingerprints() {
+        assert_eq!(fingerprint("a", b"b"), fingerprint("a", b"b"));
+        assert_eq!(fingerprint("a", b"b").len(), 64);
+        assert_ne!(fingerprint("a", b"b"), fingerprint("a", b"c"));
+        assert_ne!(fingerprint("ab", b""), fingerprint("a", b"b"));
+    }
+}
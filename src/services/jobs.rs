@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::errors::solver::Error as SolverError;
+use crate::models::{
+    api::request::SolveParams,
+    game::{board::Board, moves::FlatBoardMove},
+};
+use crate::repositories::boards::{get as get_board, set_difficulty};
+use crate::repositories::jobs::{get_unfinished, update as update_job};
+use crate::repositories::solutions::{create as create_solution, get as get_solution};
+use crate::services::{analysis, db::Pool as DbPool, solver};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[schema(as = JobStatus)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Pending,
+    Running,
+    Solved,
+    UnableToSolve,
+    BudgetExceeded,
+    DepthExceeded,
+    TimedOut,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: i32,
+    pub board_id: i32,
+    pub status: Status,
+    pub params: SolveParams,
+    pub moves: Option<Vec<FlatBoardMove>>,
+}
+
+impl Job {
+    pub fn new(board_id: i32, params: SolveParams) -> Self {
+        Self {
+            id: 0,
+            board_id,
+            status: Status::Pending,
+            params,
+            moves: None,
+        }
+    }
+}
+
+// Record the difficulty of the board from its solution, if the solution is
+// guaranteed to minimize the number of moves, starts from the board's starting
+// position, and the board's difficulty is not yet known
+pub fn record_difficulty(
+    board: &Board,
+    options: &solver::Options,
+    optimal: bool,
+    moves: &[FlatBoardMove],
+    pool: &DbPool,
+) {
+    if optimal
+        && options.objective == solver::Objective::Moves
+        && board.moves.is_empty()
+        && board.difficulty.is_none()
+    {
+        let _difficulty_recorded =
+            set_difficulty(board, analysis::difficulty(board, moves), pool).is_ok();
+    }
+}
+
+// Find the status and moves of a finished job, using the cached solution for
+// the board if there is one, and caching the solution otherwise. Only solutions
+// minimizing the number of moves without a maximum depth are cached, and only
+// if they are guaranteed to be optimal. Boards with constrained blocks are
+// never cached, since their hashes do not record how each block is constrained.
+fn find_solution(job: &Job, pool: &DbPool) -> (Status, Option<Vec<FlatBoardMove>>) {
+    let Ok(board) = get_board(job.board_id, pool) else {
+        return (Status::Failed, None);
+    };
+
+    let options = job.params.options();
+    let use_cache = options.objective == solver::Objective::Moves
+        && options.max_depth.is_none()
+        && !board.has_constrained_blocks()
+        && board.targets.is_empty();
+
+    let cached_solution = use_cache.then(|| get_solution(&board, pool).ok()).flatten();
+
+    let maybe_moves = match cached_solution {
+        Some(cached_solution) => cached_solution,
+        None => match solver::solve(&board, &options) {
+            Ok(maybe_moves) => {
+                if use_cache && options.is_optimal() {
+                    let _solution_cached =
+                        create_solution(&board, maybe_moves.clone(), pool).is_ok();
+                }
+
+                maybe_moves
+            }
+            Err(SolverError::BudgetExceeded) => return (Status::BudgetExceeded, None),
+            Err(SolverError::DepthExceeded) => return (Status::DepthExceeded, None),
+            Err(SolverError::TimedOut) => return (Status::TimedOut, None),
+            Err(SolverError::BoardError(_) | SolverError::Cancelled) => {
+                return (Status::Failed, None)
+            }
+        },
+    };
+
+    match maybe_moves {
+        Some(moves) => {
+            record_difficulty(&board, &options, options.is_optimal(), &moves, pool);
+
+            (Status::Solved, Some(moves))
+        }
+        None => (Status::UnableToSolve, None),
+    }
+}
+
+fn run(mut job: Job, pool: &DbPool) {
+    tracing::info!("Running solve job {} for board {}", job.id, job.board_id);
+
+    job.status = Status::Running;
+
+    if let Err(err) = update_job(&job, pool) {
+        tracing::error!("DieselError: {}", err);
+        return;
+    }
+
+    (job.status, job.moves) = find_solution(&job, pool);
+
+    tracing::info!("Solve job {} finished with status {:?}", job.id, job.status);
+
+    if let Err(err) = update_job(&job, pool) {
+        tracing::error!("DieselError: {}", err);
+    }
+}
+
+// Run the job on tokio's blocking thread pool so that the solver does not
+// block the async runtime
+pub fn spawn(job: Job, pool: &DbPool) {
+    let pool = pool.clone();
+
+    tokio::task::spawn_blocking(move || run(job, &pool));
+}
+
+// Restart the jobs which were pending or running when the server last stopped
+pub fn resume(pool: &DbPool) {
+    match get_unfinished(pool) {
+        Ok(jobs) => {
+            tracing::info!("Resuming {} unfinished solve jobs", jobs.len());
+
+            for job in jobs {
+                spawn(job, pool);
+            }
+        }
+        Err(err) => tracing::error!("DieselError: {}", err),
+    }
+}
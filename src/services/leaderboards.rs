@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::errors::board::Error as BoardError;
+use crate::models::game::{
+    board::{Board, State as BoardState},
+    layouts::Layout,
+};
+
+// Most characters in a player's name
+const MAX_PLAYER_LEN: usize = 50;
+
+// Fewest milliseconds a move of a submitted solve may take on average, below
+// which the moves cannot have been made by hand
+const MIN_MOVE_DURATION_MS: u64 = 100;
+
+// Solve of a puzzle submitted to the puzzle's leaderboard, whose number of
+// moves and duration are taken from the board it was solved on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub id: i32,
+    pub puzzle: String,
+    pub player: String,
+    pub num_moves: usize,
+    pub duration_ms: u64,
+    // time, in milliseconds since the Unix epoch, the score was submitted
+    pub created_at: u64,
+}
+
+// Order of the scores on a leaderboard, where ties are broken by the other
+// measure, and then by which score was submitted first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Ranking {
+    #[default]
+    FewestMoves,
+    Fastest,
+}
+
+// Whether the player's name is not blank and has at most MAX_PLAYER_LEN
+// characters
+pub fn is_valid_player(player: &str) -> bool {
+    !player.trim().is_empty() && player.chars().count() <= MAX_PLAYER_LEN
+}
+
+// Number of moves made to solve the board, and milliseconds between the first
+// and last of them, as recorded when the board was saved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solve {
+    pub num_moves: usize,
+    pub duration_ms: u64,
+}
+
+// Solve of the board, which must be solved and have been started from the
+// layout, with the layout's goal. If the board is not solved, the
+// SolutionInvalid error is returned, if it was started from another layout,
+// the PuzzleMismatch error is returned, and if its moves took less than
+// MIN_MOVE_DURATION_MS on average, the DurationInvalid error is returned.
+pub fn verify(layout: Layout, board: &Board) -> Result<Solve, BoardError> {
+    let (BoardState::Solved, Some(started_solving_at), Some(finished_at)) =
+        (board.state, board.started_solving_at, board.finished_at)
+    else {
+        return Err(BoardError::SolutionInvalid);
+    };
+
+    let start = board.at_move(0)?;
+    let puzzle = layout.board();
+
+    if start.key() != puzzle.key()
+        || start.goal != puzzle.goal
+        || start.walls != puzzle.walls
+        || start.targets != puzzle.targets
+    {
+        return Err(BoardError::PuzzleMismatch);
+    }
+
+    let num_moves = board.moves.len();
+    let duration_ms = finished_at.saturating_sub(started_solving_at);
+
+    if duration_ms < MIN_MOVE_DURATION_MS.saturating_mul(num_moves as u64) {
+        return Err(BoardError::DurationInvalid);
+    }
+
+    Ok(Solve {
+        num_moves,
+        duration_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::game::layouts;
+    use crate::services::solver::{self, Options};
+
+    #[test]
+    fn verifies_solves() {
+        let layout = layouts::find("Pennant").unwrap();
+        let moves = solver::solve(&layout.board(), &Options::default())
+            .unwrap()
+            .unwrap();
+
+        let mut board = layout.board();
+        board.apply_moves(&moves[..moves.len() - 1]).unwrap();
+        board.update_timer(1000);
+
+        assert_eq!(verify(layout, &board), Err(BoardError::SolutionInvalid));
+
+        board.apply_moves(&moves[moves.len() - 1..]).unwrap();
+        board.update_timer(1000 + 100 * moves.len() as u64);
+
+        assert_eq!(
+            verify(layout, &board),
+            Ok(Solve {
+                num_moves: layout.optimal_moves,
+                duration_ms: 100 * moves.len() as u64,
+            })
+        );
+
+        board.finished_at = Some(1000 + 100 * moves.len() as u64 - 1);
+
+        assert_eq!(verify(layout, &board), Err(BoardError::DurationInvalid));
+
+        let other_layout = layouts::find("Heng Dao Li Ma").unwrap();
+
+        assert_eq!(
+            verify(other_layout, &board),
+            Err(BoardError::PuzzleMismatch)
+        );
+    }
+
+    #[test]
+    fn validates_players() {
+        assert!(is_valid_player("Sam"));
+        assert!(!is_valid_player(""));
+        assert!(!is_valid_player("   "));
+        assert!(!is_valid_player(&"a".repeat(MAX_PLAYER_LEN + 1)));
+    }
+}
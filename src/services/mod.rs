@@ -1,3 +1,16 @@
+pub use klotski_core::{analysis, ordering, randomizer, solver};
+
+pub mod api_keys;
+pub mod classics;
+pub mod daily;
 pub mod db;
-pub mod randomizer;
-pub mod solver;
+pub mod distances;
+pub mod expiry;
+pub mod explorer;
+pub mod hardest;
+pub mod idempotency;
+pub mod jobs;
+pub mod leaderboards;
+pub mod rate_limits;
+pub mod sessions;
+pub mod shares;
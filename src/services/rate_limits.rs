@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// Requests per minute allowed for each client by default, and for expensive
+// requests such as solving and creating boards
+const DEFAULT_LIMIT: u32 = 120;
+const DEFAULT_EXPENSIVE_LIMIT: u32 = 10;
+
+// Most buckets kept before the buckets which have been refilled are forgotten,
+// after which the least recently used buckets are forgotten if there are still
+// too many, EVICTED_BUCKETS at a time
+const MAX_BUCKETS: usize = 10_000;
+const EVICTED_BUCKETS: usize = MAX_BUCKETS / 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tier {
+    Default,
+    Expensive,
+}
+
+// Client whose requests are counted together, which is either the address
+// requests are made from or the API key they are made with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Client {
+    Address(IpAddr),
+    ApiKey(i32),
+}
+
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    // Tokens in the bucket at the given time, without refilling it, so that
+    // the bucket keeps the time it was last used
+    fn tokens_at(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+
+        (self.tokens + elapsed.as_secs_f64() * self.capacity / 60.0).min(self.capacity)
+    }
+
+    fn refill(&mut self, now: Instant) {
+        self.tokens = self.tokens_at(now);
+        self.updated_at = now;
+    }
+}
+
+// Token bucket per client and tier, where each bucket holds up to a minute's
+// worth of requests and is refilled continuously at the limit per minute. A
+// limit of 0 leaves the requests unlimited.
+#[derive(Debug, Clone)]
+pub struct Limiter {
+    default_limit: u32,
+    expensive_limit: u32,
+    // header in which the proxy in front of the API gives the client's address,
+    // which is only trusted when set, since clients can set any header
+    client_ip_header: Option<String>,
+    buckets: Arc<Mutex<HashMap<(Client, Tier), Bucket>>>,
+}
+
+impl Limiter {
+    pub fn new(default_limit: u32, expensive_limit: u32) -> Self {
+        Self {
+            default_limit,
+            expensive_limit,
+            client_ip_header: None,
+            buckets: Arc::default(),
+        }
+    }
+
+    // Limits set using the RATE_LIMIT and EXPENSIVE_RATE_LIMIT environment
+    // variables, in requests per minute, and the header giving the client's
+    // address set using the CLIENT_IP_HEADER environment variable, e.g.
+    // Fly-Client-IP
+    pub fn from_env() -> Self {
+        let limit = |name: &str, default: u32| {
+            dotenvy::var(name)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            client_ip_header: dotenvy::var("CLIENT_IP_HEADER")
+                .ok()
+                .filter(|header| !header.is_empty()),
+            ..Self::new(
+                limit("RATE_LIMIT", DEFAULT_LIMIT),
+                limit("EXPENSIVE_RATE_LIMIT", DEFAULT_EXPENSIVE_LIMIT),
+            )
+        }
+    }
+
+    pub fn client_ip_header(&self) -> Option<&str> {
+        self.client_ip_header.as_deref()
+    }
+
+    pub fn limit(&self, tier: Tier) -> u32 {
+        match tier {
+            Tier::Default => self.default_limit,
+            Tier::Expensive => self.expensive_limit,
+        }
+    }
+
+    // Take a token from the client's bucket for the tier, or return how long
+    // the client must wait until a token is available, where the limit is
+    // usually the tier's limit but may be set for each client
+    pub fn acquire(
+        &self,
+        client: Client,
+        tier: Tier,
+        limit: u32,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        if limit == 0 {
+            return Ok(());
+        }
+
+        let capacity = f64::from(limit);
+
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_BUCKETS {
+            buckets.retain(|_, bucket| bucket.tokens_at(now) < bucket.capacity);
+        }
+
+        if buckets.len() >= MAX_BUCKETS {
+            let mut keys: Vec<_> = buckets
+                .iter()
+                .map(|(key, bucket)| (bucket.updated_at, *key))
+                .collect();
+
+            keys.select_nth_unstable_by_key(EVICTED_BUCKETS - 1, |(updated_at, _)| *updated_at);
+
+            for (_, key) in &keys[..EVICTED_BUCKETS] {
+                buckets.remove(key);
+            }
+        }
+
+        let bucket = buckets.entry((client, tier)).or_insert(Bucket {
+            capacity,
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        // the client's limit may have changed since the bucket was created
+        bucket.capacity = capacity;
+        bucket.refill(now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+
+        Err(Duration::from_secs_f64(
+            (1.0 - bucket.tokens) * 60.0 / capacity,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    const CLIENT: Client = Client::Address(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    const OTHER_CLIENT: Client = Client::Address(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    const API_KEY_CLIENT: Client = Client::ApiKey(1);
+
+    fn acquire(
+        limiter: &Limiter,
+        client: Client,
+        tier: Tier,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        limiter.acquire(client, tier, limiter.limit(tier), now)
+    }
+
+    #[test]
+    fn limits_each_client_and_tier() {
+        let limiter = Limiter::new(60, 2);
+        let now = Instant::now();
+
+        assert!(acquire(&limiter, CLIENT, Tier::Expensive, now).is_ok());
+        assert!(acquire(&limiter, CLIENT, Tier::Expensive, now).is_ok());
+        assert_eq!(
+            acquire(&limiter, CLIENT, Tier::Expensive, now),
+            Err(Duration::from_secs(30))
+        );
+
+        assert!(acquire(&limiter, CLIENT, Tier::Default, now).is_ok());
+        assert!(acquire(&limiter, OTHER_CLIENT, Tier::Expensive, now).is_ok());
+        assert!(acquire(&limiter, API_KEY_CLIENT, Tier::Expensive, now).is_ok());
+    }
+
+    #[test]
+    fn refills_over_time() {
+        let limiter = Limiter::new(60, 0);
+        let now = Instant::now();
+
+        for _ in 0..60 {
+            assert!(acquire(&limiter, CLIENT, Tier::Default, now).is_ok());
+        }
+
+        assert!(acquire(&limiter, CLIENT, Tier::Default, now).is_err());
+        assert!(acquire(
+            &limiter,
+            CLIENT,
+            Tier::Default,
+            now + Duration::from_secs(1)
+        )
+        .is_ok());
+
+        for _ in 0..100 {
+            assert!(acquire(&limiter, CLIENT, Tier::Expensive, now).is_ok());
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_buckets() {
+        let limiter = Limiter::new(60, 0);
+        let now = Instant::now();
+
+        for id in 0..MAX_BUCKETS {
+            let client = Client::ApiKey(i32::try_from(id).unwrap());
+            let later = now + Duration::from_micros(u64::try_from(id).unwrap());
+
+            assert!(acquire(&limiter, client, Tier::Default, later).is_ok());
+        }
+
+        let later = now + Duration::from_micros(u64::try_from(MAX_BUCKETS).unwrap());
+
+        assert!(acquire(&limiter, CLIENT, Tier::Default, later).is_ok());
+
+        let buckets = limiter.buckets.lock().unwrap();
+
+        assert_eq!(buckets.len(), MAX_BUCKETS - EVICTED_BUCKETS + 1);
+        assert!(!buckets.contains_key(&(Client::ApiKey(0), Tier::Default)));
+        assert!(buckets.contains_key(&(
+            Client::ApiKey(i32::try_from(MAX_BUCKETS - 1).unwrap()),
+            Tier::Default
+        )));
+    }
+
+    #[test]
+    fn limits_each_client_by_its_own_limit() {
+        let limiter = Limiter::new(1, 1);
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            assert!(limiter
+                .acquire(API_KEY_CLIENT, Tier::Default, 5, now)
+                .is_ok());
+        }
+
+        assert_eq!(
+            limiter.acquire(API_KEY_CLIENT, Tier::Default, 5, now),
+            Err(Duration::from_secs(12))
+        );
+    }
+}
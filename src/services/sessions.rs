@@ -0,0 +1,90 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+const CHANNEL_CAPACITY: usize = 64;
+
+// Keeps one broadcast channel per board so that every connection to a board
+// receives the updates caused by the other connections. Events are serialized
+// before being broadcast so that each connection can forward them as is.
+#[derive(Debug, Clone, Default)]
+pub struct Manager {
+    channels: Arc<Mutex<HashMap<i32, broadcast::Sender<String>>>>,
+}
+
+impl Manager {
+    pub fn join(&self, board_id: i32) -> broadcast::Receiver<String> {
+        let mut channels = self.channels.lock().unwrap();
+
+        channels
+            .entry(board_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    // Remove the channel for the board once its last connection has left.
+    // The receiver of the leaving connection must be dropped beforehand.
+    pub fn leave(&self, board_id: i32) {
+        let mut channels = self.channels.lock().unwrap();
+
+        if channels
+            .get(&board_id)
+            .is_some_and(|sender| sender.receiver_count() == 0)
+        {
+            channels.remove(&board_id);
+        }
+    }
+
+    pub fn publish<T: Serialize>(&self, board_id: i32, event: &T) {
+        let Ok(message) = serde_json::to_string(event) else {
+            tracing::error!("Unable to serialize event for board {}", board_id);
+            return;
+        };
+
+        if let Some(sender) = self.channels.lock().unwrap().get(&board_id) {
+            let _event_sent = sender.send(message).is_ok();
+        }
+    }
+
+    pub fn connections(&self, board_id: i32) -> usize {
+        self.channels
+            .lock()
+            .unwrap()
+            .get(&board_id)
+            .map_or(0, broadcast::Sender::receiver_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manager() {
+        let manager = Manager::default();
+
+        let mut first = manager.join(1);
+        let mut second = manager.join(1);
+        let mut other = manager.join(2);
+
+        assert_eq!(manager.connections(1), 2);
+
+        manager.publish(1, &"moved");
+
+        assert_eq!(first.try_recv().unwrap(), "\"moved\"");
+        assert_eq!(second.try_recv().unwrap(), "\"moved\"");
+        assert!(other.try_recv().is_err());
+
+        drop(first);
+        manager.leave(1);
+
+        assert_eq!(manager.connections(1), 1);
+
+        drop(second);
+        manager.leave(1);
+
+        assert_eq!(manager.connections(1), 0);
+        assert!(!manager.channels.lock().unwrap().contains_key(&1));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::services::randomizer;
+
+// Number of characters in a share code, which gives 62^8 possible codes
+const CODE_LEN: usize = 8;
+
+// Characters of share codes, which are safe to use in URLs as is
+const CODE_ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+// Most codes generated when sharing a board, for one which is not yet taken
+pub const MAX_CODE_ATTEMPTS: usize = 5;
+
+// Starting layout of a board shared by its code, from which a new board is
+// created for each visitor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub code: String,
+    pub notation: String,
+    pub name: Option<String>,
+    // time, in milliseconds since the Unix epoch, the board was shared
+    pub created_at: u64,
+}
+
+// Share code of the given number, whose CODE_LEN characters are its lowest
+// digits in base 62
+#[allow(clippy::cast_possible_truncation)]
+pub fn code(mut value: u64) -> String {
+    let mut code = String::with_capacity(CODE_LEN);
+
+    for _ in 0..CODE_LEN {
+        code.push(char::from(CODE_ALPHABET[(value % 62) as usize]));
+        value /= 62;
+    }
+
+    code
+}
+
+// Share code drawn at random, which is not guaranteed to be free
+pub fn random_code() -> String {
+    code(randomizer::random_seed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes() {
+        assert_eq!(code(0), "00000000");
+        assert_eq!(code(61), "z0000000");
+        assert_eq!(code(62), "01000000");
+        assert_ne!(code(1), code(62));
+
+        let code = random_code();
+        assert_eq!(code.len(), CODE_LEN);
+        assert!(code.bytes().all(|byte| byte.is_ascii_alphanumeric()));
+    }
+}